@@ -1,4 +1,5 @@
 use crate::config::get_individual_spacing;
+use crate::config::BarDock;
 use crate::config::KomobarConfig;
 use crate::config::KomobarTheme;
 use crate::config::MonitorConfigOrIndex;
@@ -6,17 +7,19 @@ use crate::config::Position;
 use crate::config::PositionConfig;
 use crate::komorebi::Komorebi;
 use crate::komorebi::KomorebiNotificationState;
+use crate::layout::Section;
+use crate::layout::SectionWidget;
 use crate::process_hwnd;
 use crate::render::Color32Ext;
 use crate::render::Grouping;
 use crate::render::RenderConfig;
 use crate::render::RenderExt;
-use crate::widget::BarWidget;
 use crate::widget::WidgetConfig;
 use crate::KomorebiEvent;
 use crate::BAR_HEIGHT;
 use crate::DEFAULT_PADDING;
 use crate::MAX_LABEL_WIDTH;
+use crate::MONITOR_BOTTOM;
 use crate::MONITOR_LEFT;
 use crate::MONITOR_RIGHT;
 use crate::MONITOR_TOP;
@@ -51,6 +54,7 @@ use komorebi_themes::catppuccin_egui;
 use komorebi_themes::Base16Value;
 use komorebi_themes::Catppuccin;
 use komorebi_themes::CatppuccinValue;
+use komorebi_themes::Palette;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -65,11 +69,13 @@ pub struct Komobar {
     pub config: KomobarConfig,
     pub render_config: Rc<RefCell<RenderConfig>>,
     pub komorebi_notification_state: Option<Rc<RefCell<KomorebiNotificationState>>>,
-    pub left_widgets: Vec<Box<dyn BarWidget>>,
-    pub center_widgets: Vec<Box<dyn BarWidget>>,
-    pub right_widgets: Vec<Box<dyn BarWidget>>,
+    pub left_widgets: Section,
+    pub center_widgets: Section,
+    pub right_widgets: Section,
     pub rx_gui: Receiver<KomorebiEvent>,
     pub rx_config: Receiver<KomobarConfig>,
+    /// Signals that the palette of a custom theme has changed
+    pub rx_theme: Receiver<()>,
     pub bg_color: Rc<RefCell<Color32>>,
     pub bg_color_with_alpha: Rc<RefCell<Color32>>,
     pub scale_factor: f32,
@@ -78,6 +84,12 @@ pub struct Komobar {
     applied_theme_on_first_frame: bool,
 }
 
+/// Sets the colours used by widgets to highlight crossed thresholds
+fn set_threshold_colours(visuals: &mut Visuals, warning: Color32, error: Color32) {
+    visuals.warn_fg_color = warning;
+    visuals.error_fg_color = error;
+}
+
 pub fn apply_theme(
     ctx: &Context,
     theme: KomobarTheme,
@@ -91,64 +103,74 @@ pub fn apply_theme(
         KomobarTheme::Catppuccin {
             name: catppuccin,
             accent: catppuccin_value,
-        } => match catppuccin {
-            Catppuccin::Frappe => {
-                catppuccin_egui::set_theme(ctx, catppuccin_egui::FRAPPE);
-                let catppuccin_value = catppuccin_value.unwrap_or_default();
-                let accent = catppuccin_value.color32(catppuccin.as_theme());
-
-                ctx.style_mut(|style| {
-                    style.visuals.selection.stroke.color = accent;
-                    style.visuals.widgets.hovered.fg_stroke.color = accent;
-                    style.visuals.widgets.active.fg_stroke.color = accent;
-                    style.visuals.override_text_color = None;
-                });
+        } => {
+            match catppuccin {
+                Catppuccin::Frappe => {
+                    catppuccin_egui::set_theme(ctx, catppuccin_egui::FRAPPE);
+                    let catppuccin_value = catppuccin_value.unwrap_or_default();
+                    let accent = catppuccin_value.color32(catppuccin.as_theme());
+
+                    ctx.style_mut(|style| {
+                        style.visuals.selection.stroke.color = accent;
+                        style.visuals.widgets.hovered.fg_stroke.color = accent;
+                        style.visuals.widgets.active.fg_stroke.color = accent;
+                        style.visuals.override_text_color = None;
+                    });
 
-                bg_color.replace(catppuccin_egui::FRAPPE.base);
-            }
-            Catppuccin::Latte => {
-                catppuccin_egui::set_theme(ctx, catppuccin_egui::LATTE);
-                let catppuccin_value = catppuccin_value.unwrap_or_default();
-                let accent = catppuccin_value.color32(catppuccin.as_theme());
-
-                ctx.style_mut(|style| {
-                    style.visuals.selection.stroke.color = accent;
-                    style.visuals.widgets.hovered.fg_stroke.color = accent;
-                    style.visuals.widgets.active.fg_stroke.color = accent;
-                    style.visuals.override_text_color = None;
-                });
+                    bg_color.replace(catppuccin_egui::FRAPPE.base);
+                }
+                Catppuccin::Latte => {
+                    catppuccin_egui::set_theme(ctx, catppuccin_egui::LATTE);
+                    let catppuccin_value = catppuccin_value.unwrap_or_default();
+                    let accent = catppuccin_value.color32(catppuccin.as_theme());
+
+                    ctx.style_mut(|style| {
+                        style.visuals.selection.stroke.color = accent;
+                        style.visuals.widgets.hovered.fg_stroke.color = accent;
+                        style.visuals.widgets.active.fg_stroke.color = accent;
+                        style.visuals.override_text_color = None;
+                    });
 
-                bg_color.replace(catppuccin_egui::LATTE.base);
-            }
-            Catppuccin::Macchiato => {
-                catppuccin_egui::set_theme(ctx, catppuccin_egui::MACCHIATO);
-                let catppuccin_value = catppuccin_value.unwrap_or_default();
-                let accent = catppuccin_value.color32(catppuccin.as_theme());
-
-                ctx.style_mut(|style| {
-                    style.visuals.selection.stroke.color = accent;
-                    style.visuals.widgets.hovered.fg_stroke.color = accent;
-                    style.visuals.widgets.active.fg_stroke.color = accent;
-                    style.visuals.override_text_color = None;
-                });
+                    bg_color.replace(catppuccin_egui::LATTE.base);
+                }
+                Catppuccin::Macchiato => {
+                    catppuccin_egui::set_theme(ctx, catppuccin_egui::MACCHIATO);
+                    let catppuccin_value = catppuccin_value.unwrap_or_default();
+                    let accent = catppuccin_value.color32(catppuccin.as_theme());
+
+                    ctx.style_mut(|style| {
+                        style.visuals.selection.stroke.color = accent;
+                        style.visuals.widgets.hovered.fg_stroke.color = accent;
+                        style.visuals.widgets.active.fg_stroke.color = accent;
+                        style.visuals.override_text_color = None;
+                    });
 
-                bg_color.replace(catppuccin_egui::MACCHIATO.base);
-            }
-            Catppuccin::Mocha => {
-                catppuccin_egui::set_theme(ctx, catppuccin_egui::MOCHA);
-                let catppuccin_value = catppuccin_value.unwrap_or_default();
-                let accent = catppuccin_value.color32(catppuccin.as_theme());
-
-                ctx.style_mut(|style| {
-                    style.visuals.selection.stroke.color = accent;
-                    style.visuals.widgets.hovered.fg_stroke.color = accent;
-                    style.visuals.widgets.active.fg_stroke.color = accent;
-                    style.visuals.override_text_color = None;
-                });
+                    bg_color.replace(catppuccin_egui::MACCHIATO.base);
+                }
+                Catppuccin::Mocha => {
+                    catppuccin_egui::set_theme(ctx, catppuccin_egui::MOCHA);
+                    let catppuccin_value = catppuccin_value.unwrap_or_default();
+                    let accent = catppuccin_value.color32(catppuccin.as_theme());
+
+                    ctx.style_mut(|style| {
+                        style.visuals.selection.stroke.color = accent;
+                        style.visuals.widgets.hovered.fg_stroke.color = accent;
+                        style.visuals.widgets.active.fg_stroke.color = accent;
+                        style.visuals.override_text_color = None;
+                    });
 
-                bg_color.replace(catppuccin_egui::MOCHA.base);
+                    bg_color.replace(catppuccin_egui::MOCHA.base);
+                }
             }
-        },
+
+            ctx.style_mut(|style| {
+                set_threshold_colours(
+                    &mut style.visuals,
+                    CatppuccinValue::Yellow.color32(catppuccin.as_theme()),
+                    CatppuccinValue::Red.color32(catppuccin.as_theme()),
+                );
+            });
+        }
         KomobarTheme::Base16 {
             name: base16,
             accent: base16_value,
@@ -161,10 +183,45 @@ pub fn apply_theme(
                 style.visuals.selection.stroke.color = accent;
                 style.visuals.widgets.hovered.fg_stroke.color = accent;
                 style.visuals.widgets.active.fg_stroke.color = accent;
+                set_threshold_colours(
+                    &mut style.visuals,
+                    Base16Value::Base0A.color32(base16),
+                    Base16Value::Base08.color32(base16),
+                );
             });
 
             bg_color.replace(base16.background());
         }
+        KomobarTheme::Custom { path, name } => {
+            let colours = match Palette::load(&path, name.as_deref()) {
+                Ok(palette) => palette.colours(),
+                Err(error) => {
+                    tracing::error!("could not load palette from {}: {error}", path.display());
+                    return;
+                }
+            };
+
+            let mut visuals = match Rgba::from(colours.bar_background).intensity() < 0.5 {
+                true => Visuals::dark(),
+                false => Visuals::light(),
+            };
+
+            visuals.panel_fill = colours.bar_background;
+            visuals.window_fill = colours.bar_background;
+            visuals.widgets.noninteractive.fg_stroke.color = colours.bar_text;
+            visuals.widgets.inactive.fg_stroke.color = colours.bar_text;
+            visuals.selection.stroke.color = colours.accent;
+            visuals.widgets.hovered.fg_stroke.color = colours.accent;
+            visuals.widgets.active.fg_stroke.color = colours.accent;
+            set_threshold_colours(&mut visuals, colours.warning, colours.error);
+
+            ctx.set_style(Style {
+                visuals,
+                ..Style::default()
+            });
+
+            bg_color.replace(colours.bar_background);
+        }
     }
 
     // Apply transparency_alpha
@@ -229,21 +286,36 @@ impl Komobar {
 
         for (idx, widget_config) in self.config.left_widgets.iter().enumerate() {
             if let WidgetConfig::Komorebi(config) = widget_config {
-                komorebi_widgets.push((Komorebi::from(config), idx, Alignment::Left));
+                komorebi_widgets.push((
+                    Komorebi::from(config),
+                    idx,
+                    Alignment::Left,
+                    widget_config,
+                ));
             }
         }
 
         if let Some(center_widgets) = &self.config.center_widgets {
             for (idx, widget_config) in center_widgets.iter().enumerate() {
                 if let WidgetConfig::Komorebi(config) = widget_config {
-                    komorebi_widgets.push((Komorebi::from(config), idx, Alignment::Center));
+                    komorebi_widgets.push((
+                        Komorebi::from(config),
+                        idx,
+                        Alignment::Center,
+                        widget_config,
+                    ));
                 }
             }
         }
 
         for (idx, widget_config) in self.config.right_widgets.iter().enumerate() {
             if let WidgetConfig::Komorebi(config) = widget_config {
-                komorebi_widgets.push((Komorebi::from(config), idx, Alignment::Right));
+                komorebi_widgets.push((
+                    Komorebi::from(config),
+                    idx,
+                    Alignment::Right,
+                    widget_config,
+                ));
             }
         }
 
@@ -252,15 +324,15 @@ impl Komobar {
             .left_widgets
             .iter()
             .filter(|config| config.enabled())
-            .map(|config| config.as_boxed_bar_widget())
-            .collect::<Vec<Box<dyn BarWidget>>>();
+            .map(SectionWidget::from)
+            .collect::<Vec<_>>();
 
         let mut center_widgets = match &self.config.center_widgets {
             Some(center_widgets) => center_widgets
                 .iter()
                 .filter(|config| config.enabled())
-                .map(|config| config.as_boxed_bar_widget())
-                .collect::<Vec<Box<dyn BarWidget>>>(),
+                .map(SectionWidget::from)
+                .collect::<Vec<_>>(),
             None => vec![],
         };
 
@@ -269,13 +341,13 @@ impl Komobar {
             .right_widgets
             .iter()
             .filter(|config| config.enabled())
-            .map(|config| config.as_boxed_bar_widget())
-            .collect::<Vec<Box<dyn BarWidget>>>();
+            .map(SectionWidget::from)
+            .collect::<Vec<_>>();
 
         if !komorebi_widgets.is_empty() {
             komorebi_widgets
                 .into_iter()
-                .for_each(|(mut widget, idx, side)| {
+                .for_each(|(mut widget, idx, side, widget_config)| {
                     match komorebi_notification_state {
                         None => {
                             komorebi_notification_state =
@@ -292,20 +364,21 @@ impl Komobar {
                         }
                     }
 
-                    let boxed: Box<dyn BarWidget> = Box::new(widget);
+                    let boxed = widget_config.wrap(Box::new(widget));
                     match side {
-                        Alignment::Left => left_widgets[idx] = boxed,
-                        Alignment::Center => center_widgets[idx] = boxed,
-                        Alignment::Right => right_widgets[idx] = boxed,
+                        Alignment::Left => left_widgets[idx].widget = boxed,
+                        Alignment::Center => center_widgets[idx].widget = boxed,
+                        Alignment::Right => right_widgets[idx].widget = boxed,
                     }
                 });
         }
 
         right_widgets.reverse();
 
-        self.left_widgets = left_widgets;
-        self.center_widgets = center_widgets;
-        self.right_widgets = right_widgets;
+        let layout = self.config.layout.unwrap_or_default();
+        self.left_widgets = Section::new(layout.left, left_widgets);
+        self.center_widgets = Section::new(layout.center, center_widgets);
+        self.right_widgets = Section::new(layout.right, right_widgets);
 
         let (usr_monitor_index, config_work_area_offset) = match &self.config.monitor {
             MonitorConfigOrIndex::MonitorConfig(monitor_config) => {
@@ -340,24 +413,8 @@ impl Komobar {
                         tracing::info!("work area offset applied to monitor: {}", monitor_index);
                     }
                 }
-            } else if let Some(height) = self.config.height.or(Some(BAR_HEIGHT)) {
-                // We only add the `bottom_margin` to the work_area_offset since the top margin is
-                // already considered on the `size_rect.top`
-                let bottom_margin = self
-                    .config
-                    .margin
-                    .as_ref()
-                    .map_or(0, |v| v.to_individual(0.0).bottom as i32);
-                let new_rect = komorebi_client::Rect {
-                    left: 0,
-                    top: (height as i32)
-                        + (self.size_rect.top - MONITOR_TOP.load(Ordering::SeqCst))
-                        + bottom_margin,
-                    right: 0,
-                    bottom: (height as i32)
-                        + (self.size_rect.top - MONITOR_TOP.load(Ordering::SeqCst))
-                        + bottom_margin,
-                };
+            } else {
+                let new_rect = self.docked_work_area_offset();
 
                 if new_rect != self.work_area_offset {
                     self.work_area_offset = new_rect;
@@ -382,11 +439,84 @@ impl Komobar {
 
         tracing::info!("widget configuration options applied");
 
+        self.render_config.borrow_mut().komorebi_notification_state =
+            komorebi_notification_state.clone();
         self.komorebi_notification_state = komorebi_notification_state;
     }
 
+    /// The work area offset which keeps tiled windows clear of the bar on the edge it is docked to
+    fn docked_work_area_offset(&self) -> komorebi_client::Rect {
+        let margin = get_individual_spacing(0.0, &self.config.margin);
+
+        match self.config.dock.unwrap_or_default() {
+            BarDock::Horizontal => {
+                let height = self.config.height.unwrap_or(BAR_HEIGHT);
+                // We only add the `bottom_margin` to the work_area_offset since the top margin is
+                // already considered on the `size_rect.top`
+                let offset = (height as i32)
+                    + (self.size_rect.top - MONITOR_TOP.load(Ordering::SeqCst))
+                    + margin.bottom as i32;
+
+                komorebi_client::Rect {
+                    left: 0,
+                    top: offset,
+                    right: 0,
+                    bottom: offset,
+                }
+            }
+            BarDock::Left => {
+                let offset =
+                    (self.config.width.unwrap_or(BAR_HEIGHT) + margin.left + margin.right) as i32;
+
+                // the left offset moves the work area and the right offset shrinks it
+                komorebi_client::Rect {
+                    left: offset,
+                    top: 0,
+                    right: offset,
+                    bottom: 0,
+                }
+            }
+            BarDock::Right => komorebi_client::Rect {
+                left: 0,
+                top: 0,
+                right: (self.config.width.unwrap_or(BAR_HEIGHT) + margin.left + margin.right)
+                    as i32,
+                bottom: 0,
+            },
+        }
+    }
+
     /// Updates the `size_rect` field. Returns a bool indicating if the field was changed or not
     fn update_size_rect(&mut self) {
+        let dock = self.config.dock.unwrap_or_default();
+
+        // vertical bars span the height of the monitor on the edge they are docked to
+        if dock.is_vertical() {
+            let margin = get_individual_spacing(0.0, &self.config.margin);
+            let width = self.config.width.unwrap_or(BAR_HEIGHT);
+            let monitor_left = MONITOR_LEFT.load(Ordering::SeqCst) as f32;
+            let monitor_top = MONITOR_TOP.load(Ordering::SeqCst) as f32;
+
+            let left = match dock {
+                BarDock::Right => {
+                    monitor_left + MONITOR_RIGHT.load(Ordering::SeqCst) as f32
+                        - width
+                        - margin.right
+                }
+                _ => monitor_left + margin.left,
+            };
+
+            self.size_rect = komorebi_client::Rect {
+                left: left as i32,
+                top: (monitor_top + margin.top) as i32,
+                right: width as i32,
+                bottom: (MONITOR_BOTTOM.load(Ordering::SeqCst) as f32 - margin.top - margin.bottom)
+                    as i32,
+            };
+
+            return;
+        }
+
         let position = self.config.position.clone().unwrap_or(PositionConfig {
             start: Some(Position {
                 x: MONITOR_LEFT.load(Ordering::SeqCst) as f32,
@@ -431,11 +561,11 @@ impl Komobar {
     }
 
     fn try_apply_theme(&mut self, ctx: &Context) {
-        match self.config.theme {
+        match &self.config.theme {
             Some(theme) => {
                 apply_theme(
                     ctx,
-                    theme,
+                    theme.clone(),
                     self.bg_color.clone(),
                     self.bg_color_with_alpha.clone(),
                     self.config.transparency_alpha,
@@ -462,10 +592,10 @@ impl Komobar {
                 let config = home_dir.join("komorebi.json");
                 match komorebi_client::StaticConfig::read(&config) {
                     Ok(config) => {
-                        if let Some(theme) = config.theme {
+                        if let Some(theme) = config.current_theme() {
                             apply_theme(
                                 ctx,
-                                KomobarTheme::from(theme),
+                                KomobarTheme::from(theme.clone()),
                                 self.bg_color.clone(),
                                 self.bg_color_with_alpha.clone(),
                                 bar_transparency_alpha,
@@ -482,6 +612,13 @@ impl Komobar {
                                 KomorebiTheme::Base16 {
                                     name, stack_border, ..
                                 } => stack_border.unwrap_or(Base16Value::Base0B).color32(name),
+                                KomorebiTheme::Custom { path, name } => {
+                                    match Palette::load(&path, name.as_deref()) {
+                                        Ok(palette) => palette.colours().stack_border,
+                                        // the error was already logged when applying the theme
+                                        Err(_) => return,
+                                    }
+                                }
                             };
 
                             if let Some(state) = &self.komorebi_notification_state {
@@ -521,6 +658,7 @@ impl Komobar {
         cc: &eframe::CreationContext<'_>,
         rx_gui: Receiver<KomorebiEvent>,
         rx_config: Receiver<KomobarConfig>,
+        rx_theme: Receiver<()>,
         config: KomobarConfig,
     ) -> Self {
         let mut komobar = Self {
@@ -530,11 +668,12 @@ impl Komobar {
             config,
             render_config: Rc::new(RefCell::new(RenderConfig::new())),
             komorebi_notification_state: None,
-            left_widgets: vec![],
-            center_widgets: vec![],
-            right_widgets: vec![],
+            left_widgets: Section::default(),
+            center_widgets: Section::default(),
+            right_widgets: Section::default(),
             rx_gui,
             rx_config,
+            rx_theme,
             bg_color: Rc::new(RefCell::new(Style::default().visuals.panel_fill)),
             bg_color_with_alpha: Rc::new(RefCell::new(Style::default().visuals.panel_fill)),
             scale_factor: cc.egui_ctx.native_pixels_per_point().unwrap_or(1.0),
@@ -640,6 +779,7 @@ impl Komobar {
         MONITOR_TOP.store(monitor_size.top, Ordering::SeqCst);
         MONITOR_LEFT.store(monitor_size.left, Ordering::SeqCst);
         MONITOR_RIGHT.store(monitor_size.right, Ordering::SeqCst);
+        MONITOR_BOTTOM.store(monitor_size.bottom, Ordering::SeqCst);
 
         // Since the `config.position` is changed on `main.rs` we need to update it here.
         // If the user had set up some `start` position, that will be overriden here
@@ -673,6 +813,10 @@ impl eframe::App for Komobar {
             self.apply_config(ctx, self.komorebi_notification_state.clone());
         }
 
+        if self.rx_theme.try_iter().count() > 0 {
+            self.try_apply_theme(ctx);
+        }
+
         match self.rx_gui.try_recv() {
             Err(error) => match error {
                 TryRecvError::Empty => {}
@@ -756,7 +900,7 @@ impl eframe::App for Komobar {
                     let rect = komorebi_client::Rect {
                         top,
                         left,
-                        bottom: monitor_size.bottom,
+                        bottom: MONITOR_BOTTOM.load(Ordering::SeqCst),
                         right,
                     };
                     if *monitor_size != rect {
@@ -782,7 +926,7 @@ impl eframe::App for Komobar {
                             self.bg_color_with_alpha.clone(),
                             self.config.transparency_alpha,
                             self.config.grouping,
-                            self.config.theme,
+                            self.config.theme.clone(),
                             self.render_config.clone(),
                         );
                 }
@@ -881,15 +1025,50 @@ impl eframe::App for Komobar {
                     .outer_margin(Margin::same(0))
             };
 
-            let available_height = ui.max_rect().max.y;
+            // on vertical bars the left, center and right sections are stacked from top to bottom
+            let vertical = render_config.label_layout.is_vertical();
+            let (left_anchor, right_anchor, section_layout, right_layout) = match vertical {
+                true => (
+                    Align2::CENTER_TOP,
+                    Align2::CENTER_BOTTOM,
+                    Layout::top_down(Align::Center),
+                    Layout::bottom_up(Align::Center),
+                ),
+                false => (
+                    Align2::LEFT_CENTER,
+                    Align2::RIGHT_CENTER,
+                    Layout::left_to_right(Align::Center),
+                    Layout::right_to_left(Align::Center),
+                ),
+            };
+
+            // widgets fill the height of horizontal bars, but are stacked on vertical bars
+            let available_height = match vertical {
+                true => Style::default().spacing.interact_size.y,
+                false => ui.max_rect().max.y,
+            };
             ctx.style_mut(|style| {
                 style.spacing.interact_size.y = available_height;
             });
 
+            // the center section is always centered on the bar, leaving the space on either side
+            // of it to the left and right sections
+            let bar_width = match vertical {
+                true => ui.max_rect().height(),
+                false => ui.max_rect().width(),
+            };
+            let side_space = if self.center_widgets.is_empty()
+                && (self.left_widgets.is_empty() || self.right_widgets.is_empty())
+            {
+                bar_width
+            } else {
+                (bar_width - self.center_widgets.footprint()) / 2.0 - render_config.spacing
+            };
+
             if !self.left_widgets.is_empty() {
                 // Left-aligned widgets layout
                 Area::new(Id::new("left_panel"))
-                    .anchor(Align2::LEFT_CENTER, [0.0, 0.0]) // Align in the left center of the window
+                    .anchor(left_anchor, [0.0, 0.0]) // Align at the left (top on vertical bars)
                     .show(ctx, |ui| {
                         let mut left_area_frame = area_frame;
                         if let Some(padding) = self
@@ -908,14 +1087,13 @@ impl eframe::App for Komobar {
                         }
 
                         left_area_frame.show(ui, |ui| {
-                            ui.horizontal(|ui| {
+                            ui.with_layout(section_layout, |ui| {
                                 let mut render_conf = render_config.clone();
                                 render_conf.alignment = Some(Alignment::Left);
 
                                 render_config.apply_on_alignment(ui, |ui| {
-                                    for w in &mut self.left_widgets {
-                                        w.render(ctx, ui, &mut render_conf);
-                                    }
+                                    self.left_widgets
+                                        .show(ctx, ui, &mut render_conf, side_space);
                                 });
                             });
                         });
@@ -925,7 +1103,7 @@ impl eframe::App for Komobar {
             if !self.right_widgets.is_empty() {
                 // Right-aligned widgets layout
                 Area::new(Id::new("right_panel"))
-                    .anchor(Align2::RIGHT_CENTER, [0.0, 0.0]) // Align in the right center of the window
+                    .anchor(right_anchor, [0.0, 0.0]) // Align at the right (bottom on vertical bars)
                     .show(ctx, |ui| {
                         let mut right_area_frame = area_frame;
                         if let Some(padding) = self
@@ -944,24 +1122,25 @@ impl eframe::App for Komobar {
                         }
 
                         right_area_frame.show(ui, |ui| {
-                            let initial_size = Vec2 {
-                                x: ui.available_size_before_wrap().x,
-                                y: ui.spacing().interact_size.y,
-                            };
-                            ui.allocate_ui_with_layout(
-                                initial_size,
-                                Layout::right_to_left(Align::Center),
-                                |ui| {
-                                    let mut render_conf = render_config.clone();
-                                    render_conf.alignment = Some(Alignment::Right);
-
-                                    render_config.apply_on_alignment(ui, |ui| {
-                                        for w in &mut self.right_widgets {
-                                            w.render(ctx, ui, &mut render_conf);
-                                        }
-                                    });
+                            let initial_size = match vertical {
+                                true => Vec2 {
+                                    x: ui.spacing().interact_size.x,
+                                    y: ui.available_size_before_wrap().y,
                                 },
-                            );
+                                false => Vec2 {
+                                    x: ui.available_size_before_wrap().x,
+                                    y: ui.spacing().interact_size.y,
+                                },
+                            };
+                            ui.allocate_ui_with_layout(initial_size, right_layout, |ui| {
+                                let mut render_conf = render_config.clone();
+                                render_conf.alignment = Some(Alignment::Right);
+
+                                render_config.apply_on_alignment(ui, |ui| {
+                                    self.right_widgets
+                                        .show(ctx, ui, &mut render_conf, side_space);
+                                });
+                            });
                         });
                     });
             }
@@ -986,14 +1165,13 @@ impl eframe::App for Komobar {
                         }
 
                         center_area_frame.show(ui, |ui| {
-                            ui.horizontal(|ui| {
+                            ui.with_layout(section_layout, |ui| {
                                 let mut render_conf = render_config.clone();
                                 render_conf.alignment = Some(Alignment::Center);
 
                                 render_config.apply_on_alignment(ui, |ui| {
-                                    for w in &mut self.center_widgets {
-                                        w.render(ctx, ui, &mut render_conf);
-                                    }
+                                    self.center_widgets
+                                        .show(ctx, ui, &mut render_conf, bar_width);
                                 });
                             });
                         });
@@ -1,11 +1,16 @@
 use crate::config::LabelPrefix;
 use crate::render::RenderConfig;
+use crate::scheduler;
+use crate::scheduler::DataReceiver;
 use crate::selected_frame::SelectableFrame;
+use crate::threshold::ThresholdConfig;
+use crate::threshold::Thresholds;
 use crate::widget::BarWidget;
+use crate::widget::CommonWidgetConfig;
+use crate::widget::InternalAction;
 use eframe::egui::text::LayoutJob;
 use eframe::egui::Align;
 use eframe::egui::Context;
-use eframe::egui::Label;
 use eframe::egui::TextFormat;
 use eframe::egui::Ui;
 use serde::Deserialize;
@@ -15,9 +20,8 @@ use starship_battery::Manager;
 use starship_battery::State;
 use std::process::Command;
 use std::time::Duration;
-use std::time::Instant;
 
-#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct BatteryConfig {
     /// Enable the Battery widget
@@ -28,6 +32,11 @@ pub struct BatteryConfig {
     pub data_refresh_interval: Option<u64>,
     /// Display label prefix
     pub label_prefix: Option<LabelPrefix>,
+    /// Thresholds at which the widget is highlighted and alerts are sent
+    pub thresholds: Option<Vec<ThresholdConfig>>,
+    /// Actions, visibility condition and overflow priority of the widget
+    #[serde(flatten)]
+    pub common: CommonWidgetConfig,
 }
 
 impl From<BatteryConfig> for Battery {
@@ -37,79 +46,85 @@ impl From<BatteryConfig> for Battery {
         Self {
             enable: value.enable,
             hide_on_full_charge: value.hide_on_full_charge.unwrap_or(false),
-            manager: Manager::new().unwrap(),
-            last_state: String::new(),
-            data_refresh_interval,
+            reading: scheduler::schedule(
+                Duration::from_secs(data_refresh_interval),
+                None,
+                battery_reading(),
+            ),
             label_prefix: value.label_prefix.unwrap_or(LabelPrefix::Icon),
-            state: BatteryState::Discharging,
-            last_updated: Instant::now()
-                .checked_sub(Duration::from_secs(data_refresh_interval))
-                .unwrap(),
+            thresholds: Thresholds::from(value.thresholds),
         }
     }
 }
 
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum BatteryState {
     Charging,
     Discharging,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BatteryReading {
+    pub percentage: u32,
+    pub state: BatteryState,
+}
+
+/// Returns a data collector for the charge and state of the first battery, if there is one, which
+/// is shared by every widget and condition
+pub fn battery_reading() -> impl FnMut() -> Option<BatteryReading> + Send + 'static {
+    scheduler::shared("battery", || {
+        let mut state = BatteryState::Discharging;
+
+        move || {
+            let mut batteries = Manager::new().ok()?.batteries().ok()?;
+            let first = batteries.next()?.ok()?;
+
+            match first.state() {
+                State::Charging => state = BatteryState::Charging,
+                State::Discharging => state = BatteryState::Discharging,
+                _ => {}
+            }
+
+            Some(BatteryReading {
+                percentage: first.state_of_charge().get::<percent>().round() as u32,
+                state,
+            })
+        }
+    })
+}
+
 pub struct Battery {
     pub enable: bool,
     hide_on_full_charge: bool,
-    manager: Manager,
-    pub state: BatteryState,
-    data_refresh_interval: u64,
+    reading: DataReceiver<Option<BatteryReading>>,
     label_prefix: LabelPrefix,
-    last_state: String,
-    last_updated: Instant,
+    thresholds: Thresholds,
 }
 
 impl Battery {
-    fn output(&mut self) -> String {
-        let mut output = self.last_state.clone();
-
-        let now = Instant::now();
-        if now.duration_since(self.last_updated) > Duration::from_secs(self.data_refresh_interval) {
-            output.clear();
-
-            if let Ok(mut batteries) = self.manager.batteries() {
-                if let Some(Ok(first)) = batteries.nth(0) {
-                    let percentage = first.state_of_charge().get::<percent>();
-
-                    if percentage == 100.0 && self.hide_on_full_charge {
-                        output = String::new()
-                    } else {
-                        match first.state() {
-                            State::Charging => self.state = BatteryState::Charging,
-                            State::Discharging => self.state = BatteryState::Discharging,
-                            _ => {}
-                        }
-
-                        output = match self.label_prefix {
-                            LabelPrefix::Text | LabelPrefix::IconAndText => {
-                                format!("BAT: {percentage:.0}%")
-                            }
-                            LabelPrefix::None | LabelPrefix::Icon => format!("{percentage:.0}%"),
-                        }
-                    }
-                }
-            }
+    fn output(&mut self) -> Option<(String, BatteryReading)> {
+        let reading = (*self.reading.latest())?;
 
-            self.last_state.clone_from(&output);
-            self.last_updated = now;
+        if reading.percentage == 100 && self.hide_on_full_charge {
+            return None;
         }
 
-        output
+        let percentage = reading.percentage;
+        let output = match self.label_prefix {
+            LabelPrefix::Text | LabelPrefix::IconAndText => format!("BAT: {percentage}%"),
+            LabelPrefix::None | LabelPrefix::Icon => format!("{percentage}%"),
+        };
+
+        Some((output, reading))
     }
 }
 
 impl BarWidget for Battery {
     fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
         if self.enable {
-            let output = self.output();
-            if !output.is_empty() {
-                let emoji = match self.state {
+            if let Some((output, reading)) = self.output() {
+                let highlight = self.thresholds.evaluate("BAT", reading.percentage);
+                let emoji = match reading.state {
                     BatteryState::Charging => egui_phosphor::regular::BATTERY_CHARGING,
                     BatteryState::Discharging => egui_phosphor::regular::BATTERY_FULL,
                 };
@@ -129,16 +144,24 @@ impl BarWidget for Battery {
                     10.0,
                     TextFormat {
                         font_id: config.text_font_id.clone(),
-                        color: ctx.style().visuals.text_color(),
+                        color: highlight.text_color(ctx, config.background_color),
                         valign: Align::Center,
                         ..Default::default()
                     },
                 );
 
+                let label_layout = config.label_layout;
+                let builtin_click = config.builtin_click;
+
                 config.apply_on_widget(false, ui, |ui| {
                     if SelectableFrame::new(false)
-                        .show(ui, |ui| ui.add(Label::new(layout_job).selectable(false)))
+                        .show(ui, |ui| {
+                            highlight
+                                .frame(ctx)
+                                .show(ui, |ui| label_layout.add(ui, layout_job))
+                        })
                         .clicked()
+                        && builtin_click
                     {
                         if let Err(error) = Command::new("cmd.exe")
                             .args(["/C", "start", "ms-settings:batterysaver"])
@@ -151,4 +174,10 @@ impl BarWidget for Battery {
             }
         }
     }
+
+    fn handle_action(&mut self, action: InternalAction) {
+        if action == InternalAction::ToggleLabel {
+            self.label_prefix.toggle_text();
+        }
+    }
 }
@@ -0,0 +1,140 @@
+use chrono::Datelike;
+use chrono::Months;
+use chrono::NaiveDate;
+use eframe::egui::CentralPanel;
+use eframe::egui::Context;
+use eframe::egui::Frame;
+use eframe::egui::Grid;
+use eframe::egui::Margin;
+use eframe::egui::Pos2;
+use eframe::egui::Rect;
+use eframe::egui::RichText;
+use eframe::egui::Ui;
+use eframe::egui::Vec2;
+use eframe::egui::ViewportBuilder;
+use eframe::egui::ViewportId;
+
+const CALENDAR_SIZE: Vec2 = Vec2::new(280.0, 240.0);
+const WEEKDAYS: [&str; 7] = ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"];
+
+/// A month calendar with ISO week numbers, opened in a popup window next to the bar
+#[derive(Clone, Debug, Default)]
+pub struct Calendar {
+    /// The first day of the displayed month, if the calendar is open
+    month: Option<NaiveDate>,
+}
+
+impl Calendar {
+    /// Opens the calendar on the month of `today`, or closes it if it is already open
+    pub fn toggle(&mut self, today: NaiveDate) {
+        self.month = match self.month {
+            Some(_) => None,
+            None => today.with_day(1),
+        };
+    }
+
+    /// Shows the calendar, if it is open, below (or above) the `anchor` rect of the widget
+    pub fn show(&mut self, ctx: &Context, anchor: Rect, today: NaiveDate) {
+        let Some(mut month) = self.month else {
+            return;
+        };
+
+        let (bar_rect, monitor_size) = ctx.input(|input| {
+            (
+                input.viewport().outer_rect.unwrap_or(Rect::NOTHING),
+                input.viewport().monitor_size.unwrap_or(Vec2::INFINITY),
+            )
+        });
+
+        // open upwards when the bar is at the bottom of the screen
+        let y = if bar_rect.bottom() + CALENDAR_SIZE.y > monitor_size.y {
+            bar_rect.top() - CALENDAR_SIZE.y
+        } else {
+            bar_rect.bottom()
+        };
+        let position = Pos2::new(bar_rect.left() + anchor.left(), y);
+
+        let mut open = true;
+
+        ctx.show_viewport_immediate(
+            ViewportId::from_hash_of("komorebi-bar-calendar"),
+            ViewportBuilder::default()
+                .with_title("komorebi-bar calendar")
+                .with_decorations(false)
+                .with_taskbar(false)
+                .with_always_on_top()
+                .with_position(position)
+                .with_inner_size(CALENDAR_SIZE),
+            |ctx, _class| {
+                CentralPanel::default()
+                    .frame(Frame::central_panel(&ctx.style()).inner_margin(Margin::same(8)))
+                    .show(ctx, |ui| month = Self::month_grid(ui, month, today));
+
+                if ctx.input(|input| input.viewport().close_requested()) {
+                    open = false;
+                }
+            },
+        );
+
+        self.month = open.then_some(month);
+    }
+
+    /// Renders the grid for the month, returning the month to display on the next frame
+    fn month_grid(ui: &mut Ui, month: NaiveDate, today: NaiveDate) -> NaiveDate {
+        let mut displayed = month;
+        let accent = ui.style().visuals.selection.stroke.color;
+        let weak = ui.style().visuals.weak_text_color();
+
+        ui.horizontal(|ui| {
+            if ui.button("<").clicked() {
+                displayed = month - Months::new(1);
+            }
+
+            ui.strong(month.format("%B %Y").to_string());
+
+            if ui.button(">").clicked() {
+                displayed = month + Months::new(1);
+            }
+        });
+
+        ui.separator();
+
+        let first_weekday = month.weekday().num_days_from_monday() as i64;
+        let first_cell = month - chrono::Duration::days(first_weekday);
+
+        Grid::new("komorebi-bar-calendar-grid")
+            .spacing(Vec2::new(10.0, 4.0))
+            .show(ui, |ui| {
+                ui.label(RichText::new("Wk").color(weak));
+                for weekday in WEEKDAYS {
+                    ui.label(RichText::new(weekday).color(weak));
+                }
+                ui.end_row();
+
+                for week in 0..6 {
+                    let monday = first_cell + chrono::Duration::weeks(week);
+                    if week > 0 && monday.month() != month.month() {
+                        break;
+                    }
+
+                    ui.label(RichText::new(monday.iso_week().week().to_string()).color(weak));
+
+                    for offset in 0..7 {
+                        let day = monday + chrono::Duration::days(offset);
+                        let mut text = RichText::new(day.day().to_string());
+
+                        if day == today {
+                            text = text.strong().color(accent);
+                        } else if day.month() != month.month() {
+                            text = text.color(weak);
+                        }
+
+                        ui.label(text);
+                    }
+                    ui.end_row();
+                }
+            });
+
+        displayed
+    }
+}
@@ -0,0 +1,418 @@
+use crate::render::RenderConfig;
+use crate::scheduler;
+use crate::selected_frame::SelectableFrame;
+use crate::widget::BarWidget;
+use crate::widget::CommonWidgetConfig;
+use crossbeam_channel::Receiver;
+use crossbeam_channel::Sender;
+use eframe::egui::text::LayoutJob;
+use eframe::egui::Align;
+use eframe::egui::Color32;
+use eframe::egui::Context;
+use eframe::egui::TextFormat;
+use eframe::egui::Ui;
+use komorebi_client::Colour;
+use serde::Deserialize;
+use serde::Serialize;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::os::windows::process::CommandExt;
+use std::process::Child;
+use std::process::ChildStdout;
+use std::process::Stdio;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CommandConfig {
+    /// Enable the Command widget
+    pub enable: bool,
+    /// Command to run (executed with `cmd.exe /C`)
+    pub command: String,
+    /// Re-run the command on an interval or read its output line by line as it is printed (default: Interval)
+    pub mode: Option<CommandMode>,
+    /// Data refresh interval in Interval mode, or the delay before restarting an exited command in Stream mode (default: 10 seconds)
+    pub data_refresh_interval: Option<u64>,
+    /// How the command output is parsed (default: Text)
+    pub output_format: Option<CommandOutputFormat>,
+    /// Icon to display before the output, which can be overridden by a JSON payload (default: terminal window icon)
+    pub icon: Option<String>,
+    /// Command to run when the widget is clicked (executed with `cmd.exe /C`)
+    pub on_click: Option<String>,
+    /// Actions, visibility condition and overflow priority of the widget
+    #[serde(flatten)]
+    pub common: CommonWidgetConfig,
+}
+
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum CommandMode {
+    /// Run the command every `data_refresh_interval` seconds and display its output
+    #[default]
+    Interval,
+    /// Run the command once and display every line it prints to stdout
+    Stream,
+}
+
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum CommandOutputFormat {
+    /// The first non-empty line of output is displayed as it is
+    #[default]
+    Text,
+    /// Each output is a JSON object with `text`, and optionally `icon`, `colour` and `tooltip`
+    Json,
+}
+
+/// The payload a command prints when using the Json output format
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct CommandOutput {
+    #[serde(default)]
+    pub text: String,
+    pub icon: Option<String>,
+    pub colour: Option<Colour>,
+    pub tooltip: Option<String>,
+}
+
+impl CommandOutput {
+    fn parse(output: &str, format: CommandOutputFormat) -> Option<Self> {
+        let line = output.lines().map(str::trim).find(|line| !line.is_empty());
+
+        match format {
+            CommandOutputFormat::Text => Some(Self {
+                text: line.unwrap_or_default().to_string(),
+                ..Default::default()
+            }),
+            CommandOutputFormat::Json => match serde_json::from_str(output.trim()) {
+                Ok(payload) => Some(payload),
+                Err(error) => {
+                    tracing::warn!("command widget output is not a valid JSON payload: {error}");
+                    line.map(|line| Self {
+                        text: line.to_string(),
+                        ..Default::default()
+                    })
+                }
+            },
+        }
+    }
+
+    /// Lays out the icon (falling back to `default_icon`) and the text of the payload
+    pub fn layout_job(
+        &self,
+        ctx: &Context,
+        config: &RenderConfig,
+        default_icon: &str,
+    ) -> LayoutJob {
+        let mut layout_job = LayoutJob::simple(
+            self.icon
+                .clone()
+                .unwrap_or_else(|| default_icon.to_string()),
+            config.icon_font_id.clone(),
+            ctx.style().visuals.selection.stroke.color,
+            100.0,
+        );
+
+        layout_job.append(
+            &self.text,
+            10.0,
+            TextFormat {
+                font_id: config.text_font_id.clone(),
+                color: self
+                    .colour
+                    .map_or_else(|| ctx.style().visuals.text_color(), Color32::from),
+                valign: Align::Center,
+                ..Default::default()
+            },
+        );
+
+        layout_job
+    }
+}
+
+impl From<CommandConfig> for Command {
+    fn from(value: CommandConfig) -> Self {
+        let (tx, rx) = crossbeam_channel::unbounded();
+        let worker = CommandWorker::spawn(&value, tx);
+
+        Self {
+            enable: value.enable,
+            icon: value
+                .icon
+                .unwrap_or_else(|| egui_phosphor::regular::TERMINAL_WINDOW.to_string()),
+            on_click: value.on_click,
+            output: CommandOutput::default(),
+            rx,
+            _worker: worker,
+        }
+    }
+}
+
+pub struct Command {
+    pub enable: bool,
+    icon: String,
+    on_click: Option<String>,
+    output: CommandOutput,
+    rx: Receiver<CommandOutput>,
+    _worker: CommandWorker,
+}
+
+/// Runs the configured command on a background thread, stopping it when the widget is dropped
+/// (eg. when the configuration is reloaded)
+struct CommandWorker {
+    stop: Arc<AtomicBool>,
+    child: Arc<Mutex<Option<Child>>>,
+}
+
+impl Drop for CommandWorker {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(mut child) = take(&self.child) {
+            let _ = child.kill();
+        }
+    }
+}
+
+impl CommandWorker {
+    fn spawn(config: &CommandConfig, tx: Sender<CommandOutput>) -> Self {
+        let worker = Self {
+            stop: Arc::new(AtomicBool::new(false)),
+            child: Arc::new(Mutex::new(None)),
+        };
+
+        if !config.enable {
+            return worker;
+        }
+
+        let command = config.command.clone();
+        let mode = config.mode.unwrap_or_default();
+        let format = config.output_format.unwrap_or_default();
+        let interval = Duration::from_secs(config.data_refresh_interval.unwrap_or(10));
+        let stop = worker.stop.clone();
+        let child = worker.child.clone();
+
+        std::thread::spawn(move || {
+            while !stop.load(Ordering::SeqCst) {
+                let stdout = match start(&command, &child, &stop) {
+                    Ok(Some(stdout)) => stdout,
+                    Ok(None) => break,
+                    Err(error) => {
+                        tracing::error!("could not run command widget: {error}");
+                        std::thread::sleep(interval);
+                        continue;
+                    }
+                };
+
+                match mode {
+                    CommandMode::Interval => {
+                        // the output is read on its own thread so that a command which hangs can
+                        // be killed once the next run is due
+                        let (output_tx, output_rx) = crossbeam_channel::bounded(1);
+                        std::thread::spawn(move || {
+                            let mut output = String::new();
+                            let _ = BufReader::new(stdout).read_to_string(&mut output);
+                            let _ = output_tx.send(output);
+                        });
+
+                        let output = output_rx.recv_timeout(interval);
+
+                        if let Some(mut process) = take(&child) {
+                            if output.is_err() {
+                                tracing::warn!(
+                                    "command widget did not exit within {}s, killing it",
+                                    interval.as_secs()
+                                );
+
+                                let _ = process.kill();
+                            }
+
+                            let _ = process.wait();
+                        }
+
+                        if let Some(output) = output
+                            .ok()
+                            .and_then(|output| CommandOutput::parse(&output, format))
+                        {
+                            if tx.send(output).is_err() {
+                                break;
+                            }
+
+                            scheduler::request_repaint();
+                        }
+                    }
+                    CommandMode::Stream => {
+                        for line in BufReader::new(stdout).lines() {
+                            let Ok(line) = line else {
+                                break;
+                            };
+
+                            if let Some(output) = CommandOutput::parse(&line, format) {
+                                if tx.send(output).is_err() {
+                                    break;
+                                }
+
+                                scheduler::request_repaint();
+                            }
+                        }
+
+                        if let Some(mut process) = take(&child) {
+                            let _ = process.wait();
+                        }
+                    }
+                }
+
+                std::thread::sleep(interval);
+            }
+        });
+
+        worker
+    }
+}
+
+/// Starts the command with a piped stdout and stores the process so that it is killed when the
+/// worker is dropped, `None` is returned if the worker was stopped in the meantime
+fn start(
+    command: &str,
+    child: &Mutex<Option<Child>>,
+    stop: &AtomicBool,
+) -> std::io::Result<Option<ChildStdout>> {
+    let mut process = shell(command).stdout(Stdio::piped()).spawn()?;
+    let stdout = process.stdout.take();
+
+    if let Ok(mut guard) = child.lock() {
+        *guard = Some(process);
+    }
+
+    // the stop flag may have been set while the process was starting
+    if stop.load(Ordering::SeqCst) {
+        if let Some(mut process) = take(child) {
+            let _ = process.kill();
+        }
+
+        return Ok(None);
+    }
+
+    Ok(stdout)
+}
+
+fn take(child: &Mutex<Option<Child>>) -> Option<Child> {
+    child.lock().ok().and_then(|mut child| child.take())
+}
+
+pub fn shell(command: &str) -> std::process::Command {
+    let mut process = std::process::Command::new("cmd.exe");
+    process
+        .args(["/C", command])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .creation_flags(CREATE_NO_WINDOW);
+
+    process
+}
+
+impl BarWidget for Command {
+    fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
+        if self.enable {
+            // only the most recent output is relevant
+            if let Some(output) = self.rx.try_iter().last() {
+                self.output = output;
+            }
+
+            if self.output.text.is_empty() {
+                return;
+            }
+
+            let layout_job = self.output.layout_job(ctx, config, &self.icon);
+
+            let label_layout = config.label_layout;
+            let builtin_click = config.builtin_click;
+
+            config.apply_on_widget(false, ui, |ui| {
+                let mut response =
+                    SelectableFrame::new(false).show(ui, |ui| label_layout.add(ui, layout_job));
+
+                if let Some(tooltip) = &self.output.tooltip {
+                    response = response.on_hover_text(tooltip);
+                }
+
+                if builtin_click && response.clicked() {
+                    if let Some(on_click) = &self.on_click {
+                        if let Err(error) = shell(on_click).spawn() {
+                            eprintln!("{}", error)
+                        }
+                    }
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CommandOutput;
+    use super::CommandOutputFormat;
+    use komorebi_client::Colour;
+    use komorebi_client::Rgb;
+
+    fn text(text: &str) -> Option<CommandOutput> {
+        Some(CommandOutput {
+            text: text.to_string(),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn text_uses_the_first_non_empty_line() {
+        let format = CommandOutputFormat::Text;
+
+        assert_eq!(CommandOutput::parse("42%\n", format), text("42%"));
+        assert_eq!(
+            CommandOutput::parse("\n  \n  first  \nsecond\n", format),
+            text("first")
+        );
+        assert_eq!(CommandOutput::parse("", format), text(""));
+    }
+
+    #[test]
+    fn json_payload() {
+        let format = CommandOutputFormat::Json;
+
+        assert_eq!(
+            CommandOutput::parse(
+                r#"{"text": "3 updates", "icon": "!", "colour": {"r": 255, "g": 0, "b": 0}, "tooltip": "pacman"}"#,
+                format
+            ),
+            Some(CommandOutput {
+                text: String::from("3 updates"),
+                icon: Some(String::from("!")),
+                colour: Some(Colour::Rgb(Rgb::new(255, 0, 0))),
+                tooltip: Some(String::from("pacman")),
+            })
+        );
+
+        assert_eq!(
+            CommandOutput::parse(r#"{"icon": "!"}"#, format),
+            Some(CommandOutput {
+                icon: Some(String::from("!")),
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_json_falls_back_to_text() {
+        let format = CommandOutputFormat::Json;
+
+        assert_eq!(
+            CommandOutput::parse("error: not json\n{", format),
+            text("error: not json")
+        );
+        assert_eq!(CommandOutput::parse("\n\n", format), None);
+    }
+}
@@ -0,0 +1,202 @@
+use crate::battery::battery_reading;
+use crate::battery::BatteryReading;
+use crate::battery::BatteryState;
+use crate::cpu::cpu_usage;
+use crate::komorebi::KomorebiNotificationState;
+use crate::memory::memory_usage;
+use crate::render::RenderConfig;
+use crate::scheduler;
+use crate::scheduler::DataReceiver;
+use crate::widget::BarWidget;
+use crate::widget::InternalAction;
+use eframe::egui::Context;
+use eframe::egui::Ui;
+use komorebi_client::MatchingRule;
+use komorebi_client::MatchingStrategy;
+use regex::Regex;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// How often the data used by battery and threshold conditions is refreshed
+const CONDITION_DATA_REFRESH_INTERVAL: u64 = 10;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum WidgetCondition {
+    /// The focused workspace on the bar's monitor has the given name
+    Workspace(String),
+    /// The focused window matches the given rule
+    FocusedWindow(MatchingRule),
+    /// The system is running on battery power
+    OnBattery,
+    /// A system metric is within the given bounds
+    Threshold(ThresholdCondition),
+    /// All of the conditions are met
+    All(Vec<WidgetCondition>),
+    /// Any of the conditions are met
+    Any(Vec<WidgetCondition>),
+    /// The condition is not met
+    Not(Box<WidgetCondition>),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ThresholdCondition {
+    /// Metric to compare against the bounds
+    pub metric: ThresholdMetric,
+    /// The condition is met when the value is greater than or equal to this percentage
+    pub above: Option<u32>,
+    /// The condition is met when the value is less than or equal to this percentage
+    pub below: Option<u32>,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum ThresholdMetric {
+    /// Global CPU usage percentage
+    Cpu,
+    /// Percentage of memory in use
+    Memory,
+    /// Battery charge percentage
+    Battery,
+}
+
+/// A [`WidgetCondition`] with the data sources and compiled regular expressions it depends on
+enum Condition {
+    Workspace(String),
+    FocusedWindow(MatchingRule, HashMap<String, Regex>),
+    OnBattery(DataReceiver<Option<BatteryReading>>),
+    Threshold {
+        value: DataReceiver<Option<u32>>,
+        above: Option<u32>,
+        below: Option<u32>,
+    },
+    All(Vec<Condition>),
+    Any(Vec<Condition>),
+    Not(Box<Condition>),
+}
+
+impl From<&WidgetCondition> for Condition {
+    fn from(value: &WidgetCondition) -> Self {
+        let interval = Duration::from_secs(CONDITION_DATA_REFRESH_INTERVAL);
+
+        match value {
+            WidgetCondition::Workspace(name) => Condition::Workspace(name.clone()),
+            WidgetCondition::FocusedWindow(rule) => {
+                let mut regex_identifiers = HashMap::new();
+                for identifier in rule.identifiers() {
+                    if matches!(identifier.matching_strategy, Some(MatchingStrategy::Regex)) {
+                        match Regex::new(&identifier.id) {
+                            Ok(re) => {
+                                regex_identifiers.insert(identifier.id.clone(), re);
+                            }
+                            Err(error) => {
+                                tracing::error!("invalid regex in widget visibility rule: {error}")
+                            }
+                        }
+                    }
+                }
+
+                Condition::FocusedWindow(rule.clone(), regex_identifiers)
+            }
+            WidgetCondition::OnBattery => {
+                Condition::OnBattery(scheduler::schedule(interval, None, battery_reading()))
+            }
+            WidgetCondition::Threshold(threshold) => {
+                let value = match threshold.metric {
+                    ThresholdMetric::Cpu => {
+                        let mut collect = cpu_usage();
+                        scheduler::schedule(interval, None, move || Some(collect()))
+                    }
+                    ThresholdMetric::Memory => {
+                        let mut collect = memory_usage();
+                        scheduler::schedule(interval, None, move || Some(collect() as u32))
+                    }
+                    ThresholdMetric::Battery => {
+                        let mut collect = battery_reading();
+                        scheduler::schedule(interval, None, move || {
+                            collect().map(|reading| reading.percentage)
+                        })
+                    }
+                };
+
+                Condition::Threshold {
+                    value,
+                    above: threshold.above,
+                    below: threshold.below,
+                }
+            }
+            WidgetCondition::All(conditions) => {
+                Condition::All(conditions.iter().map(Condition::from).collect())
+            }
+            WidgetCondition::Any(conditions) => {
+                Condition::Any(conditions.iter().map(Condition::from).collect())
+            }
+            WidgetCondition::Not(condition) => {
+                Condition::Not(Box::new(Condition::from(condition.as_ref())))
+            }
+        }
+    }
+}
+
+impl Condition {
+    fn is_met(&mut self, state: Option<&KomorebiNotificationState>) -> bool {
+        match self {
+            Condition::Workspace(name) => {
+                state.is_some_and(|state| state.selected_workspace.eq(name))
+            }
+            Condition::FocusedWindow(rule, regex_identifiers) => state
+                .and_then(|state| state.focused_window.as_ref())
+                .is_some_and(|target| {
+                    komorebi_client::matches_rule(target, rule, regex_identifiers)
+                }),
+            Condition::OnBattery(reading) => (*reading.latest())
+                .is_some_and(|reading| reading.state == BatteryState::Discharging),
+            Condition::Threshold {
+                value,
+                above,
+                below,
+            } => (*value.latest()).is_some_and(|value| {
+                above.is_none_or(|above| value >= above) && below.is_none_or(|below| value <= below)
+            }),
+            Condition::All(conditions) => conditions.iter_mut().all(|c| c.is_met(state)),
+            Condition::Any(conditions) => conditions.iter_mut().any(|c| c.is_met(state)),
+            Condition::Not(condition) => !condition.is_met(state),
+        }
+    }
+}
+
+/// Wraps a widget so that it is only rendered while its `visible_when` condition is met
+pub struct ConditionalWidget {
+    widget: Box<dyn BarWidget>,
+    condition: Condition,
+}
+
+impl ConditionalWidget {
+    pub fn new(widget: Box<dyn BarWidget>, condition: &WidgetCondition) -> Self {
+        Self {
+            widget,
+            condition: Condition::from(condition),
+        }
+    }
+}
+
+impl BarWidget for ConditionalWidget {
+    fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
+        let state = config.komorebi_notification_state.clone();
+        let visible = match &state {
+            Some(state) => self.condition.is_met(Some(&state.borrow())),
+            None => self.condition.is_met(None),
+        };
+
+        if visible {
+            self.widget.render(ctx, ui, config);
+        }
+    }
+
+    fn handle_action(&mut self, action: InternalAction) {
+        self.widget.handle_action(action);
+    }
+}
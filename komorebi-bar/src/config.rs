@@ -1,3 +1,4 @@
+use crate::layout::LayoutConfig;
 use crate::render::Grouping;
 use crate::widget::WidgetConfig;
 use crate::DEFAULT_PADDING;
@@ -17,6 +18,14 @@ use std::path::PathBuf;
 pub struct KomobarConfig {
     /// Bar height (default: 50)
     pub height: Option<f32>,
+    /// Edge of the screen the bar is docked to, where bars docked to the left or right edge ignore
+    /// the position option and span the height of the monitor (default: Horizontal)
+    pub dock: Option<BarDock>,
+    /// Bar width when it is docked to the left or right edge of the screen (default: 50)
+    pub width: Option<f32>,
+    /// How widget labels are displayed when the bar is docked to the left or right edge of the
+    /// screen (default: IconOnly)
+    pub vertical_labels: Option<VerticalLabels>,
     /// Bar padding. Use one value for all sides or use a grouped padding for horizontal and/or
     /// vertical definition which can each take a single value for a symmetric padding or two
     /// values for each side, i.e.:
@@ -90,6 +99,8 @@ pub struct KomobarConfig {
     pub widget_spacing: Option<f32>,
     /// Visual grouping for widgets
     pub grouping: Option<Grouping>,
+    /// Sizing of the left, center and right sections of the bar
+    pub layout: Option<LayoutConfig>,
     /// Left side widgets (ordered left-to-right)
     pub left_widgets: Vec<WidgetConfig>,
     /// Center widgets (ordered left-to-right)
@@ -147,6 +158,36 @@ pub struct PositionConfig {
     pub end: Option<Position>,
 }
 
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum BarDock {
+    /// A horizontal bar placed according to the position option
+    #[default]
+    Horizontal,
+    /// A vertical bar docked to the left edge of the screen
+    Left,
+    /// A vertical bar docked to the right edge of the screen
+    Right,
+}
+
+impl BarDock {
+    pub fn is_vertical(&self) -> bool {
+        matches!(self, BarDock::Left | BarDock::Right)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum VerticalLabels {
+    /// Only display the icon of each label, falling back to the text for labels without an icon
+    #[default]
+    IconOnly,
+    /// Wrap labels to the width of the bar
+    Wrapped,
+    /// Rotate labels to read from top to bottom
+    Rotated,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FrameConfig {
@@ -373,7 +414,7 @@ impl From<Position> for Pos2 {
     }
 }
 
-#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "palette")]
 pub enum KomobarTheme {
@@ -389,6 +430,13 @@ pub enum KomobarTheme {
         name: komorebi_themes::Base16,
         accent: Option<komorebi_themes::Base16Value>,
     },
+    /// A user-defined palette loaded from a file, which is reapplied whenever the file changes
+    Custom {
+        /// Path to a base16 YAML scheme, or to a TOML or JSON file of palettes keyed by name
+        path: PathBuf,
+        /// Name of the palette to use from the file (required for files with multiple palettes)
+        name: Option<String>,
+    },
 }
 
 impl From<KomorebiTheme> for KomobarTheme {
@@ -406,6 +454,7 @@ impl From<KomorebiTheme> for KomobarTheme {
                 name,
                 accent: bar_accent,
             },
+            KomorebiTheme::Custom { path, name } => Self::Custom { path, name },
         }
     }
 }
@@ -423,6 +472,18 @@ pub enum LabelPrefix {
     IconAndText,
 }
 
+impl LabelPrefix {
+    /// Toggles the text part of the prefix on and off, leaving the icon as it is
+    pub fn toggle_text(&mut self) {
+        *self = match self {
+            LabelPrefix::None => LabelPrefix::Text,
+            LabelPrefix::Text => LabelPrefix::None,
+            LabelPrefix::Icon => LabelPrefix::IconAndText,
+            LabelPrefix::IconAndText => LabelPrefix::Icon,
+        };
+    }
+}
+
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum DisplayFormat {
@@ -1,22 +1,30 @@
 use crate::config::LabelPrefix;
+use crate::graph::sampled;
+use crate::graph::GraphConfig;
+use crate::graph::GraphSeries;
+use crate::graph::Sampled;
 use crate::render::RenderConfig;
+use crate::scheduler;
+use crate::scheduler::DataReceiver;
 use crate::selected_frame::SelectableFrame;
+use crate::threshold::ThresholdConfig;
+use crate::threshold::Thresholds;
 use crate::widget::BarWidget;
+use crate::widget::CommonWidgetConfig;
+use crate::widget::InternalAction;
 use eframe::egui::text::LayoutJob;
 use eframe::egui::Align;
 use eframe::egui::Context;
-use eframe::egui::Label;
 use eframe::egui::TextFormat;
 use eframe::egui::Ui;
 use serde::Deserialize;
 use serde::Serialize;
 use std::process::Command;
 use std::time::Duration;
-use std::time::Instant;
 use sysinfo::RefreshKind;
 use sysinfo::System;
 
-#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CpuConfig {
     /// Enable the Cpu widget
@@ -25,6 +33,13 @@ pub struct CpuConfig {
     pub data_refresh_interval: Option<u64>,
     /// Display label prefix
     pub label_prefix: Option<LabelPrefix>,
+    /// Thresholds at which the widget is highlighted and alerts are sent
+    pub thresholds: Option<Vec<ThresholdConfig>>,
+    /// Display a graph of the usage history
+    pub graph: Option<GraphConfig>,
+    /// Actions, visibility condition and overflow priority of the widget
+    #[serde(flatten)]
+    pub common: CommonWidgetConfig,
 }
 
 impl From<CpuConfig> for Cpu {
@@ -33,38 +48,46 @@ impl From<CpuConfig> for Cpu {
 
         Self {
             enable: value.enable,
-            system: System::new_with_specifics(
-                RefreshKind::default().without_memory().without_processes(),
+            usage: scheduler::schedule(
+                Duration::from_secs(data_refresh_interval),
+                Sampled::default(),
+                sampled(value.graph.map_or(0, |graph| graph.window()), cpu_usage()),
             ),
-            data_refresh_interval,
             label_prefix: value.label_prefix.unwrap_or(LabelPrefix::IconAndText),
-            last_updated: Instant::now()
-                .checked_sub(Duration::from_secs(data_refresh_interval))
-                .unwrap(),
+            thresholds: Thresholds::from(value.thresholds),
+            graph: value.graph,
         }
     }
 }
 
+/// Returns a data collector for the global CPU usage percentage, which is shared by every widget
+/// and condition
+pub fn cpu_usage() -> impl FnMut() -> u32 + Send + 'static {
+    scheduler::shared("cpu", || {
+        let mut system =
+            System::new_with_specifics(RefreshKind::default().without_memory().without_processes());
+
+        move || {
+            system.refresh_cpu_usage();
+            system.global_cpu_usage().round() as u32
+        }
+    })
+}
+
 pub struct Cpu {
     pub enable: bool,
-    system: System,
-    data_refresh_interval: u64,
+    usage: DataReceiver<Sampled<u32>>,
     label_prefix: LabelPrefix,
-    last_updated: Instant,
+    thresholds: Thresholds,
+    graph: Option<GraphConfig>,
 }
 
 impl Cpu {
     fn output(&mut self) -> String {
-        let now = Instant::now();
-        if now.duration_since(self.last_updated) > Duration::from_secs(self.data_refresh_interval) {
-            self.system.refresh_cpu_usage();
-            self.last_updated = now;
-        }
-
-        let used = self.system.global_cpu_usage();
+        let used = self.usage.latest().value;
         match self.label_prefix {
-            LabelPrefix::Text | LabelPrefix::IconAndText => format!("CPU: {:.0}%", used),
-            LabelPrefix::None | LabelPrefix::Icon => format!("{:.0}%", used),
+            LabelPrefix::Text | LabelPrefix::IconAndText => format!("CPU: {}%", used),
+            LabelPrefix::None | LabelPrefix::Icon => format!("{}%", used),
         }
     }
 }
@@ -73,6 +96,14 @@ impl BarWidget for Cpu {
     fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
         if self.enable {
             let output = self.output();
+            let highlight = self.thresholds.evaluate("CPU", self.usage.latest().value);
+            let history = self
+                .usage
+                .latest()
+                .history
+                .iter()
+                .map(|used| *used as f32 / 100.0)
+                .collect::<Vec<_>>();
             if !output.is_empty() {
                 let mut layout_job = LayoutJob::simple(
                     match self.label_prefix {
@@ -91,16 +122,34 @@ impl BarWidget for Cpu {
                     10.0,
                     TextFormat {
                         font_id: config.text_font_id.clone(),
-                        color: ctx.style().visuals.text_color(),
+                        color: highlight.text_color(ctx, config.background_color),
                         valign: Align::Center,
                         ..Default::default()
                     },
                 );
 
+                let label_layout = config.label_layout;
+                let builtin_click = config.builtin_click;
+
                 config.apply_on_widget(false, ui, |ui| {
                     if SelectableFrame::new(false)
-                        .show(ui, |ui| ui.add(Label::new(layout_job).selectable(false)))
+                        .show(ui, |ui| {
+                            highlight.frame(ctx).show(ui, |ui| {
+                                label_layout.add(ui, layout_job);
+
+                                if let Some(graph) = &self.graph {
+                                    graph.show(
+                                        ui,
+                                        &[GraphSeries {
+                                            values: &history,
+                                            color: ctx.style().visuals.selection.stroke.color,
+                                        }],
+                                    );
+                                }
+                            })
+                        })
                         .clicked()
+                        && builtin_click
                     {
                         if let Err(error) =
                             Command::new("cmd.exe").args(["/C", "taskmgr.exe"]).spawn()
@@ -112,4 +161,10 @@ impl BarWidget for Cpu {
             }
         }
     }
+
+    fn handle_action(&mut self, action: InternalAction) {
+        if action == InternalAction::ToggleLabel {
+            self.label_prefix.toggle_text();
+        }
+    }
 }
@@ -1,14 +1,19 @@
+use crate::calendar::Calendar;
 use crate::config::LabelPrefix;
 use crate::render::RenderConfig;
+use crate::scheduler;
+use crate::scheduler::DataReceiver;
 use crate::selected_frame::SelectableFrame;
+use crate::timezone::Timezone;
 use crate::widget::BarWidget;
+use crate::widget::CommonWidgetConfig;
+use crate::widget::InternalAction;
+use chrono::Datelike;
 use eframe::egui::text::LayoutJob;
 use eframe::egui::Align;
 use eframe::egui::Context;
-use eframe::egui::Label;
 use eframe::egui::TextFormat;
 use eframe::egui::Ui;
-use eframe::egui::WidgetText;
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -23,7 +28,7 @@ pub struct CustomModifiers {
 }
 
 impl CustomModifiers {
-    fn apply(&self, output: &str) -> String {
+    fn apply(&self, output: &str, timezone: &Timezone) -> String {
         let int_formatters = vec![
             "%Y", "%C", "%y", "%m", "%d", "%e", "%w", "%u", "%U", "%W", "%G", "%g", "%V", "%j",
             "%H", "%k", "%I", "%l", "%M", "%S", "%f",
@@ -38,7 +43,7 @@ impl CustomModifiers {
             }
 
             // get the strftime value of modifier
-            let formatted_modifier = chrono::Local::now().format(modifier).to_string();
+            let formatted_modifier = timezone.format(modifier);
 
             // find the gotten value in the original output
             if let Some(pos) = modified_output.find(&formatted_modifier) {
@@ -64,6 +69,15 @@ pub struct DateConfig {
     pub format: DateFormat,
     /// Display label prefix
     pub label_prefix: Option<LabelPrefix>,
+    /// IANA name of the timezone to display the date in (default: local timezone)
+    pub timezone: Option<String>,
+    /// Append the ISO week number to the date (default: false)
+    pub show_week_number: Option<bool>,
+    /// Open a month calendar when the widget is clicked instead of cycling through formats (default: false)
+    pub calendar: Option<bool>,
+    /// Actions, visibility condition and overflow priority of the widget
+    #[serde(flatten)]
+    pub common: CommonWidgetConfig,
 }
 
 impl From<DateConfig> for Date {
@@ -72,6 +86,10 @@ impl From<DateConfig> for Date {
             enable: value.enable,
             format: value.format,
             label_prefix: value.label_prefix.unwrap_or(LabelPrefix::Icon),
+            timezone: Timezone::parse(value.timezone.as_deref()),
+            show_week_number: value.show_week_number.unwrap_or_default(),
+            calendar: value.calendar.unwrap_or_default().then(Calendar::default),
+            clock: scheduler::clock(),
         }
     }
 }
@@ -116,30 +134,40 @@ impl DateFormat {
     }
 }
 
-#[derive(Clone, Debug)]
 pub struct Date {
     pub enable: bool,
     pub format: DateFormat,
     label_prefix: LabelPrefix,
+    timezone: Timezone,
+    show_week_number: bool,
+    calendar: Option<Calendar>,
+    clock: DataReceiver<i64>,
 }
 
 impl Date {
     fn output(&mut self) -> String {
-        let formatted = chrono::Local::now()
-            .format(&self.format.fmt_string())
-            .to_string();
+        let formatted = self.timezone.format(&self.format.fmt_string());
 
         // if custom modifiers are used, apply them
-        match &self.format {
-            DateFormat::CustomModifiers(custom) => custom.apply(&formatted),
+        let mut output = match &self.format {
+            DateFormat::CustomModifiers(custom) => custom.apply(&formatted, &self.timezone),
             _ => formatted,
+        };
+
+        if self.show_week_number {
+            output.push_str(&format!(" W{:02}", self.timezone.today().iso_week().week()));
         }
+
+        output
     }
 }
 
 impl BarWidget for Date {
     fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
         if self.enable {
+            // the scheduler requests a repaint every second to keep the date current
+            self.clock.latest();
+
             let mut output = self.output();
             if !output.is_empty() {
                 let mut layout_job = LayoutJob::simple(
@@ -169,20 +197,38 @@ impl BarWidget for Date {
                     },
                 );
 
+                let today = self.timezone.today();
+
+                let label_layout = config.label_layout;
+                let builtin_click = config.builtin_click;
+
                 config.apply_on_widget(false, ui, |ui| {
-                    if SelectableFrame::new(false)
-                        .show(ui, |ui| {
-                            ui.add(
-                                Label::new(WidgetText::LayoutJob(layout_job.clone()))
-                                    .selectable(false),
-                            )
-                        })
-                        .clicked()
-                    {
-                        self.format.next()
+                    let response = SelectableFrame::new(false)
+                        .show(ui, |ui| label_layout.add(ui, layout_job.clone()));
+
+                    match &mut self.calendar {
+                        Some(calendar) => {
+                            if builtin_click && response.clicked() {
+                                calendar.toggle(today);
+                            }
+
+                            calendar.show(ctx, response.rect, today);
+                        }
+                        None => {
+                            if builtin_click && response.clicked() {
+                                self.format.next()
+                            }
+                        }
                     }
                 });
             }
         }
     }
+
+    fn handle_action(&mut self, action: InternalAction) {
+        match action {
+            InternalAction::CycleFormat => self.format.next(),
+            InternalAction::ToggleLabel => self.label_prefix.toggle_text(),
+        }
+    }
 }
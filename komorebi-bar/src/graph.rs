@@ -0,0 +1,169 @@
+use eframe::egui::Color32;
+use eframe::egui::Pos2;
+use eframe::egui::Rect;
+use eframe::egui::Sense;
+use eframe::egui::Shape;
+use eframe::egui::Stroke;
+use eframe::egui::Ui;
+use eframe::egui::Vec2;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::VecDeque;
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct GraphConfig {
+    /// Number of readings to keep in the history displayed by the graph (default: 30)
+    pub window: Option<usize>,
+    /// How the history is drawn (default: Sparkline)
+    pub style: Option<GraphStyle>,
+    /// Width of the graph (default: 60.0)
+    pub width: Option<f32>,
+}
+
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum GraphStyle {
+    /// A line connecting each reading
+    #[default]
+    Sparkline,
+    /// A bar for each reading
+    Bars,
+}
+
+/// A reading along with the history of previous readings, oldest first
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Sampled<T> {
+    pub value: T,
+    pub history: VecDeque<T>,
+}
+
+/// Wraps a data collector to keep a history of up to `window` readings, which is empty when there
+/// is no graph to display
+pub fn sampled<T, F>(window: usize, mut collect: F) -> impl FnMut() -> Sampled<T> + Send + 'static
+where
+    T: Clone + Send + 'static,
+    F: FnMut() -> T + Send + 'static,
+{
+    let mut history = VecDeque::with_capacity(window);
+
+    move || {
+        let value = collect();
+
+        if window > 0 {
+            if history.len() == window {
+                history.pop_front();
+            }
+
+            history.push_back(value.clone());
+        }
+
+        Sampled {
+            value,
+            history: history.clone(),
+        }
+    }
+}
+
+/// A series of values to draw on a graph
+pub struct GraphSeries<'a> {
+    /// Values normalised to the range 0.0..=1.0, oldest first
+    pub values: &'a [f32],
+    pub color: Color32,
+}
+
+impl GraphConfig {
+    /// The number of readings to keep in the history
+    pub fn window(&self) -> usize {
+        self.window.unwrap_or(30).max(2)
+    }
+
+    /// Draws the series on a graph filling the height available in the bar
+    pub fn show(&self, ui: &mut Ui, series: &[GraphSeries]) {
+        let window = self.window();
+        let width = self.width.unwrap_or(60.0);
+        let height = ui.available_height() - ui.spacing().button_padding.y * 2.0;
+
+        let (response, painter) =
+            ui.allocate_painter(Vec2::new(width, height.max(1.0)), Sense::hover());
+        let rect = response.rect;
+
+        let step = rect.width() / (window - 1) as f32;
+        let point = |idx: usize, offset: usize, value: f32| {
+            Pos2::new(
+                rect.left() + (idx + offset) as f32 * step,
+                rect.bottom() - value.clamp(0.0, 1.0) * rect.height(),
+            )
+        };
+
+        for series in series {
+            // keep the most recent reading on the right edge while the history fills up
+            let offset = window.saturating_sub(series.values.len());
+
+            match self.style.unwrap_or_default() {
+                GraphStyle::Sparkline => {
+                    let points = series
+                        .values
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, value)| point(idx, offset, *value))
+                        .collect::<Vec<_>>();
+
+                    if points.len() > 1 {
+                        painter.add(Shape::line(points, Stroke::new(1.5, series.color)));
+                    }
+                }
+                GraphStyle::Bars => {
+                    // the bars share the width evenly, with a gap between them if there is room
+                    let stride = rect.width() / window as f32;
+                    let bar_width = if stride > 2.0 { stride - 1.0 } else { stride };
+
+                    for (idx, value) in series.values.iter().enumerate() {
+                        let left = rect.left() + (idx + offset) as f32 * stride;
+                        let top = point(idx, offset, *value).y;
+
+                        painter.rect_filled(
+                            Rect::from_min_max(
+                                Pos2::new(left, top),
+                                Pos2::new(left + bar_width, rect.bottom()),
+                            ),
+                            0.0,
+                            series.color.gamma_multiply(0.6),
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sampled;
+
+    #[test]
+    fn history_is_limited_to_the_window() {
+        let mut value = 0;
+        let mut collect = sampled(3, move || {
+            value += 1;
+            value
+        });
+
+        assert_eq!(collect().history, [1]);
+        assert_eq!(collect().history, [1, 2]);
+        assert_eq!(collect().history, [1, 2, 3]);
+
+        let reading = collect();
+        assert_eq!(reading.value, 4);
+        assert_eq!(reading.history, [2, 3, 4]);
+    }
+
+    #[test]
+    fn no_history_without_a_graph() {
+        let mut collect = sampled(0, || 42);
+
+        let reading = collect();
+        assert_eq!(reading.value, 42);
+        assert!(reading.history.is_empty());
+    }
+}
@@ -1,17 +1,18 @@
 use crate::config::LabelPrefix;
 use crate::render::RenderConfig;
+use crate::scheduler;
+use crate::scheduler::DataReceiver;
 use crate::widget::BarWidget;
+use crate::widget::CommonWidgetConfig;
+use crate::widget::InternalAction;
 use eframe::egui::text::LayoutJob;
 use eframe::egui::Align;
 use eframe::egui::Context;
-use eframe::egui::Label;
 use eframe::egui::TextFormat;
 use eframe::egui::Ui;
-use eframe::egui::WidgetText;
 use serde::Deserialize;
 use serde::Serialize;
 use std::time::Duration;
-use std::time::Instant;
 use windows::Win32::Globalization::LCIDToLocaleName;
 use windows::Win32::Globalization::LOCALE_ALLOW_NEUTRAL_NAMES;
 use windows::Win32::System::SystemServices::LOCALE_NAME_MAX_LENGTH;
@@ -22,7 +23,7 @@ use windows::Win32::UI::WindowsAndMessaging::GetWindowThreadProcessId;
 const DEFAULT_DATA_REFRESH_INTERVAL: u64 = 1;
 const ERROR_TEXT: &str = "Error";
 
-#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct KeyboardConfig {
     /// Enable the Input widget
@@ -31,6 +32,9 @@ pub struct KeyboardConfig {
     pub data_refresh_interval: Option<u64>,
     /// Display label prefix
     pub label_prefix: Option<LabelPrefix>,
+    /// Actions, visibility condition and overflow priority of the widget
+    #[serde(flatten)]
+    pub common: CommonWidgetConfig,
 }
 
 impl From<KeyboardConfig> for Keyboard {
@@ -41,20 +45,20 @@ impl From<KeyboardConfig> for Keyboard {
 
         Self {
             enable: value.enable,
-            data_refresh_interval,
             label_prefix: value.label_prefix.unwrap_or(LabelPrefix::IconAndText),
-            last_updated: Instant::now(),
-            lang_name: get_lang(),
+            lang_name: scheduler::schedule(
+                Duration::from_secs(data_refresh_interval),
+                get_lang(),
+                get_lang,
+            ),
         }
     }
 }
 
 pub struct Keyboard {
     pub enable: bool,
-    data_refresh_interval: u64,
     label_prefix: LabelPrefix,
-    last_updated: Instant,
-    lang_name: String,
+    lang_name: DataReceiver<String>,
 }
 
 /// Retrieves the name of the active keyboard layout for the current foreground window.
@@ -127,15 +131,10 @@ fn get_lang() -> String {
 
 impl Keyboard {
     fn output(&mut self) -> String {
-        let now = Instant::now();
-        if now.duration_since(self.last_updated) > Duration::from_secs(self.data_refresh_interval) {
-            self.last_updated = now;
-            self.lang_name = get_lang();
-        }
-
+        let lang_name = self.lang_name.latest();
         match self.label_prefix {
-            LabelPrefix::Text | LabelPrefix::IconAndText => format!("KB: {}", self.lang_name),
-            LabelPrefix::None | LabelPrefix::Icon => self.lang_name.clone(),
+            LabelPrefix::Text | LabelPrefix::IconAndText => format!("KB: {lang_name}"),
+            LabelPrefix::None | LabelPrefix::Icon => lang_name.clone(),
         }
     }
 }
@@ -168,10 +167,16 @@ impl BarWidget for Keyboard {
                     },
                 );
 
-                config.apply_on_widget(true, ui, |ui| {
-                    ui.add(Label::new(WidgetText::LayoutJob(layout_job.clone())).selectable(false))
-                });
+                let label_layout = config.label_layout;
+
+                config.apply_on_widget(true, ui, |ui| label_layout.add(ui, layout_job.clone()));
             }
         }
     }
+
+    fn handle_action(&mut self, action: InternalAction) {
+        if action == InternalAction::ToggleLabel {
+            self.label_prefix.toggle_text();
+        }
+    }
 }
@@ -6,8 +6,8 @@ use crate::komorebi_layout::KomorebiLayout;
 use crate::render::Grouping;
 use crate::render::RenderConfig;
 use crate::selected_frame::SelectableFrame;
-use crate::ui::CustomUi;
 use crate::widget::BarWidget;
+use crate::widget::CommonWidgetConfig;
 use crate::ICON_CACHE;
 use crate::MAX_LABEL_WIDTH;
 use crate::MONITOR_INDEX;
@@ -18,7 +18,6 @@ use eframe::egui::Context;
 use eframe::egui::CornerRadius;
 use eframe::egui::Frame;
 use eframe::egui::Image;
-use eframe::egui::Label;
 use eframe::egui::Margin;
 use eframe::egui::RichText;
 use eframe::egui::Sense;
@@ -30,11 +29,15 @@ use eframe::egui::Ui;
 use eframe::egui::Vec2;
 use image::RgbaImage;
 use komorebi_client::Container;
+use komorebi_client::LabelOverride;
+use komorebi_client::LabelTemplate;
 use komorebi_client::NotificationEvent;
 use komorebi_client::PathExt;
 use komorebi_client::Rect;
+use komorebi_client::RuleTarget;
 use komorebi_client::SocketMessage;
 use komorebi_client::Window;
+use komorebi_client::WindowLabeller;
 use komorebi_client::Workspace;
 use komorebi_client::WorkspaceLayer;
 use serde::Deserialize;
@@ -59,6 +62,9 @@ pub struct KomorebiConfig {
     pub focused_window: Option<KomorebiFocusedWindowConfig>,
     /// Configure the Configuration Switcher widget
     pub configuration_switcher: Option<KomorebiConfigurationSwitcherConfig>,
+    /// Actions, visibility condition and overflow priority of the widget
+    #[serde(flatten)]
+    pub common: CommonWidgetConfig,
 }
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize)]
@@ -94,7 +100,7 @@ pub struct KomorebiWorkspaceLayerConfig {
     pub show_when_tiling: Option<bool>,
 }
 
-#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct KomorebiFocusedWindowConfig {
     /// Enable the Komorebi Focused Window widget
@@ -103,6 +109,11 @@ pub struct KomorebiFocusedWindowConfig {
     pub show_icon: Option<bool>,
     /// Display format of the currently focused window
     pub display: Option<DisplayFormat>,
+    /// Label template for each window, instead of the window title
+    /// (e.g. "{index}: {process} — {title:30}")
+    pub label: Option<LabelTemplate>,
+    /// Label templates for specific applications, where the first matching rule is used
+    pub label_overrides: Option<Vec<LabelOverride>>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -141,13 +152,24 @@ impl From<&KomorebiConfig> for Komorebi {
                 mouse_follows_focus: true,
                 work_area_offset: None,
                 focused_container_information: KomorebiNotificationStateContainerInformation::EMPTY,
+                focused_window: None,
                 stack_accent: None,
                 monitor_index: MONITOR_INDEX.load(Ordering::SeqCst),
                 monitor_usr_idx_map: HashMap::new(),
             })),
             workspaces: value.workspaces,
             layout: value.layout.clone(),
-            focused_window: value.focused_window,
+            focused_window: value.focused_window.clone(),
+            focused_window_labeller: value
+                .focused_window
+                .as_ref()
+                .map(|focused_window| {
+                    WindowLabeller::new(
+                        focused_window.label.clone(),
+                        focused_window.label_overrides.clone().unwrap_or_default(),
+                    )
+                })
+                .unwrap_or_default(),
             workspace_layer: value.workspace_layer,
             configuration_switcher,
         }
@@ -160,6 +182,7 @@ pub struct Komorebi {
     pub workspaces: Option<KomorebiWorkspacesConfig>,
     pub layout: Option<KomorebiLayoutConfig>,
     pub focused_window: Option<KomorebiFocusedWindowConfig>,
+    pub focused_window_labeller: WindowLabeller,
     pub workspace_layer: Option<KomorebiWorkspaceLayerConfig>,
     pub configuration_switcher: Option<KomorebiConfigurationSwitcherConfig>,
 }
@@ -169,6 +192,8 @@ impl BarWidget for Komorebi {
         let mut komorebi_notification_state = self.komorebi_notification_state.borrow_mut();
         let icon_size = Vec2::splat(config.icon_font_id.size);
         let text_size = Vec2::splat(config.text_font_id.size);
+        let builtin_click = config.builtin_click;
+        let label_layout = config.label_layout;
 
         if let Some(workspaces) = self.workspaces {
             if workspaces.enable {
@@ -249,16 +274,16 @@ impl BarWidget for Komorebi {
                                     || (is_selected && matches!(format, WorkspacesDisplayFormat::AllIconsAndTextOnSelected | WorkspacesDisplayFormat::Existing(DisplayFormat::IconAndTextOnSelected)))
                                 {
                                      if is_selected {
-                                        ui.add(Label::new(RichText::new(ws.to_string()).color(ctx.style().visuals.selection.stroke.color)).selectable(false))
+                                        label_layout.add_text(ui, RichText::new(ws.to_string()).color(ctx.style().visuals.selection.stroke.color))
                                     }
                                     else {
-                                        ui.add(Label::new(ws.to_string()).selectable(false))
+                                        label_layout.add_text(ui, ws.to_string())
                                     }
                                 } else {
                                     ui.response()
                                 }
                             })
-                            .clicked()
+                            .clicked() && builtin_click
                             {
                                 update = Some(ws.to_string());
 
@@ -396,12 +421,13 @@ impl BarWidget for Komorebi {
                                     }
 
                                     if display_format != DisplayFormat::Icon {
-                                        ui.add(Label::new(layer.to_string()).selectable(false));
+                                        label_layout.add_text(ui, layer.to_string());
                                     }
                                 })
                                 .on_hover_text(layer.to_string());
 
-                            if layer_frame.clicked()
+                            if builtin_click
+                                && layer_frame.clicked()
                                 && komorebi_client::send_batch([
                                     SocketMessage::MouseFollowsFocus(false),
                                     SocketMessage::ToggleWorkspaceLayer,
@@ -449,9 +475,9 @@ impl BarWidget for Komorebi {
                     if path.is_file() {
                         config.apply_on_widget(false, ui,|ui|{
                     if SelectableFrame::new(false).show(ui, |ui|{
-                          ui.add(Label::new(name).selectable(false))
+                          label_layout.add_text(ui, name)
                             })
-                            .clicked()
+                            .clicked() && builtin_click
                         {
                             let canonicalized = dunce::canonicalize(path.clone()).unwrap_or(path);
                             let mut proceed = true;
@@ -501,7 +527,7 @@ impl BarWidget for Komorebi {
             }
         }
 
-        if let Some(focused_window) = self.focused_window {
+        if let Some(focused_window) = &self.focused_window {
             if focused_window.enable {
                 let titles = &komorebi_notification_state
                     .focused_container_information
@@ -511,6 +537,9 @@ impl BarWidget for Komorebi {
                         let icons = &komorebi_notification_state
                             .focused_container_information
                             .icons;
+                        let targets = &komorebi_notification_state
+                            .focused_container_information
+                            .targets;
                         let focused_window_idx = komorebi_notification_state
                             .focused_container_information
                             .focused_window_idx;
@@ -519,6 +548,12 @@ impl BarWidget for Komorebi {
                         let len = iter.len();
 
                         for (i, (title, icon)) in iter.enumerate() {
+                            let label = targets
+                                .get(i)
+                                .and_then(Option::as_ref)
+                                .and_then(|target| self.focused_window_labeller.label(target, i))
+                                .unwrap_or_else(|| title.clone());
+
                             let selected = i == focused_window_idx && len != 1;
                             let text_color = if selected { ctx.style().visuals.selection.stroke.color} else { ui.style().visuals.text_color() };
 
@@ -552,7 +587,7 @@ impl BarWidget for Komorebi {
                                                     );
 
                                                     if let DisplayFormat::Icon = format {
-                                                        response.on_hover_text(title);
+                                                        response.on_hover_text(&label);
                                                     }
                                                 });
                                         }
@@ -564,19 +599,14 @@ impl BarWidget for Komorebi {
                                         || (format == DisplayFormat::IconAndTextOnSelected
                                             && i == focused_window_idx)
                                     {
-                                        let available_height = ui.available_height();
-                                        let mut custom_ui = CustomUi(ui);
-
-                                        custom_ui.add_sized_left_to_right(
-                                            Vec2::new(
-                                                MAX_LABEL_WIDTH.load(Ordering::SeqCst) as f32,
-                                                available_height,
-                                            ),
-                                            Label::new(RichText::new( title).color(text_color)).selectable(false).truncate(),
+                                        label_layout.add_truncated(
+                                            ui,
+                                            RichText::new(&label).color(text_color),
+                                            MAX_LABEL_WIDTH.load(Ordering::SeqCst) as f32,
                                         );
                                     }
                                 })
-                                .clicked()
+                                .clicked() && builtin_click
                             {
                                 if selected {
                                     return;
@@ -629,6 +659,8 @@ pub struct KomorebiNotificationState {
     )>,
     pub selected_workspace: String,
     pub focused_container_information: KomorebiNotificationStateContainerInformation,
+    /// The focused window on the focused workspace, used to evaluate widget visibility rules
+    pub focused_window: Option<RuleTarget>,
     pub layout: KomorebiLayout,
     pub hide_empty_workspaces: bool,
     pub mouse_follows_focus: bool,
@@ -664,7 +696,7 @@ impl KomorebiNotificationState {
             NotificationEvent::Socket(message) => match message {
                 SocketMessage::ReloadStaticConfiguration(path) => {
                     if let Ok(config) = komorebi_client::StaticConfig::read(&path) {
-                        if let Some(theme) = config.theme {
+                        if let Some(theme) = config.current_theme() {
                             apply_theme(
                                 ctx,
                                 KomobarTheme::from(theme),
@@ -798,13 +830,34 @@ impl KomorebiNotificationState {
         }
 
         self.focused_container_information = (&monitor.workspaces()[focused_workspace_idx]).into();
+        self.focused_window = focused_window(&monitor.workspaces()[focused_workspace_idx])
+            .and_then(|window| RuleTarget::try_from(window).ok());
     }
 }
 
+fn focused_window(workspace: &Workspace) -> Option<Window> {
+    if let Some(container) = workspace.monocle_container() {
+        return container.focused_window().copied();
+    }
+
+    workspace
+        .floating_windows()
+        .iter()
+        .find(|window| window.is_focused())
+        .or_else(|| {
+            workspace
+                .focused_container()
+                .and_then(|container| container.focused_window())
+        })
+        .copied()
+}
+
 #[derive(Clone, Debug)]
 pub struct KomorebiNotificationStateContainerInformation {
     pub titles: Vec<String>,
     pub icons: Vec<Option<RgbaImage>>,
+    /// Targets for the label templates of the windows
+    pub targets: Vec<Option<RuleTarget>>,
     pub focused_window_idx: usize,
 }
 
@@ -862,6 +915,11 @@ impl From<&Container> for KomorebiNotificationStateContainerInformation {
                 .map(|w| w.title().unwrap_or_default())
                 .collect::<Vec<_>>(),
             icons,
+            targets: value
+                .windows()
+                .iter()
+                .map(|w| RuleTarget::try_from(*w).ok())
+                .collect::<Vec<_>>(),
             focused_window_idx: value.focused_window_idx(),
         }
     }
@@ -893,6 +951,7 @@ impl From<&Window> for KomorebiNotificationStateContainerInformation {
         Self {
             titles: vec![value.title().unwrap_or_default()],
             icons,
+            targets: vec![RuleTarget::try_from(*value).ok()],
             focused_window_idx: 0,
         }
     }
@@ -902,6 +961,7 @@ impl KomorebiNotificationStateContainerInformation {
     pub const EMPTY: Self = Self {
         titles: vec![],
         icons: vec![],
+        targets: vec![],
         focused_window_idx: 0,
     };
 }
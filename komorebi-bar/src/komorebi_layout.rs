@@ -229,6 +229,8 @@ impl KomorebiLayout {
         workspace_idx: Option<usize>,
     ) {
         let monitor_idx = render_config.monitor_idx;
+        let builtin_click = render_config.builtin_click;
+        let label_layout = render_config.label_layout;
         let font_id = render_config.icon_font_id.clone();
         let mut show_options = RenderConfig::load_show_komorebi_layout_options();
         let format = layout_config.display.unwrap_or(DisplayFormat::IconAndText);
@@ -245,12 +247,12 @@ impl KomorebiLayout {
                     }
 
                     if let DisplayFormat::Text | DisplayFormat::IconAndText = format {
-                        ui.add(Label::new(self.to_string()).selectable(false));
+                        label_layout.add_text(ui, self.to_string());
                     }
                 })
                 .on_hover_text(self.to_string());
 
-            if layout_frame.clicked() {
+            if builtin_click && layout_frame.clicked() {
                 show_options = self.on_click(&show_options, monitor_idx, workspace_idx);
             }
 
@@ -0,0 +1,216 @@
+use crate::bar::Alignment;
+use crate::render::RenderConfig;
+use crate::widget::BarWidget;
+use crate::widget::WidgetConfig;
+use eframe::egui::Context;
+use eframe::egui::Rect;
+use eframe::egui::RichText;
+use eframe::egui::Ui;
+use serde::Deserialize;
+use serde::Serialize;
+use std::cmp::Reverse;
+
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct LayoutConfig {
+    /// Sizing of the left section
+    pub left: Option<SectionConfig>,
+    /// Sizing of the center section
+    pub center: Option<SectionConfig>,
+    /// Sizing of the right section
+    pub right: Option<SectionConfig>,
+}
+
+/// Sizing of a section of the bar
+///
+/// When a section runs out of space, the widgets in it which have a priority collapse into an
+/// overflow menu, lowest priority first. Widgets without a priority never collapse.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct SectionConfig {
+    /// Fixed width of the section (height on vertical bars), which takes precedence over
+    /// min_width and max_width
+    pub width: Option<f32>,
+    /// Minimum width of the section (height on vertical bars), which keeps the space it takes up
+    /// stable as its widgets change size
+    pub min_width: Option<f32>,
+    /// Maximum width of the section (height on vertical bars), beyond which widgets with a
+    /// priority collapse into the overflow menu
+    pub max_width: Option<f32>,
+}
+
+impl SectionConfig {
+    /// The width available to the widgets of the section, given the space left for it on the bar
+    fn available_width(&self, space: f32) -> f32 {
+        match self.width {
+            Some(width) => width,
+            None => self
+                .max_width
+                .map_or(space, |max_width| max_width.min(space))
+                .max(self.min_width.unwrap_or(0.0)),
+        }
+    }
+}
+
+/// The size of the rect along the main axis of the bar
+fn extent(rect: Rect, vertical: bool) -> f32 {
+    match vertical {
+        true => rect.height(),
+        false => rect.width(),
+    }
+}
+
+/// A widget in a section of the bar
+pub struct SectionWidget {
+    pub widget: Box<dyn BarWidget>,
+    /// Widgets without a priority are never collapsed into the overflow menu
+    priority: Option<u32>,
+    /// Width (height on vertical bars) of the widget the last time it was displayed on the bar
+    width: f32,
+}
+
+impl From<&WidgetConfig> for SectionWidget {
+    fn from(value: &WidgetConfig) -> Self {
+        Self {
+            widget: value.as_boxed_bar_widget(),
+            priority: value.priority(),
+            width: 0.0,
+        }
+    }
+}
+
+/// The left, center or right section of the bar, which collapses its lowest priority widgets
+/// into an overflow menu when they don't fit in the space available to it
+#[derive(Default)]
+pub struct Section {
+    config: SectionConfig,
+    widgets: Vec<SectionWidget>,
+    /// Width of the overflow menu button the last time it was displayed
+    overflow_width: f32,
+    /// Width taken up by the section on the last frame
+    width: f32,
+}
+
+impl Section {
+    pub fn new(config: Option<SectionConfig>, widgets: Vec<SectionWidget>) -> Self {
+        Self {
+            config: config.unwrap_or_default(),
+            widgets,
+            overflow_width: 0.0,
+            width: 0.0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.widgets.is_empty()
+    }
+
+    /// The width the section takes up on the bar
+    pub fn footprint(&self) -> f32 {
+        if self.is_empty() {
+            return 0.0;
+        }
+
+        match self.config.width {
+            Some(width) => width,
+            None => self.width.max(self.config.min_width.unwrap_or(0.0)),
+        }
+    }
+
+    /// Which widgets have to be collapsed into the overflow menu for the section to fit in the
+    /// available width, based on the widths they were last displayed at
+    fn collapsed(&self, available_width: f32, spacing: f32) -> Vec<bool> {
+        let mut collapsed = vec![false; self.widgets.len()];
+
+        let width = |collapsed: &[bool]| {
+            let displayed = self
+                .widgets
+                .iter()
+                .zip(collapsed)
+                .filter(|(_, collapsed)| !**collapsed)
+                .map(|(widget, _)| widget.width + spacing)
+                .sum::<f32>();
+
+            if collapsed.contains(&true) {
+                displayed + self.overflow_width
+            } else {
+                displayed
+            }
+        };
+
+        // lowest priority first, starting with the widgets furthest from the edge of the bar
+        let mut candidates = (0..self.widgets.len())
+            .filter(|idx| self.widgets[*idx].priority.is_some())
+            .collect::<Vec<_>>();
+        candidates.sort_by_key(|idx| (self.widgets[*idx].priority, Reverse(*idx)));
+
+        for idx in candidates {
+            if width(&collapsed) <= available_width {
+                break;
+            }
+
+            collapsed[idx] = true;
+        }
+
+        collapsed
+    }
+
+    /// Renders the widgets which fit in the space left for the section on the bar, followed by
+    /// the overflow menu if any had to be collapsed
+    pub fn show(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig, space: f32) {
+        let vertical = config.label_layout.is_vertical();
+
+        if let Some(min_width) = self.config.width.or(self.config.min_width) {
+            match vertical {
+                true => ui.set_min_height(min_width),
+                false => ui.set_min_width(min_width),
+            }
+        }
+
+        let spacing = match vertical {
+            true => ui.spacing().item_spacing.y,
+            false => ui.spacing().item_spacing.x,
+        };
+
+        let collapsed = self.collapsed(self.config.available_width(space), spacing);
+
+        for (section_widget, collapsed) in self.widgets.iter_mut().zip(&collapsed) {
+            if !collapsed {
+                let response = ui
+                    .scope(|ui| section_widget.widget.render(ctx, ui, config))
+                    .response;
+                section_widget.width = extent(response.rect, vertical);
+            }
+        }
+
+        if collapsed.contains(&true) {
+            // right widgets are stored in reverse, so list them in their configured order
+            let is_reversed = matches!(config.alignment, Some(Alignment::Right));
+            let icon = RichText::new(egui_phosphor::regular::DOTS_THREE_OUTLINE)
+                .font(config.icon_font_id.clone());
+
+            let response = ui
+                .menu_button(icon, |ui| {
+                    let mut overflowed = self
+                        .widgets
+                        .iter_mut()
+                        .zip(&collapsed)
+                        .filter(|(_, collapsed)| **collapsed)
+                        .map(|(section_widget, _)| section_widget)
+                        .collect::<Vec<_>>();
+
+                    if is_reversed {
+                        overflowed.reverse();
+                    }
+
+                    for section_widget in overflowed {
+                        ui.horizontal(|ui| section_widget.widget.render(ctx, ui, config));
+                    }
+                })
+                .response;
+            self.overflow_width = extent(response.rect, vertical);
+        }
+
+        self.width = extent(ui.min_rect(), vertical);
+    }
+}
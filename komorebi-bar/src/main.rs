@@ -1,28 +1,39 @@
 mod bar;
 mod battery;
+mod calendar;
+mod command;
+mod condition;
 mod config;
 mod cpu;
 mod date;
+mod graph;
 mod keyboard;
 mod komorebi;
 mod komorebi_layout;
+mod layout;
 mod media;
 mod memory;
 mod network;
+mod plugin;
 mod render;
+mod scheduler;
 mod selected_frame;
 mod storage;
+mod threshold;
 mod time;
+mod timezone;
 mod ui;
 mod update;
 mod widget;
 
 use crate::bar::Komobar;
 use crate::config::KomobarConfig;
+use crate::config::KomobarTheme;
 use crate::config::Position;
 use crate::config::PositionConfig;
 use clap::Parser;
 use config::MonitorConfigOrIndex;
+use crossbeam_channel::Sender;
 use eframe::egui::ViewportBuilder;
 use font_loader::system_fonts;
 use hotwatch::EventKind;
@@ -37,6 +48,7 @@ use std::path::PathBuf;
 use std::sync::atomic::AtomicI32;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
+use std::sync::Arc;
 use std::sync::LazyLock;
 use std::sync::Mutex;
 use std::time::Duration;
@@ -55,6 +67,7 @@ pub static MAX_LABEL_WIDTH: AtomicI32 = AtomicI32::new(400);
 pub static MONITOR_LEFT: AtomicI32 = AtomicI32::new(0);
 pub static MONITOR_TOP: AtomicI32 = AtomicI32::new(0);
 pub static MONITOR_RIGHT: AtomicI32 = AtomicI32::new(0);
+pub static MONITOR_BOTTOM: AtomicI32 = AtomicI32::new(0);
 pub static MONITOR_INDEX: AtomicUsize = AtomicUsize::new(0);
 pub static BAR_HEIGHT: f32 = 50.0;
 pub static DEFAULT_PADDING: f32 = 10.0;
@@ -62,6 +75,70 @@ pub static DEFAULT_PADDING: f32 = 10.0;
 pub static ICON_CACHE: LazyLock<Mutex<HashMap<String, RgbaImage>>> =
     LazyLock::new(|| Mutex::new(HashMap::new()));
 
+/// Watches the palette file of a custom theme, so that the theme can be reapplied when the
+/// palette changes without reloading the rest of the configuration
+struct PaletteWatch {
+    hotwatch: Hotwatch,
+    path: Option<PathBuf>,
+    tx_theme: Sender<()>,
+}
+
+impl PaletteWatch {
+    fn new(tx_theme: Sender<()>) -> color_eyre::Result<Self> {
+        Ok(Self {
+            hotwatch: Hotwatch::new()?,
+            path: None,
+            tx_theme,
+        })
+    }
+
+    /// Watches the palette file of the given theme instead of the one watched until now
+    fn update(&mut self, theme: Option<&KomobarTheme>) {
+        let path = match theme {
+            Some(KomobarTheme::Custom { path, .. }) => Some(path.clone()),
+            _ => None,
+        };
+
+        if self.path == path {
+            return;
+        }
+
+        if let Some(previous) = self.path.take() {
+            if let Err(error) = self.hotwatch.unwatch(&previous) {
+                tracing::error!("could not stop watching {}: {error}", previous.display());
+            }
+        }
+
+        let Some(path) = path else {
+            return;
+        };
+
+        let tx_theme = self.tx_theme.clone();
+        let watched = self.hotwatch.watch(&path, move |event| match event.kind {
+            EventKind::Modify(_) | EventKind::Remove(_) => {
+                tracing::info!("palette file updated");
+
+                if let Err(error) = tx_theme.send(()) {
+                    tracing::error!("could not send palette update to gui: {error}")
+                }
+
+                scheduler::request_repaint();
+            }
+            _ => {}
+        });
+
+        match watched {
+            Ok(()) => {
+                tracing::info!("watching palette file for changes: {}", path.display());
+                self.path = Some(path);
+            }
+            Err(error) => {
+                tracing::error!("could not watch palette file {}: {error}", path.display());
+            }
+        }
+    }
+}
+
 #[derive(Parser)]
 #[clap(author, about, version)]
 struct Opts {
@@ -263,6 +340,11 @@ fn main() -> color_eyre::Result<()> {
         Ordering::SeqCst,
     );
 
+    MONITOR_BOTTOM.store(
+        state.monitors.elements()[monitor_index].size().bottom,
+        Ordering::SeqCst,
+    );
+
     MONITOR_INDEX.store(monitor_index, Ordering::SeqCst);
 
     match config.position {
@@ -313,6 +395,11 @@ fn main() -> color_eyre::Result<()> {
     let (tx_gui, rx_gui) = crossbeam_channel::unbounded();
     let (tx_config, rx_config) = crossbeam_channel::unbounded();
 
+    let (tx_theme, rx_theme) = crossbeam_channel::unbounded();
+
+    let palette_watch = Arc::new(Mutex::new(PaletteWatch::new(tx_theme)?));
+    palette_watch.lock().unwrap().update(config.theme.as_ref());
+
     let mut hotwatch = Hotwatch::new()?;
     let config_path_cl = config_path.clone();
 
@@ -324,9 +411,13 @@ fn main() -> color_eyre::Result<()> {
                     config_path_cl.as_path().to_string_lossy()
                 );
 
+                palette_watch.lock().unwrap().update(updated.theme.as_ref());
+
                 if let Err(error) = tx_config.send(updated) {
                     tracing::error!("could not send configuration update to gui: {error}")
                 }
+
+                scheduler::request_repaint();
             }
             Err(error) => {
                 tracing::error!("{error}");
@@ -341,11 +432,7 @@ fn main() -> color_eyre::Result<()> {
         "komorebi-bar",
         native_options,
         Box::new(|cc| {
-            let ctx_repainter = cc.egui_ctx.clone();
-            std::thread::spawn(move || loop {
-                std::thread::sleep(Duration::from_secs(1));
-                ctx_repainter.request_repaint();
-            });
+            scheduler::set_repaint_context(&cc.egui_ctx);
 
             let ctx_komorebi = cc.egui_ctx.clone();
             std::thread::spawn(move || {
@@ -424,7 +511,7 @@ fn main() -> color_eyre::Result<()> {
                 }
             });
 
-            Ok(Box::new(Komobar::new(cc, rx_gui, rx_config, config)))
+            Ok(Box::new(Komobar::new(cc, rx_gui, rx_config, rx_theme, config)))
         }),
     )
     .map_err(|error| color_eyre::eyre::Error::msg(error.to_string()))
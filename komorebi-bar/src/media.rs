@@ -1,25 +1,29 @@
 use crate::render::RenderConfig;
+use crate::scheduler;
+use crate::scheduler::DataReceiver;
 use crate::selected_frame::SelectableFrame;
-use crate::ui::CustomUi;
 use crate::widget::BarWidget;
+use crate::widget::CommonWidgetConfig;
 use crate::MAX_LABEL_WIDTH;
 use eframe::egui::text::LayoutJob;
 use eframe::egui::Align;
 use eframe::egui::Context;
-use eframe::egui::Label;
 use eframe::egui::TextFormat;
 use eframe::egui::Ui;
-use eframe::egui::Vec2;
 use serde::Deserialize;
 use serde::Serialize;
 use std::sync::atomic::Ordering;
+use std::time::Duration;
 use windows::Media::Control::GlobalSystemMediaTransportControlsSessionManager;
 
-#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct MediaConfig {
     /// Enable the Media widget
     pub enable: bool,
+    /// Actions, visibility condition and overflow priority of the widget
+    #[serde(flatten)]
+    pub common: CommonWidgetConfig,
 }
 
 impl From<MediaConfig> for Media {
@@ -28,20 +32,27 @@ impl From<MediaConfig> for Media {
     }
 }
 
-#[derive(Clone, Debug)]
 pub struct Media {
     pub enable: bool,
     pub session_manager: GlobalSystemMediaTransportControlsSessionManager,
+    now_playing: DataReceiver<String>,
 }
 
 impl Media {
     pub fn new(enable: bool) -> Self {
+        let session_manager = GlobalSystemMediaTransportControlsSessionManager::RequestAsync()
+            .unwrap()
+            .get()
+            .unwrap();
+
+        let collector = session_manager.clone();
+
         Self {
             enable,
-            session_manager: GlobalSystemMediaTransportControlsSessionManager::RequestAsync()
-                .unwrap()
-                .get()
-                .unwrap(),
+            session_manager,
+            now_playing: scheduler::schedule(Duration::from_secs(1), String::new(), move || {
+                now_playing(&collector)
+            }),
         }
     }
 
@@ -54,26 +65,31 @@ impl Media {
     }
 
     fn output(&mut self) -> String {
-        if let Ok(session) = self.session_manager.GetCurrentSession() {
-            if let Ok(operation) = session.TryGetMediaPropertiesAsync() {
-                if let Ok(properties) = operation.get() {
-                    if let (Ok(artist), Ok(title)) = (properties.Artist(), properties.Title()) {
-                        if artist.is_empty() {
-                            return format!("{title}");
-                        }
-
-                        if title.is_empty() {
-                            return format!("{artist}");
-                        }
-
-                        return format!("{artist} - {title}");
+        self.now_playing.latest().clone()
+    }
+}
+
+/// The artist and title of the media which is currently playing
+fn now_playing(session_manager: &GlobalSystemMediaTransportControlsSessionManager) -> String {
+    if let Ok(session) = session_manager.GetCurrentSession() {
+        if let Ok(operation) = session.TryGetMediaPropertiesAsync() {
+            if let Ok(properties) = operation.get() {
+                if let (Ok(artist), Ok(title)) = (properties.Artist(), properties.Title()) {
+                    if artist.is_empty() {
+                        return format!("{title}");
                     }
+
+                    if title.is_empty() {
+                        return format!("{artist}");
+                    }
+
+                    return format!("{artist} - {title}");
                 }
             }
         }
-
-        String::new()
     }
+
+    String::new()
 }
 
 impl BarWidget for Media {
@@ -99,21 +115,20 @@ impl BarWidget for Media {
                     },
                 );
 
+                let label_layout = config.label_layout;
+                let builtin_click = config.builtin_click;
+
                 config.apply_on_widget(false, ui, |ui| {
                     if SelectableFrame::new(false)
                         .show(ui, |ui| {
-                            let available_height = ui.available_height();
-                            let mut custom_ui = CustomUi(ui);
-
-                            custom_ui.add_sized_left_to_right(
-                                Vec2::new(
-                                    MAX_LABEL_WIDTH.load(Ordering::SeqCst) as f32,
-                                    available_height,
-                                ),
-                                Label::new(layout_job).selectable(false).truncate(),
+                            label_layout.add_truncated(
+                                ui,
+                                layout_job,
+                                MAX_LABEL_WIDTH.load(Ordering::SeqCst) as f32,
                             )
                         })
                         .clicked()
+                        && builtin_click
                     {
                         self.toggle();
                     }
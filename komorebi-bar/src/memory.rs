@@ -1,22 +1,30 @@
 use crate::config::LabelPrefix;
+use crate::graph::sampled;
+use crate::graph::GraphConfig;
+use crate::graph::GraphSeries;
+use crate::graph::Sampled;
 use crate::render::RenderConfig;
+use crate::scheduler;
+use crate::scheduler::DataReceiver;
 use crate::selected_frame::SelectableFrame;
+use crate::threshold::ThresholdConfig;
+use crate::threshold::Thresholds;
 use crate::widget::BarWidget;
+use crate::widget::CommonWidgetConfig;
+use crate::widget::InternalAction;
 use eframe::egui::text::LayoutJob;
 use eframe::egui::Align;
 use eframe::egui::Context;
-use eframe::egui::Label;
 use eframe::egui::TextFormat;
 use eframe::egui::Ui;
 use serde::Deserialize;
 use serde::Serialize;
 use std::process::Command;
 use std::time::Duration;
-use std::time::Instant;
 use sysinfo::RefreshKind;
 use sysinfo::System;
 
-#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct MemoryConfig {
     /// Enable the Memory widget
@@ -25,6 +33,13 @@ pub struct MemoryConfig {
     pub data_refresh_interval: Option<u64>,
     /// Display label prefix
     pub label_prefix: Option<LabelPrefix>,
+    /// Thresholds at which the widget is highlighted and alerts are sent
+    pub thresholds: Option<Vec<ThresholdConfig>>,
+    /// Display a graph of the usage history
+    pub graph: Option<GraphConfig>,
+    /// Actions, visibility condition and overflow priority of the widget
+    #[serde(flatten)]
+    pub common: CommonWidgetConfig,
 }
 
 impl From<MemoryConfig> for Memory {
@@ -33,41 +48,52 @@ impl From<MemoryConfig> for Memory {
 
         Self {
             enable: value.enable,
-            system: System::new_with_specifics(
-                RefreshKind::default().without_cpu().without_processes(),
+            usage: scheduler::schedule(
+                Duration::from_secs(data_refresh_interval),
+                Sampled::default(),
+                sampled(
+                    value.graph.map_or(0, |graph| graph.window()),
+                    memory_usage(),
+                ),
             ),
-            data_refresh_interval,
             label_prefix: value.label_prefix.unwrap_or(LabelPrefix::IconAndText),
-            last_updated: Instant::now()
-                .checked_sub(Duration::from_secs(data_refresh_interval))
-                .unwrap(),
+            thresholds: Thresholds::from(value.thresholds),
+            graph: value.graph,
         }
     }
 }
 
+/// Returns a data collector for the percentage of memory in use, which is shared by every widget
+/// and condition
+pub fn memory_usage() -> impl FnMut() -> u64 + Send + 'static {
+    scheduler::shared("memory", || {
+        let mut system =
+            System::new_with_specifics(RefreshKind::default().without_cpu().without_processes());
+
+        move || {
+            system.refresh_memory();
+
+            (system.used_memory() * 100)
+                .checked_div(system.total_memory())
+                .unwrap_or_default()
+        }
+    })
+}
+
 pub struct Memory {
     pub enable: bool,
-    system: System,
-    data_refresh_interval: u64,
+    usage: DataReceiver<Sampled<u64>>,
     label_prefix: LabelPrefix,
-    last_updated: Instant,
+    thresholds: Thresholds,
+    graph: Option<GraphConfig>,
 }
 
 impl Memory {
     fn output(&mut self) -> String {
-        let now = Instant::now();
-        if now.duration_since(self.last_updated) > Duration::from_secs(self.data_refresh_interval) {
-            self.system.refresh_memory();
-            self.last_updated = now;
-        }
-
-        let used = self.system.used_memory();
-        let total = self.system.total_memory();
+        let used = self.usage.latest().value;
         match self.label_prefix {
-            LabelPrefix::Text | LabelPrefix::IconAndText => {
-                format!("RAM: {}%", (used * 100) / total)
-            }
-            LabelPrefix::None | LabelPrefix::Icon => format!("{}%", (used * 100) / total),
+            LabelPrefix::Text | LabelPrefix::IconAndText => format!("RAM: {}%", used),
+            LabelPrefix::None | LabelPrefix::Icon => format!("{}%", used),
         }
     }
 }
@@ -76,6 +102,16 @@ impl BarWidget for Memory {
     fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
         if self.enable {
             let output = self.output();
+            let highlight = self
+                .thresholds
+                .evaluate("RAM", self.usage.latest().value as u32);
+            let history = self
+                .usage
+                .latest()
+                .history
+                .iter()
+                .map(|used| *used as f32 / 100.0)
+                .collect::<Vec<_>>();
             if !output.is_empty() {
                 let mut layout_job = LayoutJob::simple(
                     match self.label_prefix {
@@ -94,16 +130,34 @@ impl BarWidget for Memory {
                     10.0,
                     TextFormat {
                         font_id: config.text_font_id.clone(),
-                        color: ctx.style().visuals.text_color(),
+                        color: highlight.text_color(ctx, config.background_color),
                         valign: Align::Center,
                         ..Default::default()
                     },
                 );
 
+                let label_layout = config.label_layout;
+                let builtin_click = config.builtin_click;
+
                 config.apply_on_widget(false, ui, |ui| {
                     if SelectableFrame::new(false)
-                        .show(ui, |ui| ui.add(Label::new(layout_job).selectable(false)))
+                        .show(ui, |ui| {
+                            highlight.frame(ctx).show(ui, |ui| {
+                                label_layout.add(ui, layout_job);
+
+                                if let Some(graph) = &self.graph {
+                                    graph.show(
+                                        ui,
+                                        &[GraphSeries {
+                                            values: &history,
+                                            color: ctx.style().visuals.selection.stroke.color,
+                                        }],
+                                    );
+                                }
+                            })
+                        })
                         .clicked()
+                        && builtin_click
                     {
                         if let Err(error) =
                             Command::new("cmd.exe").args(["/C", "taskmgr.exe"]).spawn()
@@ -115,4 +169,10 @@ impl BarWidget for Memory {
             }
         }
     }
+
+    fn handle_action(&mut self, action: InternalAction) {
+        if action == InternalAction::ToggleLabel {
+            self.label_prefix.toggle_text();
+        }
+    }
 }
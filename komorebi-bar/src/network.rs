@@ -1,23 +1,28 @@
 use crate::config::LabelPrefix;
+use crate::graph::GraphConfig;
+use crate::graph::GraphSeries;
 use crate::render::RenderConfig;
+use crate::scheduler;
+use crate::scheduler::DataReceiver;
 use crate::selected_frame::SelectableFrame;
 use crate::widget::BarWidget;
+use crate::widget::CommonWidgetConfig;
+use crate::widget::InternalAction;
 use eframe::egui::text::LayoutJob;
 use eframe::egui::Align;
 use eframe::egui::Context;
-use eframe::egui::Label;
 use eframe::egui::TextFormat;
 use eframe::egui::Ui;
 use num_derive::FromPrimitive;
 use serde::Deserialize;
 use serde::Serialize;
+use std::collections::VecDeque;
 use std::fmt;
 use std::process::Command;
 use std::time::Duration;
-use std::time::Instant;
 use sysinfo::Networks;
 
-#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct NetworkConfig {
     /// Enable the Network widget
@@ -34,116 +39,131 @@ pub struct NetworkConfig {
     pub data_refresh_interval: Option<u64>,
     /// Display label prefix
     pub label_prefix: Option<LabelPrefix>,
+    /// Display a graph of the network activity history
+    pub graph: Option<GraphConfig>,
+    /// Actions, visibility condition and overflow priority of the widget
+    #[serde(flatten)]
+    pub common: CommonWidgetConfig,
 }
 
 impl From<NetworkConfig> for Network {
     fn from(value: NetworkConfig) -> Self {
         let data_refresh_interval = value.data_refresh_interval.unwrap_or(10);
+        let show_total_activity = value.show_total_data_transmitted;
+        let show_activity = value.show_network_activity;
+        let window = value.graph.map_or(0, |graph| graph.window());
+
+        let mut networks = Networks::new_with_refreshed_list();
+        let mut history = VecDeque::with_capacity(window);
 
         Self {
             enable: value.enable,
-            show_total_activity: value.show_total_data_transmitted,
-            show_activity: value.show_network_activity,
+            show_total_activity,
+            show_activity,
             show_default_interface: value.show_default_interface.unwrap_or(true),
-            networks_network_activity: Networks::new_with_refreshed_list(),
-            default_interface: String::new(),
-            data_refresh_interval,
+            data: scheduler::schedule(
+                Duration::from_secs(data_refresh_interval),
+                NetworkData::default(),
+                move || {
+                    let mut data = NetworkData::default();
+                    let mut rate = (0, 0);
+
+                    if let Ok(interface) = netdev::get_default_interface() {
+                        if let Some(friendly_name) = &interface.friendly_name {
+                            data.default_interface.clone_from(friendly_name);
+
+                            if show_activity || show_total_activity || window > 0 {
+                                networks.refresh(true);
+
+                                for (interface_name, network) in &networks {
+                                    if friendly_name.eq(interface_name) {
+                                        rate = (
+                                            network.received() / data_refresh_interval.max(1),
+                                            network.transmitted() / data_refresh_interval.max(1),
+                                        );
+
+                                        if show_activity {
+                                            data.activity.push(NetworkReading::new(
+                                                NetworkReadingFormat::Speed,
+                                                Network::to_pretty_bytes(
+                                                    network.received(),
+                                                    data_refresh_interval,
+                                                ),
+                                                Network::to_pretty_bytes(
+                                                    network.transmitted(),
+                                                    data_refresh_interval,
+                                                ),
+                                            ));
+                                        }
+
+                                        if show_total_activity {
+                                            data.total_activity.push(NetworkReading::new(
+                                                NetworkReadingFormat::Total,
+                                                Network::to_pretty_bytes(
+                                                    network.total_received(),
+                                                    1,
+                                                ),
+                                                Network::to_pretty_bytes(
+                                                    network.total_transmitted(),
+                                                    1,
+                                                ),
+                                            ))
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    if window > 0 {
+                        if history.len() == window {
+                            history.pop_front();
+                        }
+
+                        history.push_back(rate);
+                        data.history = history.clone();
+                    }
+
+                    data
+                },
+            ),
             label_prefix: value.label_prefix.unwrap_or(LabelPrefix::Icon),
             network_activity_fill_characters: value
                 .network_activity_fill_characters
                 .unwrap_or_default(),
-            last_state_total_activity: vec![],
-            last_state_activity: vec![],
-            last_updated_network_activity: Instant::now()
-                .checked_sub(Duration::from_secs(data_refresh_interval))
-                .unwrap(),
+            graph: value.graph,
         }
     }
 }
 
+/// The network information collected in the background by the scheduler
+#[derive(Clone, Debug, Default, PartialEq)]
+struct NetworkData {
+    default_interface: String,
+    activity: Vec<NetworkReading>,
+    total_activity: Vec<NetworkReading>,
+    /// Bytes received and transmitted per second, oldest first
+    history: VecDeque<(u64, u64)>,
+}
+
 pub struct Network {
     pub enable: bool,
     pub show_total_activity: bool,
     pub show_activity: bool,
     pub show_default_interface: bool,
-    networks_network_activity: Networks,
-    data_refresh_interval: u64,
+    data: DataReceiver<NetworkData>,
     label_prefix: LabelPrefix,
-    default_interface: String,
-    last_state_total_activity: Vec<NetworkReading>,
-    last_state_activity: Vec<NetworkReading>,
-    last_updated_network_activity: Instant,
     network_activity_fill_characters: usize,
+    graph: Option<GraphConfig>,
 }
 
 impl Network {
-    fn default_interface(&mut self) {
-        if let Ok(interface) = netdev::get_default_interface() {
-            if let Some(friendly_name) = &interface.friendly_name {
-                self.default_interface.clone_from(friendly_name);
-            }
-        }
-    }
-
-    fn network_activity(&mut self) -> (Vec<NetworkReading>, Vec<NetworkReading>) {
-        let mut activity = self.last_state_activity.clone();
-        let mut total_activity = self.last_state_total_activity.clone();
-        let now = Instant::now();
-
-        if now.duration_since(self.last_updated_network_activity)
-            > Duration::from_secs(self.data_refresh_interval)
-        {
-            activity.clear();
-            total_activity.clear();
-
-            if let Ok(interface) = netdev::get_default_interface() {
-                if let Some(friendly_name) = &interface.friendly_name {
-                    self.default_interface.clone_from(friendly_name);
-
-                    self.networks_network_activity.refresh(true);
-
-                    for (interface_name, data) in &self.networks_network_activity {
-                        if friendly_name.eq(interface_name) {
-                            if self.show_activity {
-                                activity.push(NetworkReading::new(
-                                    NetworkReadingFormat::Speed,
-                                    Self::to_pretty_bytes(
-                                        data.received(),
-                                        self.data_refresh_interval,
-                                    ),
-                                    Self::to_pretty_bytes(
-                                        data.transmitted(),
-                                        self.data_refresh_interval,
-                                    ),
-                                ));
-                            }
-
-                            if self.show_total_activity {
-                                total_activity.push(NetworkReading::new(
-                                    NetworkReadingFormat::Total,
-                                    Self::to_pretty_bytes(data.total_received(), 1),
-                                    Self::to_pretty_bytes(data.total_transmitted(), 1),
-                                ))
-                            }
-                        }
-                    }
-                }
-            }
-
-            self.last_state_activity.clone_from(&activity);
-            self.last_state_total_activity.clone_from(&total_activity);
-            self.last_updated_network_activity = now;
-        }
-
-        (activity, total_activity)
-    }
-
-    fn reading_to_label(
+    fn reading_to_layout_job(
         &self,
         ctx: &Context,
         reading: NetworkReading,
         config: RenderConfig,
-    ) -> Label {
+    ) -> LayoutJob {
         let (text_down, text_up) = match self.label_prefix {
             LabelPrefix::None | LabelPrefix::Icon => match reading.format {
                 NetworkReadingFormat::Speed => (
@@ -233,7 +253,7 @@ impl Network {
             text_format.clone(),
         );
 
-        Label::new(layout_job).selectable(false)
+        layout_job
     }
 
     fn to_pretty_bytes(input_in_bytes: u64, timespan_in_s: u64) -> String {
@@ -262,30 +282,69 @@ impl BarWidget for Network {
             // widget spacing: make sure to use the same config to call the apply_on_widget function
             let mut render_config = config.clone();
 
-            if self.show_total_activity || self.show_activity {
-                let (activity, total_activity) = self.network_activity();
+            let data = self.data.latest().clone();
 
-                if self.show_total_activity {
-                    for reading in total_activity {
-                        render_config.apply_on_widget(true, ui, |ui| {
-                            ui.add(self.reading_to_label(ctx, reading, config.clone()));
-                        });
-                    }
+            if self.show_total_activity {
+                for reading in data.total_activity {
+                    render_config.apply_on_widget(true, ui, |ui| {
+                        config
+                            .label_layout
+                            .add(ui, self.reading_to_layout_job(ctx, reading, config.clone()));
+                    });
                 }
+            }
 
-                if self.show_activity {
-                    for reading in activity {
-                        render_config.apply_on_widget(true, ui, |ui| {
-                            ui.add(self.reading_to_label(ctx, reading, config.clone()));
-                        });
-                    }
+            if self.show_activity {
+                for reading in data.activity {
+                    render_config.apply_on_widget(true, ui, |ui| {
+                        config
+                            .label_layout
+                            .add(ui, self.reading_to_layout_job(ctx, reading, config.clone()));
+                    });
                 }
             }
 
+            if let Some(graph) = &self.graph {
+                let max = data
+                    .history
+                    .iter()
+                    .map(|(received, transmitted)| *received.max(transmitted))
+                    .max()
+                    .unwrap_or_default()
+                    .max(1) as f32;
+
+                let received = data
+                    .history
+                    .iter()
+                    .map(|(received, _)| *received as f32 / max)
+                    .collect::<Vec<_>>();
+                let transmitted = data
+                    .history
+                    .iter()
+                    .map(|(_, transmitted)| *transmitted as f32 / max)
+                    .collect::<Vec<_>>();
+
+                render_config.apply_on_widget(true, ui, |ui| {
+                    graph.show(
+                        ui,
+                        &[
+                            GraphSeries {
+                                values: &received,
+                                color: ctx.style().visuals.selection.stroke.color,
+                            },
+                            GraphSeries {
+                                values: &transmitted,
+                                color: ctx.style().visuals.text_color(),
+                            },
+                        ],
+                    );
+                });
+            }
+
             if self.show_default_interface {
-                self.default_interface();
+                let mut default_interface = data.default_interface;
 
-                if !self.default_interface.is_empty() {
+                if !default_interface.is_empty() {
                     let mut layout_job = LayoutJob::simple(
                         match self.label_prefix {
                             LabelPrefix::Icon | LabelPrefix::IconAndText => {
@@ -299,11 +358,11 @@ impl BarWidget for Network {
                     );
 
                     if let LabelPrefix::Text | LabelPrefix::IconAndText = self.label_prefix {
-                        self.default_interface.insert_str(0, "NET: ");
+                        default_interface.insert_str(0, "NET: ");
                     }
 
                     layout_job.append(
-                        &self.default_interface,
+                        &default_interface,
                         10.0,
                         TextFormat {
                             font_id: config.text_font_id.clone(),
@@ -313,10 +372,14 @@ impl BarWidget for Network {
                         },
                     );
 
+                    let label_layout = render_config.label_layout;
+                    let builtin_click = render_config.builtin_click;
+
                     render_config.apply_on_widget(false, ui, |ui| {
                         if SelectableFrame::new(false)
-                            .show(ui, |ui| ui.add(Label::new(layout_job).selectable(false)))
+                            .show(ui, |ui| label_layout.add(ui, layout_job))
                             .clicked()
+                            && builtin_click
                         {
                             if let Err(error) = Command::new("cmd.exe").args(["/C", "ncpa"]).spawn()
                             {
@@ -331,15 +394,21 @@ impl BarWidget for Network {
             *config = render_config.clone();
         }
     }
+
+    fn handle_action(&mut self, action: InternalAction) {
+        if action == InternalAction::ToggleLabel {
+            self.label_prefix.toggle_text();
+        }
+    }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 enum NetworkReadingFormat {
     Speed = 0,
     Total = 1,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 struct NetworkReading {
     pub format: NetworkReadingFormat,
     pub received_text: String,
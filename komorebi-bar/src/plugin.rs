@@ -0,0 +1,325 @@
+use crate::command::CommandOutput;
+use crate::komorebi::KomorebiNotificationState;
+use crate::render::RenderConfig;
+use crate::scheduler;
+use crate::scheduler::DataReceiver;
+use crate::selected_frame::SelectableFrame;
+use crate::widget::BarWidget;
+use crate::widget::CommonWidgetConfig;
+use color_eyre::eyre;
+use color_eyre::Result;
+use eframe::egui::Context;
+use eframe::egui::Ui;
+use serde::Deserialize;
+use serde::Serialize;
+use std::ffi::c_char;
+use std::ffi::c_void;
+use std::ffi::CStr;
+use std::ffi::CString;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use windows::core::s;
+use windows::core::HSTRING;
+use windows::core::PCSTR;
+use windows::Win32::Foundation::FreeLibrary;
+use windows::Win32::Foundation::HMODULE;
+use windows::Win32::System::LibraryLoader::GetProcAddress;
+use windows::Win32::System::LibraryLoader::LoadLibraryW;
+
+/// The version of the plugin ABI implemented by this version of komorebi-bar
+///
+/// A plugin is a dynamic library exporting the following C ABI functions, with all strings being
+/// null-terminated UTF-8 JSON:
+///
+/// ```c
+/// // must return the ABI version the plugin was built against
+/// uint32_t komobar_plugin_abi_version(void);
+/// // receives the `config` value from komorebi.bar.json and returns an opaque instance pointer
+/// void *komobar_plugin_new(const char *config);
+/// // receives the komorebi state and a tick counter, and returns a `PluginOutput`; the returned
+/// // string must remain valid until the next call to update or free for this instance
+/// const char *komobar_plugin_update(void *instance, const char *state, uint64_t tick);
+/// // releases the instance
+/// void komobar_plugin_free(void *instance);
+/// ```
+///
+/// Updates are run on a background thread, and the functions are never called for the same
+/// instance from more than one thread at a time.
+pub const PLUGIN_ABI_VERSION: u32 = 1;
+
+type AbiVersionFn = unsafe extern "C" fn() -> u32;
+type NewFn = unsafe extern "C" fn(*const c_char) -> *mut c_void;
+type UpdateFn = unsafe extern "C" fn(*mut c_void, *const c_char, u64) -> *const c_char;
+type FreeFn = unsafe extern "C" fn(*mut c_void);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PluginConfig {
+    /// Enable the Plugin widget
+    pub enable: bool,
+    /// Path to the plugin library (.dll)
+    pub path: PathBuf,
+    /// Configuration passed to the plugin as JSON when it is loaded
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "Option<schemars::_serde_json::Value>")
+    )]
+    pub config: Option<serde_json::Value>,
+    /// Data refresh interval (default: 1 second)
+    pub data_refresh_interval: Option<u64>,
+    /// Actions, visibility condition and overflow priority of the widget
+    #[serde(flatten)]
+    pub common: CommonWidgetConfig,
+}
+
+/// The labels returned by a plugin on each update
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PluginOutput {
+    /// Labels to render, each using the same payload as the Command widget's JSON output format
+    #[serde(default)]
+    pub labels: Vec<CommandOutput>,
+}
+
+/// The subset of the komorebi state which is passed to plugins on each update
+#[derive(Serialize)]
+struct PluginState<'a> {
+    monitor_index: usize,
+    selected_workspace: &'a str,
+    workspaces: Vec<PluginWorkspace<'a>>,
+    focused_window_titles: &'a [String],
+    focused_window_idx: usize,
+    layout: String,
+    mouse_follows_focus: bool,
+}
+
+#[derive(Serialize)]
+struct PluginWorkspace<'a> {
+    name: &'a str,
+    layer: String,
+    containers: usize,
+}
+
+impl<'a> From<&'a KomorebiNotificationState> for PluginState<'a> {
+    fn from(value: &'a KomorebiNotificationState) -> Self {
+        Self {
+            monitor_index: value.monitor_index,
+            selected_workspace: &value.selected_workspace,
+            workspaces: value
+                .workspaces
+                .iter()
+                .map(|(name, containers, layer)| PluginWorkspace {
+                    name,
+                    layer: layer.to_string(),
+                    containers: containers.len(),
+                })
+                .collect(),
+            focused_window_titles: &value.focused_container_information.titles,
+            focused_window_idx: value.focused_container_information.focused_window_idx,
+            layout: value.layout.to_string(),
+            mouse_follows_focus: value.mouse_follows_focus,
+        }
+    }
+}
+
+/// A loaded plugin library and the instance created from it
+struct PluginLibrary {
+    module: HMODULE,
+    instance: *mut c_void,
+    update: UpdateFn,
+    free: FreeFn,
+}
+
+impl PluginLibrary {
+    fn load(path: &Path, config: &serde_json::Value) -> Result<Self> {
+        let module = unsafe { LoadLibraryW(&HSTRING::from(path.as_os_str())) }?;
+
+        let library = (|| -> Result<Self> {
+            let abi_version: AbiVersionFn = unsafe {
+                std::mem::transmute(proc_address(module, s!("komobar_plugin_abi_version"))?)
+            };
+            let new: NewFn =
+                unsafe { std::mem::transmute(proc_address(module, s!("komobar_plugin_new"))?) };
+            let update: UpdateFn =
+                unsafe { std::mem::transmute(proc_address(module, s!("komobar_plugin_update"))?) };
+            let free: FreeFn =
+                unsafe { std::mem::transmute(proc_address(module, s!("komobar_plugin_free"))?) };
+
+            let version = unsafe { abi_version() };
+            if version != PLUGIN_ABI_VERSION {
+                eyre::bail!(
+                    "plugin abi version {version} is not supported (expected {PLUGIN_ABI_VERSION})"
+                );
+            }
+
+            let config = CString::new(serde_json::to_string(config)?)?;
+            let instance = unsafe { new(config.as_ptr()) };
+            if instance.is_null() {
+                eyre::bail!("plugin could not be initialized");
+            }
+
+            Ok(Self {
+                module,
+                instance,
+                update,
+                free,
+            })
+        })();
+
+        if library.is_err() {
+            unsafe {
+                let _ = FreeLibrary(module);
+            }
+        }
+
+        library
+    }
+
+    fn update(&mut self, state: &str, tick: u64) -> Result<PluginOutput> {
+        let state = CString::new(state)?;
+        let output = unsafe { (self.update)(self.instance, state.as_ptr(), tick) };
+        if output.is_null() {
+            return Ok(PluginOutput::default());
+        }
+
+        let output = unsafe { CStr::from_ptr(output) }.to_str()?;
+        Ok(serde_json::from_str(output)?)
+    }
+}
+
+// The plugin instance is only ever used by the data collector which owns it, which is never run
+// on more than one thread at a time
+unsafe impl Send for PluginLibrary {}
+
+impl Drop for PluginLibrary {
+    fn drop(&mut self) {
+        unsafe {
+            (self.free)(self.instance);
+            let _ = FreeLibrary(self.module);
+        }
+    }
+}
+
+fn proc_address(module: HMODULE, name: PCSTR) -> Result<unsafe extern "system" fn() -> isize> {
+    unsafe { GetProcAddress(module, name) }.ok_or_else(|| {
+        eyre::eyre!(
+            "plugin does not export {}",
+            unsafe { name.to_string() }.unwrap_or_default()
+        )
+    })
+}
+
+impl From<PluginConfig> for Plugin {
+    fn from(value: PluginConfig) -> Self {
+        let data_refresh_interval = value.data_refresh_interval.unwrap_or(1);
+
+        let library = if value.enable {
+            match PluginLibrary::load(
+                &value.path,
+                &value.config.unwrap_or(serde_json::Value::Null),
+            ) {
+                Ok(library) => Some(library),
+                Err(error) => {
+                    tracing::error!(
+                        "could not load plugin {}: {error}",
+                        value.path.to_string_lossy()
+                    );
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let state = Arc::new(Mutex::new(String::from("null")));
+
+        Self {
+            enable: value.enable,
+            labels: library.map(|library| {
+                scheduler::schedule(
+                    Duration::from_secs(data_refresh_interval),
+                    vec![],
+                    updates(library, state.clone()),
+                )
+            }),
+            state,
+        }
+    }
+}
+
+/// Returns a data collector which updates the plugin with the most recent komorebi state, the
+/// previous labels are kept if an update fails
+fn updates(
+    mut library: PluginLibrary,
+    state: Arc<Mutex<String>>,
+) -> impl FnMut() -> Vec<CommandOutput> + Send + 'static {
+    let mut tick = 0;
+    let mut labels = vec![];
+
+    move || {
+        let state = state
+            .lock()
+            .map(|state| state.clone())
+            .unwrap_or_else(|_| String::from("null"));
+
+        match library.update(&state, tick) {
+            Ok(output) => labels = output.labels,
+            Err(error) => tracing::error!("plugin update failed: {error}"),
+        }
+
+        tick += 1;
+        labels.clone()
+    }
+}
+
+pub struct Plugin {
+    pub enable: bool,
+    labels: Option<DataReceiver<Vec<CommandOutput>>>,
+    /// The komorebi state passed to the plugin on its next update, serialized as JSON
+    state: Arc<Mutex<String>>,
+}
+
+impl Plugin {
+    fn output(&mut self, config: &RenderConfig) -> Vec<CommandOutput> {
+        let Some(labels) = &mut self.labels else {
+            return vec![];
+        };
+
+        if let Some(state) = config
+            .komorebi_notification_state
+            .as_ref()
+            .and_then(|state| serde_json::to_string(&PluginState::from(&*state.borrow())).ok())
+        {
+            if let Ok(mut guard) = self.state.lock() {
+                *guard = state;
+            }
+        }
+
+        labels.latest().clone()
+    }
+}
+
+impl BarWidget for Plugin {
+    fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
+        if self.enable {
+            let labels = self.output(config);
+
+            for label in labels.iter().filter(|label| !label.text.is_empty()) {
+                let layout_job = label.layout_job(ctx, config, "");
+
+                let label_layout = config.label_layout;
+
+                config.apply_on_widget(false, ui, |ui| {
+                    let response =
+                        SelectableFrame::new(false).show(ui, |ui| label_layout.add(ui, layout_job));
+
+                    if let Some(tooltip) = &label.tooltip {
+                        response.on_hover_text(tooltip);
+                    }
+                });
+            }
+        }
+    }
+}
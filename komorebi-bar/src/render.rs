@@ -1,18 +1,33 @@
 use crate::bar::Alignment;
 use crate::config::KomobarConfig;
 use crate::config::MonitorConfigOrIndex;
+use crate::config::VerticalLabels;
+use crate::komorebi::KomorebiNotificationState;
+use crate::ui::CustomUi;
+use eframe::egui::text::LayoutJob;
+use eframe::egui::Align;
 use eframe::egui::Color32;
 use eframe::egui::Context;
 use eframe::egui::CornerRadius;
 use eframe::egui::FontId;
+use eframe::egui::FontSelection;
 use eframe::egui::Frame;
 use eframe::egui::InnerResponse;
+use eframe::egui::Label;
 use eframe::egui::Margin;
+use eframe::egui::Response;
+use eframe::egui::Sense;
 use eframe::egui::Shadow;
 use eframe::egui::TextStyle;
 use eframe::egui::Ui;
+use eframe::egui::Vec2;
+use eframe::egui::WidgetText;
+use eframe::epaint::TextShape;
 use serde::Deserialize;
 use serde::Serialize;
+use std::cell::RefCell;
+use std::f32::consts::FRAC_PI_2;
+use std::rc::Rc;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
@@ -55,6 +70,96 @@ pub struct RenderConfig {
     pub icon_font_id: FontId,
     /// Show all icons on the workspace section of the Komorebi widget
     pub show_all_icons: bool,
+    /// State received from komorebi, shared with the widgets which need it
+    pub komorebi_notification_state: Option<Rc<RefCell<KomorebiNotificationState>>>,
+    /// How widgets display their labels
+    pub label_layout: LabelLayout,
+    /// Whether widgets should run their built-in click handlers, which are replaced by a
+    /// configured left click action
+    pub builtin_click: bool,
+}
+
+/// How widgets display their labels, which depends on the edge of the screen the bar is docked to
+#[derive(Copy, Clone, Debug, Default)]
+pub enum LabelLayout {
+    #[default]
+    Horizontal,
+    Vertical(VerticalLabels),
+}
+
+impl LabelLayout {
+    pub fn is_vertical(&self) -> bool {
+        matches!(self, LabelLayout::Vertical(_))
+    }
+
+    /// Adds a widget label made up of an icon section followed by text sections
+    pub fn add(self, ui: &mut Ui, mut layout_job: LayoutJob) -> Response {
+        match self {
+            LabelLayout::Horizontal => ui.add(Label::new(layout_job).selectable(false)),
+            LabelLayout::Vertical(VerticalLabels::IconOnly) => {
+                if layout_job.sections.len() > 1 && !layout_job.sections[0].byte_range.is_empty() {
+                    let icon = layout_job.sections.swap_remove(0);
+                    layout_job.text.truncate(icon.byte_range.end);
+                    layout_job.sections = vec![icon];
+                }
+
+                ui.add(Label::new(layout_job).selectable(false))
+            }
+            LabelLayout::Vertical(VerticalLabels::Wrapped) => {
+                layout_job.wrap.max_width = ui.available_width();
+                ui.add(Label::new(layout_job).selectable(false))
+            }
+            LabelLayout::Vertical(VerticalLabels::Rotated) => {
+                layout_job.wrap.max_width = f32::INFINITY;
+                let galley = ui.fonts(|fonts| fonts.layout_job(layout_job));
+                let (rect, response) = ui.allocate_exact_size(
+                    Vec2::new(galley.size().y, galley.size().x),
+                    Sense::hover(),
+                );
+
+                // a quarter turn clockwise around the top right corner keeps the text in the rect
+                ui.painter().add(
+                    TextShape::new(rect.right_top(), galley, ui.visuals().text_color())
+                        .with_angle(FRAC_PI_2),
+                );
+
+                response
+            }
+        }
+    }
+
+    /// Adds a plain text label
+    pub fn add_text(self, ui: &mut Ui, text: impl Into<WidgetText>) -> Response {
+        match self {
+            LabelLayout::Horizontal => ui.add(Label::new(text).selectable(false)),
+            LabelLayout::Vertical(_) => {
+                let layout_job =
+                    text.into()
+                        .into_layout_job(ui.style(), FontSelection::Default, Align::Center);
+
+                self.add(ui, layout_job)
+            }
+        }
+    }
+
+    /// Adds a text label which is truncated to `max_width` on horizontal bars
+    pub fn add_truncated(
+        self,
+        ui: &mut Ui,
+        text: impl Into<WidgetText>,
+        max_width: f32,
+    ) -> Response {
+        match self {
+            LabelLayout::Horizontal => {
+                let available_height = ui.available_height();
+                CustomUi(ui).add_sized_left_to_right(
+                    Vec2::new(max_width, available_height),
+                    Label::new(text).selectable(false).truncate(),
+                )
+            }
+            LabelLayout::Vertical(_) => self.add_text(ui, text),
+        }
+    }
 }
 
 pub trait RenderExt {
@@ -108,6 +213,12 @@ impl RenderExt for &KomobarConfig {
             text_font_id,
             icon_font_id,
             show_all_icons,
+            komorebi_notification_state: None,
+            label_layout: match self.dock.unwrap_or_default().is_vertical() {
+                true => LabelLayout::Vertical(self.vertical_labels.unwrap_or_default()),
+                false => LabelLayout::Horizontal,
+            },
+            builtin_click: true,
         }
     }
 }
@@ -133,6 +244,9 @@ impl RenderConfig {
             text_font_id: FontId::default(),
             icon_font_id: FontId::default(),
             show_all_icons: false,
+            komorebi_notification_state: None,
+            label_layout: LabelLayout::Horizontal,
+            builtin_click: true,
         }
     }
 
@@ -305,9 +419,16 @@ impl RenderConfig {
     }
 
     fn widget_outer_margin(&mut self, ui: &mut Ui) -> Margin {
+        let vertical = self.label_layout.is_vertical();
+
         let spacing = if self.applied_on_widget {
             // Remove the default item spacing from the margin
-            (self.spacing - ui.spacing().item_spacing.x) as i8
+            let item_spacing = match vertical {
+                true => ui.spacing().item_spacing.y,
+                false => ui.spacing().item_spacing.x,
+            };
+
+            (self.spacing - item_spacing) as i8
         } else {
             0
         };
@@ -316,25 +437,27 @@ impl RenderConfig {
             self.applied_on_widget = true;
         }
 
-        Margin {
-            left: match self.alignment {
-                Some(align) => match align {
-                    Alignment::Left => spacing,
-                    Alignment::Center => spacing,
-                    Alignment::Right => 0,
-                },
-                None => 0,
+        // the margin goes on the side facing the previous widget, which is above the widget
+        // instead of to the left of it on vertical bars
+        let (leading, trailing) = match self.alignment {
+            Some(Alignment::Left | Alignment::Center) => (spacing, 0),
+            Some(Alignment::Right) => (0, spacing),
+            None => (0, 0),
+        };
+
+        match vertical {
+            true => Margin {
+                left: 0,
+                right: 0,
+                top: leading,
+                bottom: trailing,
             },
-            right: match self.alignment {
-                Some(align) => match align {
-                    Alignment::Left => 0,
-                    Alignment::Center => 0,
-                    Alignment::Right => spacing,
-                },
-                None => 0,
+            false => Margin {
+                left: leading,
+                right: trailing,
+                top: 0,
+                bottom: 0,
             },
-            top: 0,
-            bottom: 0,
         }
     }
 }
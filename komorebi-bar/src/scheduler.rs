@@ -0,0 +1,268 @@
+use crossbeam_channel::Receiver;
+use crossbeam_channel::RecvTimeoutError;
+use crossbeam_channel::Sender;
+use eframe::egui::Context;
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::sync::PoisonError;
+use std::sync::Weak;
+use std::time::Duration;
+use std::time::Instant;
+
+/// The shortest interval a data collector can be scheduled at
+const MIN_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The number of threads the data collectors are run on, so that a slow collector (eg. one making
+/// a network request) doesn't delay all of the others
+const WORKERS: usize = 4;
+
+static REPAINT_CONTEXT: OnceLock<Context> = OnceLock::new();
+
+/// The shared data collectors by kind, each holding a `Weak<Mutex<SharedCollector<T>>>`
+static SHARED: LazyLock<Mutex<HashMap<&'static str, Box<dyn Any + Send>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+static SCHEDULER: LazyLock<Sender<Job>> = LazyLock::new(|| {
+    let (tx, rx) = crossbeam_channel::unbounded();
+    let (run_tx, run_rx) = crossbeam_channel::unbounded::<Arc<Job>>();
+
+    for _ in 0..WORKERS {
+        let run_rx = run_rx.clone();
+        std::thread::spawn(move || {
+            for job in run_rx {
+                job.run();
+            }
+        });
+    }
+
+    std::thread::spawn(move || run(rx, run_tx));
+    tx
+});
+
+/// Sets the context used to request a repaint of the bar when collected data changes
+pub fn set_repaint_context(ctx: &Context) {
+    let _ = REPAINT_CONTEXT.set(ctx.clone());
+}
+
+/// Requests a repaint of the bar from any thread
+pub fn request_repaint() {
+    if let Some(ctx) = REPAINT_CONTEXT.get() {
+        ctx.request_repaint();
+    }
+}
+
+/// A data collector registered with the scheduler thread
+struct Job {
+    interval: Duration,
+    /// The job is dropped once the `DataReceiver` it sends to has been dropped
+    receiver: Weak<()>,
+    /// Set while the collector is queued or running, a run is skipped if the previous one is
+    /// still busy
+    busy: AtomicBool,
+    collect: Mutex<Box<dyn FnMut() + Send>>,
+}
+
+impl Job {
+    fn run(&self) {
+        if let Ok(mut collect) = self.collect.lock() {
+            collect();
+        }
+
+        self.busy.store(false, Ordering::SeqCst);
+    }
+}
+
+/// The receiving end of a scheduled data collector, holding the most recently collected value
+pub struct DataReceiver<T> {
+    rx: Receiver<T>,
+    value: T,
+    _handle: Arc<()>,
+}
+
+impl<T> DataReceiver<T> {
+    /// Returns the most recently collected value without blocking
+    pub fn latest(&mut self) -> &T {
+        if let Some(value) = self.rx.try_iter().last() {
+            self.value = value;
+        }
+
+        &self.value
+    }
+}
+
+/// Runs `collect` every `interval`, starting immediately
+///
+/// Collectors are run on a small pool of threads which is shared by every widget, and are woken
+/// by a single scheduler thread.
+///
+/// New values are only sent to the returned `DataReceiver` (and a repaint of the bar is only
+/// requested) when they differ from the previously collected value, and the collector is removed
+/// from the scheduler when the `DataReceiver` is dropped (eg. when the configuration is reloaded).
+pub fn schedule<T, F>(interval: Duration, initial: T, mut collect: F) -> DataReceiver<T>
+where
+    T: Clone + PartialEq + Send + 'static,
+    F: FnMut() -> T + Send + 'static,
+{
+    let (tx, rx) = crossbeam_channel::unbounded();
+    let handle = Arc::new(());
+
+    let mut last = initial.clone();
+    let job = Job {
+        interval: interval.max(MIN_INTERVAL),
+        receiver: Arc::downgrade(&handle),
+        busy: AtomicBool::new(false),
+        collect: Mutex::new(Box::new(move || {
+            let value = collect();
+            if value == last {
+                return;
+            }
+
+            last.clone_from(&value);
+            if tx.send(value).is_ok() {
+                request_repaint();
+            }
+        })),
+    };
+
+    if let Err(error) = SCHEDULER.send(job) {
+        tracing::error!("could not register data collector with the scheduler: {error}");
+    }
+
+    DataReceiver {
+        rx,
+        value: initial,
+        _handle: handle,
+    }
+}
+
+/// A data collector which is shared by every widget and condition collecting the same kind of data
+struct SharedCollector<T> {
+    collect: Box<dyn FnMut() -> T + Send>,
+    last: Option<(Instant, T)>,
+}
+
+/// Returns a handle to the shared data collector for `kind`, which is created with `create` if it
+/// doesn't exist yet
+///
+/// The collector is only run again when its last reading is older than the shortest interval a
+/// collector can be scheduled at, so eg. a Cpu widget and a Cpu condition only sample the CPU
+/// usage once between them. The collector is dropped along with the last handle to it.
+pub fn shared<T, F>(
+    kind: &'static str,
+    create: impl FnOnce() -> F,
+) -> impl FnMut() -> T + Send + 'static
+where
+    T: Clone + Send + 'static,
+    F: FnMut() -> T + Send + 'static,
+{
+    let mut collectors = SHARED.lock().unwrap_or_else(PoisonError::into_inner);
+    let existing = collectors
+        .get(kind)
+        .and_then(|collector| collector.downcast_ref::<Weak<Mutex<SharedCollector<T>>>>())
+        .and_then(Weak::upgrade);
+
+    let collector = existing.unwrap_or_else(|| {
+        let collector = Arc::new(Mutex::new(SharedCollector {
+            collect: Box::new(create()),
+            last: None,
+        }));
+
+        collectors.insert(kind, Box::new(Arc::downgrade(&collector)));
+        collector
+    });
+
+    move || {
+        let mut collector = collector.lock().unwrap_or_else(PoisonError::into_inner);
+        match &collector.last {
+            Some((collected, value)) if collected.elapsed() < MIN_INTERVAL => value.clone(),
+            _ => {
+                let value = (collector.collect)();
+                collector.last = Some((Instant::now(), value.clone()));
+                value
+            }
+        }
+    }
+}
+
+/// Requests a repaint of the bar every second for as long as the returned `DataReceiver` is
+/// alive, for widgets which display the current time
+pub fn clock() -> DataReceiver<i64> {
+    schedule(MIN_INTERVAL, 0, || chrono::Utc::now().timestamp())
+}
+
+fn run(rx: Receiver<Job>, run_tx: Sender<Arc<Job>>) {
+    let mut jobs: Vec<(Instant, Arc<Job>)> = vec![];
+
+    loop {
+        let next_run = jobs.iter().map(|(next_run, _)| *next_run).min();
+        let received = match next_run {
+            Some(next_run) => {
+                match rx.recv_timeout(next_run.saturating_duration_since(Instant::now())) {
+                    Ok(job) => Some(job),
+                    Err(RecvTimeoutError::Timeout) => None,
+                    Err(RecvTimeoutError::Disconnected) => return,
+                }
+            }
+            None => match rx.recv() {
+                Ok(job) => Some(job),
+                Err(_) => return,
+            },
+        };
+
+        let now = Instant::now();
+        jobs.extend(
+            received
+                .into_iter()
+                .chain(rx.try_iter())
+                .map(|job| (now, Arc::new(job))),
+        );
+        jobs.retain(|(_, job)| job.receiver.strong_count() > 0);
+
+        for (next_run, job) in &mut jobs {
+            let now = Instant::now();
+            if *next_run <= now {
+                if !job.busy.swap(true, Ordering::SeqCst) && run_tx.send(job.clone()).is_err() {
+                    return;
+                }
+
+                *next_run = now + job.interval;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::shared;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+
+    #[test]
+    fn shared_collectors_are_only_run_once_per_interval() {
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let counter = runs.clone();
+        let mut widget = shared("test", move || {
+            move || counter.fetch_add(1, Ordering::SeqCst) + 1
+        });
+        let mut condition = shared("test", || || -> usize { unreachable!() });
+
+        assert_eq!(widget(), 1);
+        assert_eq!(condition(), 1);
+        assert_eq!(widget(), 1);
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+        drop(widget);
+        drop(condition);
+
+        let mut recreated = shared("test", || || 10_usize);
+        assert_eq!(recreated(), 10);
+    }
+}
@@ -1,21 +1,25 @@
 use crate::config::LabelPrefix;
 use crate::render::RenderConfig;
+use crate::scheduler;
+use crate::scheduler::DataReceiver;
 use crate::selected_frame::SelectableFrame;
+use crate::threshold::ThresholdConfig;
+use crate::threshold::Thresholds;
 use crate::widget::BarWidget;
+use crate::widget::CommonWidgetConfig;
+use crate::widget::InternalAction;
 use eframe::egui::text::LayoutJob;
 use eframe::egui::Align;
 use eframe::egui::Context;
-use eframe::egui::Label;
 use eframe::egui::TextFormat;
 use eframe::egui::Ui;
 use serde::Deserialize;
 use serde::Serialize;
 use std::process::Command;
 use std::time::Duration;
-use std::time::Instant;
 use sysinfo::Disks;
 
-#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct StorageConfig {
     /// Enable the Storage widget
@@ -24,54 +28,75 @@ pub struct StorageConfig {
     pub data_refresh_interval: Option<u64>,
     /// Display label prefix
     pub label_prefix: Option<LabelPrefix>,
+    /// Thresholds at which the widget is highlighted and alerts are sent
+    pub thresholds: Option<Vec<ThresholdConfig>>,
+    /// Actions, visibility condition and overflow priority of the widget
+    #[serde(flatten)]
+    pub common: CommonWidgetConfig,
 }
 
 impl From<StorageConfig> for Storage {
     fn from(value: StorageConfig) -> Self {
+        let data_refresh_interval = value.data_refresh_interval.unwrap_or(10);
+
+        let mut disks = Disks::new_with_refreshed_list();
+
         Self {
             enable: value.enable,
-            disks: Disks::new_with_refreshed_list(),
-            data_refresh_interval: value.data_refresh_interval.unwrap_or(10),
+            disks: scheduler::schedule(
+                Duration::from_secs(data_refresh_interval),
+                vec![],
+                move || {
+                    disks.refresh(true);
+                    disks
+                        .iter()
+                        .filter(|disk| disk.total_space() > 0)
+                        .map(|disk| DiskUsage {
+                            mount: disk.mount_point().to_string_lossy().to_string(),
+                            used: ((disk.total_space() - disk.available_space()) * 100)
+                                / disk.total_space(),
+                        })
+                        .collect()
+                },
+            ),
             label_prefix: value.label_prefix.unwrap_or(LabelPrefix::IconAndText),
-            last_updated: Instant::now(),
+            thresholds: Thresholds::from(value.thresholds),
         }
     }
 }
 
+#[derive(Clone, Debug, PartialEq)]
+struct DiskUsage {
+    mount: String,
+    /// Percentage of the disk space which is in use
+    used: u64,
+}
+
 pub struct Storage {
     pub enable: bool,
-    disks: Disks,
-    data_refresh_interval: u64,
+    disks: DataReceiver<Vec<DiskUsage>>,
     label_prefix: LabelPrefix,
-    last_updated: Instant,
+    thresholds: Thresholds,
 }
 
 impl Storage {
-    fn output(&mut self) -> Vec<String> {
-        let now = Instant::now();
-        if now.duration_since(self.last_updated) > Duration::from_secs(self.data_refresh_interval) {
-            self.disks.refresh(true);
-            self.last_updated = now;
-        }
-
+    fn output(&mut self) -> Vec<(String, DiskUsage)> {
+        let label_prefix = self.label_prefix;
         let mut disks = vec![];
 
-        for disk in &self.disks {
-            let mount = disk.mount_point();
-            let total = disk.total_space();
-            let available = disk.available_space();
-            let used = total - available;
-
-            disks.push(match self.label_prefix {
-                LabelPrefix::Text | LabelPrefix::IconAndText => {
-                    format!("{} {}%", mount.to_string_lossy(), (used * 100) / total)
-                }
-                LabelPrefix::None | LabelPrefix::Icon => format!("{}%", (used * 100) / total),
-            })
+        for disk in self.disks.latest() {
+            disks.push((
+                match label_prefix {
+                    LabelPrefix::Text | LabelPrefix::IconAndText => {
+                        format!("{} {}%", disk.mount, disk.used)
+                    }
+                    LabelPrefix::None | LabelPrefix::Icon => format!("{}%", disk.used),
+                },
+                disk.clone(),
+            ))
         }
 
-        disks.sort();
-        disks.reverse();
+        disks.sort_by(|(a, _), (b, _)| b.cmp(a));
 
         disks
     }
@@ -80,7 +105,8 @@ impl Storage {
 impl BarWidget for Storage {
     fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
         if self.enable {
-            for output in self.output() {
+            for (output, disk) in self.output() {
+                let highlight = self.thresholds.evaluate(&disk.mount, disk.used as u32);
                 let mut layout_job = LayoutJob::simple(
                     match self.label_prefix {
                         LabelPrefix::Icon | LabelPrefix::IconAndText => {
@@ -98,23 +124,27 @@ impl BarWidget for Storage {
                     10.0,
                     TextFormat {
                         font_id: config.text_font_id.clone(),
-                        color: ctx.style().visuals.text_color(),
+                        color: highlight.text_color(ctx, config.background_color),
                         valign: Align::Center,
                         ..Default::default()
                     },
                 );
 
+                let label_layout = config.label_layout;
+                let builtin_click = config.builtin_click;
+
                 config.apply_on_widget(false, ui, |ui| {
                     if SelectableFrame::new(false)
-                        .show(ui, |ui| ui.add(Label::new(layout_job).selectable(false)))
+                        .show(ui, |ui| {
+                            highlight
+                                .frame(ctx)
+                                .show(ui, |ui| label_layout.add(ui, layout_job))
+                        })
                         .clicked()
+                        && builtin_click
                     {
                         if let Err(error) = Command::new("cmd.exe")
-                            .args([
-                                "/C",
-                                "explorer.exe",
-                                output.split(' ').collect::<Vec<&str>>()[0],
-                            ])
+                            .args(["/C", "explorer.exe", &disk.mount])
                             .spawn()
                         {
                             eprintln!("{}", error)
@@ -124,4 +154,10 @@ impl BarWidget for Storage {
             }
         }
     }
+
+    fn handle_action(&mut self, action: InternalAction) {
+        if action == InternalAction::ToggleLabel {
+            self.label_prefix.toggle_text();
+        }
+    }
 }
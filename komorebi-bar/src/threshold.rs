@@ -0,0 +1,312 @@
+use eframe::egui::Color32;
+use eframe::egui::Context;
+use eframe::egui::Frame;
+use eframe::egui::Margin;
+use komorebi_client::SocketMessage;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::Once;
+use std::time::Duration;
+use windows::core::HSTRING;
+use windows::Data::Xml::Dom::XmlDocument;
+use windows::UI::Notifications::ToastNotification;
+use windows::UI::Notifications::ToastNotificationManager;
+use winreg::enums::HKEY_CURRENT_USER;
+use winreg::RegKey;
+
+/// How long each phase of a flashing highlight lasts
+const FLASH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The app id shown as the source of toast notifications
+const TOAST_APP_ID: &str = "komorebi-bar";
+
+static REGISTER_TOAST_APP_ID: Once = Once::new();
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ThresholdConfig {
+    /// Percentage at which the threshold is crossed
+    pub value: u32,
+    /// Whether the threshold is crossed when the value is above or below it (default: Above)
+    pub direction: Option<ThresholdDirection>,
+    /// Severity of the threshold, used to pick a colour from the active theme (default: Warning)
+    pub level: Option<ThresholdLevel>,
+    /// Highlight the background of the widget instead of the text (default: false)
+    pub highlight_background: Option<bool>,
+    /// Flash the highlight while the threshold is crossed (default: false)
+    pub flash: Option<bool>,
+    /// Alert to send when the threshold is crossed, which is sent again if the value recovers and
+    /// crosses the threshold another time
+    pub alert: Option<ThresholdAlert>,
+}
+
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum ThresholdDirection {
+    /// The threshold is crossed when the value is greater than or equal to it
+    #[default]
+    Above,
+    /// The threshold is crossed when the value is less than or equal to it
+    Below,
+}
+
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum ThresholdLevel {
+    /// Highlighted with the warning colour of the theme
+    #[default]
+    Warning,
+    /// Highlighted with the error colour of the theme
+    Critical,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum ThresholdAlert {
+    /// Show a Windows toast notification
+    Toast,
+    /// Publish a notification to komorebi subscribers
+    Notification,
+}
+
+impl ThresholdConfig {
+    fn is_crossed(&self, value: u32) -> bool {
+        match self.direction.unwrap_or_default() {
+            ThresholdDirection::Above => value >= self.value,
+            ThresholdDirection::Below => value <= self.value,
+        }
+    }
+}
+
+/// Tracks which of a widget's thresholds have been crossed, so that alerts are only sent once
+/// per crossing
+#[derive(Debug, Default)]
+pub struct Thresholds {
+    thresholds: Vec<ThresholdConfig>,
+    crossed: HashSet<(String, usize)>,
+}
+
+impl From<Option<Vec<ThresholdConfig>>> for Thresholds {
+    fn from(value: Option<Vec<ThresholdConfig>>) -> Self {
+        Self {
+            thresholds: value.unwrap_or_default(),
+            crossed: HashSet::new(),
+        }
+    }
+}
+
+impl Thresholds {
+    /// Evaluates the value of the labelled reading against the thresholds, sending alerts for any
+    /// which have been newly crossed and returning the highlight of the most severe one
+    pub fn evaluate(&mut self, label: &str, value: u32) -> ThresholdHighlight {
+        let mut highlight: Option<&ThresholdConfig> = None;
+
+        for (idx, threshold) in self.thresholds.iter().enumerate() {
+            let key = (label.to_string(), idx);
+
+            if !threshold.is_crossed(value) {
+                self.crossed.remove(&key);
+                continue;
+            }
+
+            if self.crossed.insert(key) {
+                if let Some(alert) = threshold.alert {
+                    send_alert(alert, format!("{label} is at {value}%"));
+                }
+            }
+
+            if highlight.is_none_or(|current| {
+                threshold.level.unwrap_or_default() >= current.level.unwrap_or_default()
+            }) {
+                highlight = Some(threshold);
+            }
+        }
+
+        ThresholdHighlight(highlight.map(|threshold| ActiveThreshold {
+            level: threshold.level.unwrap_or_default(),
+            background: threshold.highlight_background.unwrap_or_default(),
+            flash: threshold.flash.unwrap_or_default(),
+        }))
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct ActiveThreshold {
+    level: ThresholdLevel,
+    background: bool,
+    flash: bool,
+}
+
+/// How a widget's label should be highlighted for the thresholds it has crossed
+#[derive(Copy, Clone, Debug)]
+pub struct ThresholdHighlight(Option<ActiveThreshold>);
+
+impl ThresholdHighlight {
+    /// The threshold to highlight, if it is not in the "off" phase of a flash
+    fn active(&self, ctx: &Context) -> Option<ActiveThreshold> {
+        let active = self.0?;
+
+        if active.flash {
+            ctx.request_repaint_after(FLASH_INTERVAL);
+
+            let phase = ctx.input(|input| input.time) / FLASH_INTERVAL.as_secs_f64();
+            if phase as u64 % 2 == 1 {
+                return None;
+            }
+        }
+
+        Some(active)
+    }
+
+    fn color(level: ThresholdLevel, ctx: &Context) -> Color32 {
+        match level {
+            ThresholdLevel::Warning => ctx.style().visuals.warn_fg_color,
+            ThresholdLevel::Critical => ctx.style().visuals.error_fg_color,
+        }
+    }
+
+    /// The colour to use for the text of the label
+    pub fn text_color(&self, ctx: &Context, background_color: Color32) -> Color32 {
+        match self.active(ctx) {
+            Some(active) if active.background => background_color,
+            Some(active) => Self::color(active.level, ctx),
+            None => ctx.style().visuals.text_color(),
+        }
+    }
+
+    /// The frame to show the label in, which is filled when highlighting the background
+    pub fn frame(&self, ctx: &Context) -> Frame {
+        match self.active(ctx) {
+            Some(active) if active.background => Frame::NONE
+                .fill(Self::color(active.level, ctx))
+                .corner_radius(ctx.style().visuals.widgets.noninteractive.corner_radius)
+                .inner_margin(Margin::symmetric(4, 0)),
+            _ => Frame::NONE,
+        }
+    }
+}
+
+fn send_alert(alert: ThresholdAlert, message: String) {
+    // alerts may block while being delivered, so keep them off the ui thread
+    std::thread::spawn(move || match alert {
+        ThresholdAlert::Toast => {
+            if let Err(error) = show_toast(&message) {
+                tracing::error!("could not show threshold toast notification: {error}");
+            }
+        }
+        ThresholdAlert::Notification => {
+            if let Err(error) =
+                komorebi_client::send_message(&SocketMessage::PublishNotification(message))
+            {
+                tracing::error!("could not publish threshold notification: {error}");
+            }
+        }
+    });
+}
+
+/// Windows drops toast notifications from unpackaged applications unless their app id has been
+/// registered
+fn register_toast_app_id() -> std::io::Result<()> {
+    let (key, _) = RegKey::predef(HKEY_CURRENT_USER)
+        .create_subkey(format!(r"Software\Classes\AppUserModelId\{TOAST_APP_ID}"))?;
+
+    key.set_value("DisplayName", &TOAST_APP_ID)
+}
+
+fn show_toast(message: &str) -> windows::core::Result<()> {
+    REGISTER_TOAST_APP_ID.call_once(|| {
+        if let Err(error) = register_toast_app_id() {
+            tracing::error!("could not register the toast notification app id: {error}");
+        }
+    });
+
+    let message = message
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+
+    let xml = XmlDocument::new()?;
+    xml.LoadXml(&HSTRING::from(format!(
+        r#"<toast><visual><binding template="ToastGeneric"><text>komorebi-bar</text><text>{message}</text></binding></visual></toast>"#
+    )))?;
+
+    let toast = ToastNotification::CreateToastNotification(&xml)?;
+    ToastNotificationManager::CreateToastNotifierWithId(&HSTRING::from(TOAST_APP_ID))?.Show(&toast)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ThresholdConfig;
+    use super::ThresholdDirection;
+    use super::ThresholdLevel;
+    use super::Thresholds;
+
+    fn threshold(value: u32, direction: Option<ThresholdDirection>) -> ThresholdConfig {
+        ThresholdConfig {
+            value,
+            direction,
+            level: None,
+            highlight_background: None,
+            flash: None,
+            alert: None,
+        }
+    }
+
+    #[test]
+    fn crossing_direction() {
+        let above = threshold(80, None);
+        assert!(!above.is_crossed(79));
+        assert!(above.is_crossed(80));
+        assert!(above.is_crossed(100));
+
+        let below = threshold(20, Some(ThresholdDirection::Below));
+        assert!(below.is_crossed(0));
+        assert!(below.is_crossed(20));
+        assert!(!below.is_crossed(21));
+    }
+
+    #[test]
+    fn most_severe_threshold_is_highlighted() {
+        let mut thresholds = Thresholds::from(Some(vec![
+            ThresholdConfig {
+                level: Some(ThresholdLevel::Critical),
+                highlight_background: Some(true),
+                ..threshold(90, None)
+            },
+            ThresholdConfig {
+                flash: Some(true),
+                ..threshold(70, None)
+            },
+        ]));
+
+        assert!(thresholds.evaluate("CPU", 50).0.is_none());
+
+        let warning = thresholds.evaluate("CPU", 75).0.unwrap();
+        assert_eq!(warning.level, ThresholdLevel::Warning);
+        assert!(!warning.background);
+        assert!(warning.flash);
+
+        let critical = thresholds.evaluate("CPU", 95).0.unwrap();
+        assert_eq!(critical.level, ThresholdLevel::Critical);
+        assert!(critical.background);
+        assert!(!critical.flash);
+    }
+
+    #[test]
+    fn crossings_are_tracked_until_the_value_recovers() {
+        let mut thresholds = Thresholds::from(Some(vec![threshold(70, None), threshold(90, None)]));
+
+        thresholds.evaluate("CPU", 95);
+        thresholds.evaluate("RAM", 75);
+        assert_eq!(thresholds.crossed.len(), 3);
+
+        thresholds.evaluate("CPU", 80);
+        assert!(thresholds.crossed.contains(&(String::from("CPU"), 0)));
+        assert!(!thresholds.crossed.contains(&(String::from("CPU"), 1)));
+
+        thresholds.evaluate("CPU", 10);
+        thresholds.evaluate("RAM", 10);
+        assert!(thresholds.crossed.is_empty());
+    }
+}
@@ -1,13 +1,17 @@
 use crate::bar::Alignment;
 use crate::config::LabelPrefix;
 use crate::render::RenderConfig;
+use crate::scheduler;
+use crate::scheduler::DataReceiver;
 use crate::selected_frame::SelectableFrame;
+use crate::timezone::Timezone;
 use crate::widget::BarWidget;
+use crate::widget::CommonWidgetConfig;
+use crate::widget::InternalAction;
 use eframe::egui::text::LayoutJob;
 use eframe::egui::Align;
 use eframe::egui::Context;
 use eframe::egui::CornerRadius;
-use eframe::egui::Label;
 use eframe::egui::Sense;
 use eframe::egui::Stroke;
 use eframe::egui::TextFormat;
@@ -26,6 +30,13 @@ pub struct TimeConfig {
     pub format: TimeFormat,
     /// Display label prefix
     pub label_prefix: Option<LabelPrefix>,
+    /// IANA name of the timezone to display the time in (default: local timezone)
+    pub timezone: Option<String>,
+    /// Additional clocks displayed after the main clock
+    pub clocks: Option<Vec<ClockConfig>>,
+    /// Actions, visibility condition and overflow priority of the widget
+    #[serde(flatten)]
+    pub common: CommonWidgetConfig,
 }
 
 impl From<TimeConfig> for Time {
@@ -34,10 +45,30 @@ impl From<TimeConfig> for Time {
             enable: value.enable,
             format: value.format,
             label_prefix: value.label_prefix.unwrap_or(LabelPrefix::Icon),
+            timezone: Timezone::parse(value.timezone.as_deref()),
+            clocks: value
+                .clocks
+                .unwrap_or_default()
+                .into_iter()
+                .map(|clock| {
+                    let timezone = Timezone::parse(Some(&clock.timezone));
+                    (clock.label.unwrap_or(clock.timezone), timezone)
+                })
+                .collect(),
+            clock: scheduler::clock(),
         }
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ClockConfig {
+    /// IANA name of the timezone to display (e.g. "Asia/Tokyo")
+    pub timezone: String,
+    /// Label displayed before the time (default: the timezone name)
+    pub label: Option<String>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum TimeFormat {
@@ -83,22 +114,38 @@ impl TimeFormat {
     }
 }
 
-#[derive(Clone, Debug)]
 pub struct Time {
     pub enable: bool,
     pub format: TimeFormat,
     label_prefix: LabelPrefix,
+    timezone: Timezone,
+    clocks: Vec<(String, Timezone)>,
+    clock: DataReceiver<i64>,
 }
 
 impl Time {
     fn output(&mut self) -> String {
-        chrono::Local::now()
+        self.timezone
             .format(&self.format.fmt_string())
-            .to_string()
             .trim()
             .to_string()
     }
 
+    /// The labelled times of the additional clocks, which fall back to a 24-hour format when the
+    /// main clock is binary
+    fn clocks_output(&self) -> String {
+        let format = match self.format {
+            TimeFormat::BinaryCircle | TimeFormat::BinaryRectangle => String::from("%T"),
+            _ => self.format.fmt_string(),
+        };
+
+        self.clocks
+            .iter()
+            .map(|(label, timezone)| format!("{label} {}", timezone.format(&format).trim()))
+            .collect::<Vec<_>>()
+            .join("  ")
+    }
+
     fn paint_binary_circle(
         &mut self,
         size: f32,
@@ -278,6 +325,9 @@ impl Time {
 impl BarWidget for Time {
     fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
         if self.enable {
+            // the scheduler requests a repaint every second to keep the clock ticking
+            self.clock.latest();
+
             let mut output = self.output();
             if !output.is_empty() {
                 let use_binary_circle = output.starts_with('c');
@@ -312,14 +362,31 @@ impl BarWidget for Time {
                     );
                 }
 
+                let clocks = self.clocks_output();
+                let clocks_job = (!clocks.is_empty()).then(|| {
+                    LayoutJob::simple(
+                        clocks,
+                        config.text_font_id.clone(),
+                        ctx.style().visuals.text_color(),
+                        100.0,
+                    )
+                });
+
                 let font_id = config.icon_font_id.clone();
                 let is_reversed = matches!(config.alignment, Some(Alignment::Right));
 
+                let label_layout = config.label_layout;
+                let builtin_click = config.builtin_click;
+
                 config.apply_on_widget(false, ui, |ui| {
                     if SelectableFrame::new(false)
                         .show(ui, |ui| {
-                            if !is_reversed {
-                                ui.add(Label::new(layout_job.clone()).selectable(false));
+                            if is_reversed {
+                                if let Some(clocks_job) = &clocks_job {
+                                    label_layout.add(ui, clocks_job.clone());
+                                }
+                            } else {
+                                label_layout.add(ui, layout_job.clone());
                             }
 
                             if use_binary_circle || use_binary_rectangle {
@@ -373,10 +440,13 @@ impl BarWidget for Time {
                             }
 
                             if is_reversed {
-                                ui.add(Label::new(layout_job.clone()).selectable(false));
+                                label_layout.add(ui, layout_job.clone());
+                            } else if let Some(clocks_job) = &clocks_job {
+                                label_layout.add(ui, clocks_job.clone());
                             }
                         })
                         .clicked()
+                        && builtin_click
                     {
                         self.format.toggle()
                     }
@@ -384,4 +454,11 @@ impl BarWidget for Time {
             }
         }
     }
+
+    fn handle_action(&mut self, action: InternalAction) {
+        match action {
+            InternalAction::CycleFormat => self.format.toggle(),
+            InternalAction::ToggleLabel => self.label_prefix.toggle_text(),
+        }
+    }
 }
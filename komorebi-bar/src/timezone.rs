@@ -0,0 +1,46 @@
+use chrono::NaiveDate;
+use chrono::Utc;
+use chrono_tz::Tz;
+
+/// The timezone a Time or Date widget is displayed in
+#[derive(Copy, Clone, Debug, Default)]
+pub enum Timezone {
+    /// The local timezone of the system
+    #[default]
+    Local,
+    /// A timezone from the bundled IANA timezone database
+    Named(Tz),
+}
+
+impl Timezone {
+    /// Parses an IANA timezone name (e.g. "America/New_York"), falling back to the local timezone
+    /// if there is no name or it is not recognised
+    pub fn parse(name: Option<&str>) -> Self {
+        match name {
+            None => Self::Local,
+            Some(name) => match name.parse::<Tz>() {
+                Ok(tz) => Self::Named(tz),
+                Err(error) => {
+                    tracing::error!("unknown timezone \"{name}\", using local time: {error}");
+                    Self::Local
+                }
+            },
+        }
+    }
+
+    /// Formats the current time in this timezone
+    pub fn format(&self, format: &str) -> String {
+        match self {
+            Timezone::Local => chrono::Local::now().format(format).to_string(),
+            Timezone::Named(tz) => Utc::now().with_timezone(tz).format(format).to_string(),
+        }
+    }
+
+    /// The current date in this timezone
+    pub fn today(&self) -> NaiveDate {
+        match self {
+            Timezone::Local => chrono::Local::now().date_naive(),
+            Timezone::Named(tz) => Utc::now().with_timezone(tz).date_naive(),
+        }
+    }
+}
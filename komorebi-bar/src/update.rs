@@ -1,20 +1,22 @@
 use crate::config::LabelPrefix;
 use crate::render::RenderConfig;
+use crate::scheduler;
+use crate::scheduler::DataReceiver;
 use crate::selected_frame::SelectableFrame;
 use crate::widget::BarWidget;
+use crate::widget::CommonWidgetConfig;
+use crate::widget::InternalAction;
 use eframe::egui::text::LayoutJob;
 use eframe::egui::Align;
 use eframe::egui::Context;
-use eframe::egui::Label;
 use eframe::egui::TextFormat;
 use eframe::egui::Ui;
 use serde::Deserialize;
 use serde::Serialize;
 use std::process::Command;
 use std::time::Duration;
-use std::time::Instant;
 
-#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct UpdateConfig {
     /// Enable the Update widget
@@ -23,85 +25,71 @@ pub struct UpdateConfig {
     pub data_refresh_interval: Option<u64>,
     /// Display label prefix
     pub label_prefix: Option<LabelPrefix>,
+    /// Actions, visibility condition and overflow priority of the widget
+    #[serde(flatten)]
+    pub common: CommonWidgetConfig,
 }
 
 impl From<UpdateConfig> for Update {
     fn from(value: UpdateConfig) -> Self {
         let data_refresh_interval = value.data_refresh_interval.unwrap_or(12);
 
-        let mut latest_version = String::new();
-
-        let client = reqwest::blocking::Client::new();
-        if let Ok(response) = client
-            .get("https://api.github.com/repos/LGUG2Z/komorebi/releases/latest")
-            .header("User-Agent", "komorebi-bar-version-checker")
-            .send()
-        {
-            #[derive(Deserialize)]
-            struct Release {
-                tag_name: String,
-            }
-
-            if let Ok(release) =
-                serde_json::from_str::<Release>(&response.text().unwrap_or_default())
-            {
-                let trimmed = release.tag_name.trim_start_matches("v");
-                latest_version = trimmed.to_string();
-            }
-        }
+        // keep the last known version if a later check fails
+        let mut known_version = String::new();
 
         Self {
             enable: value.enable,
-            data_refresh_interval,
             installed_version: env!("CARGO_PKG_VERSION").to_string(),
-            latest_version,
+            latest_version: scheduler::schedule(
+                Duration::from_secs((data_refresh_interval * 60) * 60),
+                String::new(),
+                move || {
+                    if let Some(version) = latest_version() {
+                        known_version = version;
+                    }
+
+                    known_version.clone()
+                },
+            ),
             label_prefix: value.label_prefix.unwrap_or(LabelPrefix::IconAndText),
-            last_updated: Instant::now()
-                .checked_sub(Duration::from_secs(data_refresh_interval))
-                .unwrap(),
         }
     }
 }
 
+/// Fetches the latest released version of komorebi
+fn latest_version() -> Option<String> {
+    #[derive(Deserialize)]
+    struct Release {
+        tag_name: String,
+    }
+
+    let client = reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .ok()?;
+
+    client
+        .get("https://api.github.com/repos/LGUG2Z/komorebi/releases/latest")
+        .header("User-Agent", "komorebi-bar-version-checker")
+        .send()
+        .and_then(|response| response.text())
+        .ok()
+        .and_then(|text| serde_json::from_str::<Release>(&text).ok())
+        .map(|release| release.tag_name.trim_start_matches("v").to_string())
+}
+
 pub struct Update {
     pub enable: bool,
-    data_refresh_interval: u64,
     installed_version: String,
-    latest_version: String,
+    latest_version: DataReceiver<String>,
     label_prefix: LabelPrefix,
-    last_updated: Instant,
 }
 
 impl Update {
     fn output(&mut self) -> String {
-        let now = Instant::now();
-        if now.duration_since(self.last_updated)
-            > Duration::from_secs((self.data_refresh_interval * 60) * 60)
-        {
-            let client = reqwest::blocking::Client::new();
-            if let Ok(response) = client
-                .get("https://api.github.com/repos/LGUG2Z/komorebi/releases/latest")
-                .header("User-Agent", "komorebi-bar-version-checker")
-                .send()
-            {
-                #[derive(Deserialize)]
-                struct Release {
-                    tag_name: String,
-                }
-
-                if let Ok(release) =
-                    serde_json::from_str::<Release>(&response.text().unwrap_or_default())
-                {
-                    let trimmed = release.tag_name.trim_start_matches("v");
-                    self.latest_version = trimmed.to_string();
-                }
-            }
-
-            self.last_updated = now;
-        }
-
-        if self.latest_version > self.installed_version {
-            format!("Update available! v{}", self.latest_version)
+        let latest_version = self.latest_version.latest();
+        if *latest_version > self.installed_version {
+            format!("Update available! v{}", latest_version)
         } else {
             String::new()
         }
@@ -136,15 +124,19 @@ impl BarWidget for Update {
                     },
                 );
 
+                let label_layout = config.label_layout;
+                let builtin_click = config.builtin_click;
+
                 config.apply_on_widget(false, ui, |ui| {
                     if SelectableFrame::new(false)
-                        .show(ui, |ui| ui.add(Label::new(layout_job).selectable(false)))
+                        .show(ui, |ui| label_layout.add(ui, layout_job))
                         .clicked()
+                        && builtin_click
                     {
                         if let Err(error) = Command::new("explorer.exe")
                             .args([format!(
                                 "https://github.com/LGUG2Z/komorebi/releases/v{}",
-                                self.latest_version
+                                self.latest_version.latest()
                             )])
                             .spawn()
                         {
@@ -155,4 +147,10 @@ impl BarWidget for Update {
             }
         }
     }
+
+    fn handle_action(&mut self, action: InternalAction) {
+        if action == InternalAction::ToggleLabel {
+            self.label_prefix.toggle_text();
+        }
+    }
 }
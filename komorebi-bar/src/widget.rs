@@ -1,5 +1,10 @@
 use crate::battery::Battery;
 use crate::battery::BatteryConfig;
+use crate::command::shell;
+use crate::command::Command;
+use crate::command::CommandConfig;
+use crate::condition::ConditionalWidget;
+use crate::condition::WidgetCondition;
 use crate::cpu::Cpu;
 use crate::cpu::CpuConfig;
 use crate::date::Date;
@@ -14,6 +19,8 @@ use crate::memory::Memory;
 use crate::memory::MemoryConfig;
 use crate::network::Network;
 use crate::network::NetworkConfig;
+use crate::plugin::Plugin;
+use crate::plugin::PluginConfig;
 use crate::render::RenderConfig;
 use crate::storage::Storage;
 use crate::storage::StorageConfig;
@@ -22,18 +29,142 @@ use crate::time::TimeConfig;
 use crate::update::Update;
 use crate::update::UpdateConfig;
 use eframe::egui::Context;
+use eframe::egui::PointerButton;
+use eframe::egui::Rect;
 use eframe::egui::Ui;
+use komorebi_client::SocketMessage;
 use serde::Deserialize;
 use serde::Serialize;
 
 pub trait BarWidget {
     fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig);
+
+    /// Handles a widget-internal action triggered by one of the configured [`WidgetActions`]
+    fn handle_action(&mut self, _action: InternalAction) {}
+}
+
+// Options which can be set on every widget, flattened into its own configuration (a doc comment
+// here would replace the description of every widget in the schema)
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CommonWidgetConfig {
+    /// Click and scroll actions
+    pub actions: Option<WidgetActions>,
+    /// Only show the widget while this condition is met
+    pub visible_when: Option<WidgetCondition>,
+    /// Overflow priority of the widget, see SectionConfig (default: never collapses)
+    pub priority: Option<u32>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct WidgetActions {
+    /// Action to run when the widget is left clicked
+    pub left_click: Option<WidgetAction>,
+    /// Action to run when the widget is right clicked
+    pub right_click: Option<WidgetAction>,
+    /// Action to run when the widget is middle clicked
+    pub middle_click: Option<WidgetAction>,
+    /// Action to run when scrolling up over the widget
+    pub scroll_up: Option<WidgetAction>,
+    /// Action to run when scrolling down over the widget
+    pub scroll_down: Option<WidgetAction>,
+    /// Text to display when hovering over the widget
+    pub tooltip: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum WidgetAction {
+    /// Send a message to komorebi (see `komorebic socket-schema` for the format)
+    #[cfg_attr(feature = "schemars", schemars(with = "schemars::_serde_json::Value"))]
+    SocketMessage(SocketMessage),
+    /// Run a command (executed with `cmd.exe /C`)
+    Command(String),
+    /// Trigger a behaviour built into the widget
+    Widget(InternalAction),
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum InternalAction {
+    /// Cycle through the predefined formats (Date and Time widgets)
+    CycleFormat,
+    /// Toggle the text part of the label prefix on and off
+    ToggleLabel,
+}
+
+impl WidgetActions {
+    /// The action triggered by pointer input over the given rect this frame, if any
+    fn triggered(&self, ui: &Ui, rect: Rect) -> Option<&WidgetAction> {
+        if !ui.rect_contains_pointer(rect) {
+            return None;
+        }
+
+        ui.input(|input| {
+            let scroll = input.raw_scroll_delta.y;
+
+            if input.pointer.button_clicked(PointerButton::Primary) {
+                self.left_click.as_ref()
+            } else if input.pointer.button_clicked(PointerButton::Secondary) {
+                self.right_click.as_ref()
+            } else if input.pointer.button_clicked(PointerButton::Middle) {
+                self.middle_click.as_ref()
+            } else if scroll > 0.0 {
+                self.scroll_up.as_ref()
+            } else if scroll < 0.0 {
+                self.scroll_down.as_ref()
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// Wraps a widget to run its configured [`WidgetActions`] in addition to its own built-in
+/// interactions
+pub struct ActionableWidget {
+    widget: Box<dyn BarWidget>,
+    actions: WidgetActions,
+}
+
+impl BarWidget for ActionableWidget {
+    fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
+        let builtin_click = config.builtin_click;
+        config.builtin_click = builtin_click && self.actions.left_click.is_none();
+        let mut response = ui.scope(|ui| self.widget.render(ctx, ui, config)).response;
+        config.builtin_click = builtin_click;
+
+        if let Some(tooltip) = &self.actions.tooltip {
+            response = response.on_hover_text(tooltip);
+        }
+
+        match self.actions.triggered(ui, response.rect).cloned() {
+            None => {}
+            Some(WidgetAction::SocketMessage(message)) => {
+                if let Err(error) = komorebi_client::send_message(&message) {
+                    tracing::error!("could not send message to komorebi: {error}");
+                }
+            }
+            Some(WidgetAction::Command(command)) => {
+                if let Err(error) = shell(&command).spawn() {
+                    tracing::error!("could not run widget action command: {error}");
+                }
+            }
+            Some(WidgetAction::Widget(action)) => self.widget.handle_action(action),
+        }
+    }
+
+    fn handle_action(&mut self, action: InternalAction) {
+        self.widget.handle_action(action);
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum WidgetConfig {
     Battery(BatteryConfig),
+    Command(CommandConfig),
     Cpu(CpuConfig),
     Date(DateConfig),
     Keyboard(KeyboardConfig),
@@ -41,6 +172,7 @@ pub enum WidgetConfig {
     Media(MediaConfig),
     Memory(MemoryConfig),
     Network(NetworkConfig),
+    Plugin(PluginConfig),
     Storage(StorageConfig),
     Time(TimeConfig),
     Update(UpdateConfig),
@@ -48,24 +180,77 @@ pub enum WidgetConfig {
 
 impl WidgetConfig {
     pub fn as_boxed_bar_widget(&self) -> Box<dyn BarWidget> {
-        match self {
-            WidgetConfig::Battery(config) => Box::new(Battery::from(*config)),
-            WidgetConfig::Cpu(config) => Box::new(Cpu::from(*config)),
+        let widget: Box<dyn BarWidget> = match self {
+            WidgetConfig::Battery(config) => Box::new(Battery::from(config.clone())),
+            WidgetConfig::Command(config) => Box::new(Command::from(config.clone())),
+            WidgetConfig::Cpu(config) => Box::new(Cpu::from(config.clone())),
             WidgetConfig::Date(config) => Box::new(Date::from(config.clone())),
-            WidgetConfig::Keyboard(config) => Box::new(Keyboard::from(*config)),
+            WidgetConfig::Keyboard(config) => Box::new(Keyboard::from(config.clone())),
             WidgetConfig::Komorebi(config) => Box::new(Komorebi::from(config)),
-            WidgetConfig::Media(config) => Box::new(Media::from(*config)),
-            WidgetConfig::Memory(config) => Box::new(Memory::from(*config)),
-            WidgetConfig::Network(config) => Box::new(Network::from(*config)),
-            WidgetConfig::Storage(config) => Box::new(Storage::from(*config)),
+            WidgetConfig::Media(config) => Box::new(Media::from(config.clone())),
+            WidgetConfig::Memory(config) => Box::new(Memory::from(config.clone())),
+            WidgetConfig::Network(config) => Box::new(Network::from(config.clone())),
+            WidgetConfig::Plugin(config) => Box::new(Plugin::from(config.clone())),
+            WidgetConfig::Storage(config) => Box::new(Storage::from(config.clone())),
             WidgetConfig::Time(config) => Box::new(Time::from(config.clone())),
-            WidgetConfig::Update(config) => Box::new(Update::from(*config)),
+            WidgetConfig::Update(config) => Box::new(Update::from(config.clone())),
+        };
+
+        self.wrap(widget)
+    }
+
+    /// Wraps the widget so that the actions and visibility condition configured for it are
+    /// handled, if there are any
+    pub fn wrap(&self, widget: Box<dyn BarWidget>) -> Box<dyn BarWidget> {
+        let widget: Box<dyn BarWidget> = match self.actions() {
+            Some(actions) => Box::new(ActionableWidget {
+                widget,
+                actions: actions.clone(),
+            }),
+            None => widget,
+        };
+
+        match self.visible_when() {
+            Some(condition) => Box::new(ConditionalWidget::new(widget, condition)),
+            None => widget,
         }
     }
 
+    /// The options shared by every widget
+    pub fn common(&self) -> &CommonWidgetConfig {
+        match self {
+            WidgetConfig::Battery(config) => &config.common,
+            WidgetConfig::Command(config) => &config.common,
+            WidgetConfig::Cpu(config) => &config.common,
+            WidgetConfig::Date(config) => &config.common,
+            WidgetConfig::Keyboard(config) => &config.common,
+            WidgetConfig::Komorebi(config) => &config.common,
+            WidgetConfig::Media(config) => &config.common,
+            WidgetConfig::Memory(config) => &config.common,
+            WidgetConfig::Network(config) => &config.common,
+            WidgetConfig::Plugin(config) => &config.common,
+            WidgetConfig::Storage(config) => &config.common,
+            WidgetConfig::Time(config) => &config.common,
+            WidgetConfig::Update(config) => &config.common,
+        }
+    }
+
+    pub fn actions(&self) -> Option<&WidgetActions> {
+        self.common().actions.as_ref()
+    }
+
+    pub fn visible_when(&self) -> Option<&WidgetCondition> {
+        self.common().visible_when.as_ref()
+    }
+
+    pub fn priority(&self) -> Option<u32> {
+        self.common().priority
+    }
+
     pub fn enabled(&self) -> bool {
         match self {
             WidgetConfig::Battery(config) => config.enable,
+            WidgetConfig::Command(config) => config.enable,
             WidgetConfig::Cpu(config) => config.enable,
             WidgetConfig::Date(config) => config.enable,
             WidgetConfig::Keyboard(config) => config.enable,
@@ -81,9 +266,49 @@ impl WidgetConfig {
             WidgetConfig::Media(config) => config.enable,
             WidgetConfig::Memory(config) => config.enable,
             WidgetConfig::Network(config) => config.enable,
+            WidgetConfig::Plugin(config) => config.enable,
             WidgetConfig::Storage(config) => config.enable,
             WidgetConfig::Time(config) => config.enable,
             WidgetConfig::Update(config) => config.enable,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::WidgetAction;
+    use super::WidgetConfig;
+    use serde_json::json;
+
+    #[test]
+    fn common_options_are_read_alongside_the_widget_config() {
+        let config: WidgetConfig = serde_json::from_value(json!({
+            "Time": {
+                "enable": true,
+                "format": "TwentyFourHour",
+                "priority": 2,
+                "actions": { "left_click": { "Command": "explorer.exe" } }
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(config.priority(), Some(2));
+        assert!(config.visible_when().is_none());
+        assert!(matches!(
+            config.actions().and_then(|actions| actions.left_click.as_ref()),
+            Some(WidgetAction::Command(command)) if command == "explorer.exe"
+        ));
+    }
+
+    #[test]
+    fn common_options_are_optional() {
+        let config: WidgetConfig = serde_json::from_value(json!({
+            "Memory": { "enable": true }
+        }))
+        .unwrap();
+
+        assert!(config.actions().is_none());
+        assert!(config.visible_when().is_none());
+        assert_eq!(config.priority(), None);
+    }
+}
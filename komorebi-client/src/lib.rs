@@ -5,13 +5,17 @@ pub use komorebi::animation::prefix::AnimationPrefix;
 pub use komorebi::animation::PerAnimationPrefixConfig;
 pub use komorebi::asc::ApplicationSpecificConfiguration;
 pub use komorebi::border_manager::BorderInfo;
+pub use komorebi::colour::BorderColour;
 pub use komorebi::colour::Colour;
+pub use komorebi::colour::Gradient;
 pub use komorebi::colour::Rgb;
 pub use komorebi::config_generation::ApplicationConfiguration;
 pub use komorebi::config_generation::IdWithIdentifier;
 pub use komorebi::config_generation::IdWithIdentifierAndComment;
 pub use komorebi::config_generation::MatchingRule;
 pub use komorebi::config_generation::MatchingStrategy;
+pub use komorebi::config_generation::WorkspaceTarget;
+pub use komorebi::config_generation::WorkspaceTargetRule;
 pub use komorebi::container::Container;
 pub use komorebi::core::config_generation::ApplicationConfigurationGenerator;
 pub use komorebi::core::resolve_home_path;
@@ -43,10 +47,18 @@ pub use komorebi::core::StackbarLabel;
 pub use komorebi::core::StackbarMode;
 pub use komorebi::core::StateQuery;
 pub use komorebi::core::WindowKind;
+pub use komorebi::explain::explain;
+pub use komorebi::explain::Explanation;
+pub use komorebi::matches_rule;
 pub use komorebi::monitor::Monitor;
 pub use komorebi::monitor_reconciliator::MonitorNotification;
 pub use komorebi::ring::Ring;
+pub use komorebi::transparency_manager::TransparencyProfile;
+pub use komorebi::transparency_manager::TransparencyRule;
 pub use komorebi::window::Window;
+pub use komorebi::window_label::LabelOverride;
+pub use komorebi::window_label::LabelTemplate;
+pub use komorebi::window_label::WindowLabeller;
 pub use komorebi::window_manager_event::WindowManagerEvent;
 pub use komorebi::workspace::Workspace;
 pub use komorebi::workspace::WorkspaceGlobals;
@@ -54,6 +66,7 @@ pub use komorebi::workspace::WorkspaceLayer;
 pub use komorebi::AnimationsConfig;
 pub use komorebi::AspectRatio;
 pub use komorebi::BorderColours;
+pub use komorebi::BorderOverrides;
 pub use komorebi::CrossBoundaryBehaviour;
 pub use komorebi::GlobalState;
 pub use komorebi::KomorebiTheme;
@@ -62,11 +75,16 @@ pub use komorebi::Notification;
 pub use komorebi::NotificationEvent;
 pub use komorebi::PredefinedAspectRatio;
 pub use komorebi::RuleDebug;
+pub use komorebi::RuleTarget;
+pub use komorebi::ScalableSize;
+pub use komorebi::ScheduledTheme;
 pub use komorebi::StackbarConfig;
+pub use komorebi::StackbarOverrides;
 pub use komorebi::State;
 pub use komorebi::StaticConfig;
 pub use komorebi::SubscribeOptions;
 pub use komorebi::TabsConfig;
+pub use komorebi::ThemeSchedule;
 pub use komorebi::WindowContainerBehaviour;
 pub use komorebi::WindowsApi;
 pub use komorebi::WorkspaceConfig;
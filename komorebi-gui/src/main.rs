@@ -151,10 +151,10 @@ impl KomorebiGui {
                 .unwrap();
 
         let border_colours = BorderColours {
-            single: colour32(global_state.border_colours.single),
-            stack: colour32(global_state.border_colours.stack),
-            monocle: colour32(global_state.border_colours.monocle),
-            unfocused: colour32(global_state.border_colours.unfocused),
+            single: colour32(global_state.border_colours.single.map(Colour::from)),
+            stack: colour32(global_state.border_colours.stack.map(Colour::from)),
+            monocle: colour32(global_state.border_colours.monocle.map(Colour::from)),
+            unfocused: colour32(global_state.border_colours.unfocused.map(Colour::from)),
         };
 
         let border_config = BorderConfig {
@@ -1,6 +1,8 @@
 #![warn(clippy::all)]
 #![allow(clippy::missing_errors_doc)]
 
+mod palette;
+
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
@@ -10,9 +12,12 @@ use strum::IntoEnumIterator;
 pub use base16_egui_themes::Base16;
 pub use catppuccin_egui;
 pub use eframe::egui::Color32;
+pub use palette::Palette;
+pub use palette::PaletteColours;
 use serde_variant::to_variant_name;
+use std::path::PathBuf;
 
-#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq)]
 #[serde(tag = "type")]
 pub enum Theme {
     /// A theme from catppuccin-egui
@@ -25,6 +30,8 @@ pub enum Theme {
         name: Base16,
         accent: Option<Base16Value>,
     },
+    /// A user-defined palette loaded from a file
+    Custom { path: PathBuf, name: Option<String> },
 }
 
 impl Theme {
@@ -45,6 +52,7 @@ impl Theme {
                         .to_string()
                 })
                 .collect(),
+            Theme::Custom { path, .. } => Palette::names(path).unwrap_or_default(),
         }
     }
 }
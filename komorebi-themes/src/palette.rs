@@ -0,0 +1,332 @@
+use crate::Color32;
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// The colours every base16 scheme defines
+const BASE16_KEYS: [&str; 16] = [
+    "base00", "base01", "base02", "base03", "base04", "base05", "base06", "base07", "base08",
+    "base09", "base0A", "base0B", "base0C", "base0D", "base0E", "base0F",
+];
+
+/// A user-defined palette mapping each role to a hex colour (e.g. "#89b4fa")
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct Palette {
+    /// Border colour when the container contains a single window (default: #89b4fa)
+    pub single_border: Option<String>,
+    /// Border colour when the container contains multiple windows (default: #a6e3a1)
+    pub stack_border: Option<String>,
+    /// Border colour when the container is in monocle mode (default: #f5c2e7)
+    pub monocle_border: Option<String>,
+    /// Border colour when the window is floating (default: #f9e2af)
+    pub floating_border: Option<String>,
+    /// Border colour when the container is unfocused (default: #1e1e2e)
+    pub unfocused_border: Option<String>,
+    /// Stackbar focused tab text colour (default: stack_border)
+    pub stackbar_focused_text: Option<String>,
+    /// Stackbar unfocused tab text colour (default: bar_text)
+    pub stackbar_unfocused_text: Option<String>,
+    /// Stackbar tab background colour (default: bar_background)
+    pub stackbar_background: Option<String>,
+    /// Komorebi status bar background colour (default: #1e1e2e)
+    pub bar_background: Option<String>,
+    /// Komorebi status bar text colour (default: #cdd6f4)
+    pub bar_text: Option<String>,
+    /// Komorebi status bar accent (default: single_border)
+    pub accent: Option<String>,
+    /// Colour used to highlight warnings (default: #f9e2af)
+    pub warning: Option<String>,
+    /// Colour used to highlight errors (default: #f38ba8)
+    pub error: Option<String>,
+}
+
+/// The colours of a [`Palette`], with defaults applied to the roles it doesn't set
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PaletteColours {
+    pub single_border: Color32,
+    pub stack_border: Color32,
+    pub monocle_border: Color32,
+    pub floating_border: Color32,
+    pub unfocused_border: Color32,
+    pub stackbar_focused_text: Color32,
+    pub stackbar_unfocused_text: Color32,
+    pub stackbar_background: Color32,
+    pub bar_background: Color32,
+    pub bar_text: Color32,
+    pub accent: Color32,
+    pub warning: Color32,
+    pub error: Color32,
+}
+
+impl Palette {
+    /// Loads a palette from a file, which is either a base16 YAML scheme or a TOML or JSON file of
+    /// palettes keyed by name. The name can be omitted for files with a single palette.
+    pub fn load(path: &Path, name: Option<&str>) -> Result<Self> {
+        let mut palettes = Self::read_all(path)?;
+
+        match name {
+            Some(name) => palettes
+                .remove(name)
+                .ok_or_else(|| eyre!("there is no palette named '{name}' in {}", path.display())),
+            None if palettes.len() == 1 => palettes
+                .into_values()
+                .next()
+                .ok_or_else(|| eyre!("there are no palettes in {}", path.display())),
+            None => Err(eyre!(
+                "a palette name is required to choose between the palettes in {}",
+                path.display()
+            )),
+        }
+    }
+
+    /// The names of the palettes in a palette file
+    pub fn names(path: &Path) -> Result<Vec<String>> {
+        let mut names = Self::read_all(path)?.into_keys().collect::<Vec<_>>();
+        names.sort();
+
+        Ok(names)
+    }
+
+    /// Reads every palette in a file, where a base16 scheme is a single palette named after the
+    /// file
+    fn read_all(path: &Path) -> Result<HashMap<String, Palette>> {
+        let contents = std::fs::read_to_string(path)?;
+
+        Ok(
+            match path.extension().and_then(|extension| extension.to_str()) {
+                Some("yaml" | "yml") => {
+                    let name = path
+                        .file_stem()
+                        .map(|stem| stem.to_string_lossy().to_string())
+                        .unwrap_or_default();
+
+                    HashMap::from([(name, Self::from_base16_yaml(&contents)?)])
+                }
+                Some("toml") => toml::from_str(&contents)?,
+                _ => serde_json::from_str(&contents)?,
+            },
+        )
+    }
+
+    /// Imports a base16 scheme, assigning its colours to roles the same way as the bundled Base16
+    /// themes. Every colour from base00 to base0F has to be defined as a hex string.
+    pub fn from_base16_yaml(contents: &str) -> Result<Self> {
+        let scheme: serde_yaml::Value = serde_yaml::from_str(contents)?;
+        // newer tinted-theming schemes nest the colours under a palette key
+        let colours = scheme.get("palette").unwrap_or(&scheme);
+
+        let mut bases = HashMap::new();
+        for key in BASE16_KEYS {
+            let value = colours
+                .get(key)
+                .or_else(|| colours.get(key.to_lowercase()))
+                .ok_or_else(|| eyre!("the base16 scheme does not define {key}"))?;
+
+            // unquoted hex values made up of digits only, like 282828, are read as numbers
+            let hex = value
+                .as_str()
+                .map(|hex| format!("#{}", hex.trim_start_matches('#')))
+                .filter(|hex| hex.len() == 7 && Color32::from_hex(hex).is_ok())
+                .ok_or_else(|| {
+                    eyre!("{key} in the base16 scheme is not a quoted six digit hex colour")
+                })?;
+
+            bases.insert(key, hex);
+        }
+
+        let base = |key: &str| bases.get(key).cloned();
+
+        Ok(Self {
+            single_border: base("base0D"),
+            stack_border: base("base0B"),
+            monocle_border: base("base0F"),
+            floating_border: base("base09"),
+            unfocused_border: base("base01"),
+            stackbar_focused_text: base("base0B"),
+            stackbar_unfocused_text: base("base05"),
+            stackbar_background: base("base01"),
+            bar_background: base("base00"),
+            bar_text: base("base05"),
+            accent: base("base0D"),
+            warning: base("base0A"),
+            error: base("base08"),
+        })
+    }
+
+    pub fn colours(&self) -> PaletteColours {
+        let colour = |hex: &Option<String>, default: Color32| {
+            hex.as_deref().map_or(default, |hex| {
+                Color32::from_hex(hex).unwrap_or_else(|_| {
+                    tracing::warn!("invalid hex colour in palette: {hex}");
+                    default
+                })
+            })
+        };
+
+        let single_border = colour(&self.single_border, Color32::from_rgb(137, 180, 250));
+        let stack_border = colour(&self.stack_border, Color32::from_rgb(166, 227, 161));
+        let bar_background = colour(&self.bar_background, Color32::from_rgb(30, 30, 46));
+        let bar_text = colour(&self.bar_text, Color32::from_rgb(205, 214, 244));
+
+        PaletteColours {
+            single_border,
+            stack_border,
+            monocle_border: colour(&self.monocle_border, Color32::from_rgb(245, 194, 231)),
+            floating_border: colour(&self.floating_border, Color32::from_rgb(249, 226, 175)),
+            unfocused_border: colour(&self.unfocused_border, Color32::from_rgb(30, 30, 46)),
+            stackbar_focused_text: colour(&self.stackbar_focused_text, stack_border),
+            stackbar_unfocused_text: colour(&self.stackbar_unfocused_text, bar_text),
+            stackbar_background: colour(&self.stackbar_background, bar_background),
+            bar_background,
+            bar_text,
+            accent: colour(&self.accent, single_border),
+            warning: colour(&self.warning, Color32::from_rgb(249, 226, 175)),
+            error: colour(&self.error, Color32::from_rgb(243, 139, 168)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Palette;
+    use crate::Color32;
+    use std::path::PathBuf;
+
+    const GRUVBOX: &str = r#"
+scheme: "Gruvbox dark, hard"
+author: "Dawid Kurek"
+base00: "1d2021"
+base01: "3c3836"
+base02: "504945"
+base03: "665c54"
+base04: "bdae93"
+base05: "d5c4a1"
+base06: "ebdbb2"
+base07: "fbf1c7"
+base08: "fb4934"
+base09: "fe8019"
+base0A: "fabd2f"
+base0B: "b8bb26"
+base0C: "8ec07c"
+base0D: "83a598"
+base0E: "d3869b"
+base0F: "d65d0e"
+"#;
+
+    /// Writes a palette file into a directory of its own, which is removed when it is dropped
+    struct PaletteFile(PathBuf);
+
+    impl PaletteFile {
+        fn new(name: &str, contents: &str) -> Self {
+            let dir =
+                std::env::temp_dir().join(format!("komorebi-themes-{}-{name}", std::process::id()));
+            std::fs::create_dir_all(&dir).unwrap();
+            let path = dir.join(name);
+            std::fs::write(&path, contents).unwrap();
+
+            Self(path)
+        }
+    }
+
+    impl Drop for PaletteFile {
+        fn drop(&mut self) {
+            if let Some(dir) = self.0.parent() {
+                let _ = std::fs::remove_dir_all(dir);
+            }
+        }
+    }
+
+    #[test]
+    fn base16_colours_are_assigned_to_roles() {
+        let palette = Palette::from_base16_yaml(GRUVBOX).unwrap();
+
+        assert_eq!(palette.bar_background.as_deref(), Some("#1d2021"));
+        assert_eq!(palette.bar_text.as_deref(), Some("#d5c4a1"));
+        assert_eq!(palette.single_border.as_deref(), Some("#83a598"));
+        assert_eq!(palette.stack_border.as_deref(), Some("#b8bb26"));
+        assert_eq!(palette.unfocused_border.as_deref(), Some("#3c3836"));
+        assert_eq!(palette.error.as_deref(), Some("#fb4934"));
+    }
+
+    #[test]
+    fn nested_and_lowercase_base16_schemes_are_read() {
+        let nested = format!(
+            "system: base16\npalette:\n{}",
+            GRUVBOX
+                .lines()
+                .filter(|line| line.starts_with("base"))
+                .map(|line| format!("  {}\n", line.to_lowercase()))
+                .collect::<String>()
+        );
+
+        assert_eq!(
+            Palette::from_base16_yaml(&nested).unwrap(),
+            Palette::from_base16_yaml(GRUVBOX).unwrap()
+        );
+    }
+
+    #[test]
+    fn malformed_base16_schemes_are_rejected() {
+        let missing = GRUVBOX.replace("base0F: \"d65d0e\"\n", "");
+        let unquoted = GRUVBOX.replace("\"504945\"", "504945");
+        let invalid = GRUVBOX.replace("\"fb4934\"", "\"not a colour\"");
+        let short = GRUVBOX.replace("\"fb4934\"", "\"fb4\"");
+
+        for scheme in [&missing, &unquoted, &invalid, &short, "- not\n- a scheme"] {
+            assert!(Palette::from_base16_yaml(scheme).is_err(), "{scheme}");
+        }
+    }
+
+    #[test]
+    fn palettes_are_loaded_by_name() {
+        let file = PaletteFile::new(
+            "palettes.toml",
+            "[dark]\nbar_background = \"#000000\"\n\n[light]\nbar_background = \"#ffffff\"\n",
+        );
+
+        let light = Palette::load(&file.0, Some("light")).unwrap();
+        assert_eq!(light.bar_background.as_deref(), Some("#ffffff"));
+
+        assert!(Palette::load(&file.0, Some("missing")).is_err());
+        assert!(Palette::load(&file.0, None).is_err());
+        assert_eq!(Palette::names(&file.0).unwrap(), ["dark", "light"]);
+    }
+
+    #[test]
+    fn single_palettes_are_loaded_without_a_name() {
+        let json = PaletteFile::new("palettes.json", r##"{ "only": { "accent": "#ff0000" } }"##);
+        let only = Palette::load(&json.0, None).unwrap();
+        assert_eq!(only.accent.as_deref(), Some("#ff0000"));
+
+        let yaml = PaletteFile::new("gruvbox-dark-hard.yaml", GRUVBOX);
+        assert_eq!(
+            Palette::load(&yaml.0, Some("gruvbox-dark-hard")).unwrap(),
+            Palette::from_base16_yaml(GRUVBOX).unwrap()
+        );
+    }
+
+    #[test]
+    fn unset_and_invalid_colours_fall_back_to_their_defaults() {
+        let palette = Palette {
+            bar_background: Some(String::from("#101010")),
+            stack_border: Some(String::from("#202020")),
+            single_border: Some(String::from("not a colour")),
+            ..Default::default()
+        };
+
+        let colours = palette.colours();
+        let defaults = Palette::default().colours();
+
+        assert_eq!(colours.bar_background, Color32::from_rgb(16, 16, 16));
+        assert_eq!(colours.single_border, defaults.single_border);
+        assert_eq!(colours.monocle_border, defaults.monocle_border);
+        // derived roles follow the colours they default to
+        assert_eq!(colours.stackbar_background, colours.bar_background);
+        assert_eq!(colours.stackbar_focused_text, Color32::from_rgb(32, 32, 32));
+        assert_eq!(colours.accent, defaults.single_border);
+    }
+}
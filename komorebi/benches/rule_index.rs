@@ -0,0 +1,136 @@
+//! Compares the indexed rule matcher against a linear scan of the same rules
+//!
+//! Run with `cargo bench -p komorebi --bench rule_index`
+
+use komorebi::core::config_generation::IdWithIdentifier;
+use komorebi::core::config_generation::MatchingRule;
+use komorebi::core::config_generation::MatchingStrategy;
+use komorebi::core::ApplicationIdentifier;
+use komorebi::matches_rule;
+use komorebi::rule_index::RuleIndex;
+use komorebi::RuleTarget;
+use regex::Regex;
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::time::Duration;
+use std::time::Instant;
+
+const RULE_COUNT: usize = 500;
+const ITERATIONS: u32 = 10_000;
+
+fn rule(kind: ApplicationIdentifier, id: String, strategy: MatchingStrategy) -> MatchingRule {
+    MatchingRule::Simple(IdWithIdentifier {
+        kind,
+        id,
+        matching_strategy: Option::from(strategy),
+    })
+}
+
+/// A rule set shaped roughly like the community app-specific configuration file, which is
+/// dominated by exact exe and class matches with a sprinkling of prefix and regex rules
+fn rules(regex_identifiers: &mut HashMap<String, Regex>) -> Vec<MatchingRule> {
+    let mut rules = vec![];
+
+    for i in 0..RULE_COUNT {
+        let rule = match i % 10 {
+            0..=5 => rule(
+                ApplicationIdentifier::Exe,
+                format!("application{i}.exe"),
+                MatchingStrategy::Equals,
+            ),
+            6 | 7 => rule(
+                ApplicationIdentifier::Class,
+                format!("WindowClass{i}"),
+                MatchingStrategy::Equals,
+            ),
+            8 => rule(
+                ApplicationIdentifier::Title,
+                format!("Dialog {i}"),
+                MatchingStrategy::StartsWith,
+            ),
+            _ => {
+                let pattern = format!("^Popup {i} .*$");
+                regex_identifiers.insert(pattern.clone(), Regex::new(&pattern).unwrap());
+                rule(
+                    ApplicationIdentifier::Title,
+                    pattern,
+                    MatchingStrategy::Regex,
+                )
+            }
+        };
+
+        rules.push(rule);
+    }
+
+    rules
+}
+
+fn linear(
+    target: &RuleTarget,
+    rules: &[MatchingRule],
+    regex_identifiers: &HashMap<String, Regex>,
+) -> Option<usize> {
+    rules
+        .iter()
+        .position(|rule| matches_rule(target, rule, regex_identifiers))
+}
+
+fn report(name: &str, elapsed: Duration) {
+    println!(
+        "{name:<10} {:>10.2?} total, {:>8.2?} per lookup",
+        elapsed,
+        elapsed / ITERATIONS
+    );
+}
+
+fn main() {
+    let mut regex_identifiers = HashMap::new();
+    let rules = rules(&mut regex_identifiers);
+    let index = RuleIndex::from(rules.clone());
+
+    let targets = [
+        RuleTarget::new(
+            String::from("Untitled - Notepad"),
+            String::from("notepad.exe"),
+            String::from("Notepad"),
+            String::from("C:\\Windows\\notepad.exe"),
+        ),
+        RuleTarget::new(
+            String::from("Popup 499 settings"),
+            String::from("unknown.exe"),
+            String::from("Unknown"),
+            String::from("C:\\unknown.exe"),
+        ),
+        RuleTarget::new(
+            String::from("Main Window"),
+            format!("application{}.exe", RULE_COUNT - 10),
+            String::from("Unknown"),
+            String::new(),
+        ),
+    ];
+
+    for target in &targets {
+        assert_eq!(
+            index
+                .find(target, &regex_identifiers)
+                .and_then(|found| rules.iter().position(|rule| rule == found)),
+            linear(target, &rules, &regex_identifiers)
+        );
+    }
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        for target in &targets {
+            black_box(linear(target, &rules, &regex_identifiers));
+        }
+    }
+    report("linear", start.elapsed());
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        for target in &targets {
+            black_box(index.find(target, &regex_identifiers));
+        }
+    }
+    report("indexed", start.elapsed());
+}
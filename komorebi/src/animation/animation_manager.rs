@@ -1,18 +1,11 @@
-use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 
 use super::prefix::AnimationPrefix;
 
-#[derive(Debug, Clone, Copy)]
-struct AnimationState {
-    pub in_progress: bool,
-    pub cancel_idx_counter: usize,
-    pub pending_cancel_count: usize,
-}
-
+/// Keeps track of the animations which have been queued and not yet finished or replaced, by key
 #[derive(Debug)]
 pub struct AnimationManager {
-    animations: HashMap<String, AnimationState>,
+    animations: HashMap<String, usize>,
 }
 
 impl Default for AnimationManager {
@@ -28,75 +21,22 @@ impl AnimationManager {
         }
     }
 
-    pub fn is_cancelled(&self, animation_key: &str) -> bool {
-        if let Some(animation_state) = self.animations.get(animation_key) {
-            animation_state.pending_cancel_count > 0
-        } else {
-            false
-        }
-    }
-
     pub fn in_progress(&self, animation_key: &str) -> bool {
-        if let Some(animation_state) = self.animations.get(animation_key) {
-            animation_state.in_progress
-        } else {
-            false
-        }
-    }
-
-    pub fn init_cancel(&mut self, animation_key: &str) -> usize {
-        if let Some(animation_state) = self.animations.get_mut(animation_key) {
-            animation_state.pending_cancel_count += 1;
-            animation_state.cancel_idx_counter += 1;
-
-            // return cancel idx
-            animation_state.cancel_idx_counter
-        } else {
-            0
-        }
-    }
-
-    pub fn latest_cancel_idx(&mut self, animation_key: &str) -> usize {
-        if let Some(animation_state) = self.animations.get_mut(animation_key) {
-            animation_state.cancel_idx_counter
-        } else {
-            0
-        }
-    }
-
-    pub fn end_cancel(&mut self, animation_key: &str) {
-        if let Some(animation_state) = self.animations.get_mut(animation_key) {
-            animation_state.pending_cancel_count -= 1;
-        }
-    }
-
-    pub fn cancel(&mut self, animation_key: &str) {
-        if let Some(animation_state) = self.animations.get_mut(animation_key) {
-            animation_state.in_progress = false;
-        }
+        self.animations.contains_key(animation_key)
     }
 
     pub fn start(&mut self, animation_key: &str) {
-        if let Entry::Vacant(e) = self.animations.entry(animation_key.to_string()) {
-            e.insert(AnimationState {
-                in_progress: true,
-                cancel_idx_counter: 0,
-                pending_cancel_count: 0,
-            });
-
-            return;
-        }
-
-        if let Some(animation_state) = self.animations.get_mut(animation_key) {
-            animation_state.in_progress = true;
-        }
+        *self
+            .animations
+            .entry(animation_key.to_string())
+            .or_default() += 1;
     }
 
     pub fn end(&mut self, animation_key: &str) {
-        if let Some(animation_state) = self.animations.get_mut(animation_key) {
-            animation_state.in_progress = false;
+        if let Some(count) = self.animations.get_mut(animation_key) {
+            *count -= 1;
 
-            if animation_state.pending_cancel_count == 0 {
+            if *count == 0 {
                 self.animations.remove(animation_key);
             }
         }
@@ -1,16 +1,44 @@
+use color_eyre::eyre::bail;
 use color_eyre::Result;
 
+use crossbeam_channel::Receiver;
+use crossbeam_channel::Sender;
 use serde::Deserialize;
 use serde::Serialize;
+use std::collections::HashMap;
 use std::sync::atomic::Ordering;
+use std::sync::OnceLock;
 use std::time::Duration;
 use std::time::Instant;
 
+use super::render_dispatcher::Frame;
 use super::RenderDispatcher;
 use super::ANIMATION_DURATION_GLOBAL;
 use super::ANIMATION_FPS;
 use super::ANIMATION_MANAGER;
 
+type BoxedRenderDispatcher = Box<dyn RenderDispatcher + Send>;
+
+static SCHEDULER: OnceLock<Sender<(BoxedRenderDispatcher, Duration)>> = OnceLock::new();
+
+/// An animation which is being rendered by the scheduler
+struct Animation {
+    render_dispatcher: BoxedRenderDispatcher,
+    duration: Duration,
+    start: Instant,
+}
+
+impl Animation {
+    #[allow(clippy::cast_precision_loss)]
+    fn progress(&self) -> f64 {
+        if self.duration.is_zero() {
+            return 1.0;
+        }
+
+        (self.start.elapsed().as_millis() as f64 / self.duration.as_millis() as f64).min(1.0)
+    }
+}
+
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct AnimationEngine;
@@ -31,91 +59,126 @@ impl AnimationEngine {
         }
     }
 
-    /// Returns true if the animation needs to continue
-    pub fn cancel(animation_key: &str) -> bool {
-        // should be more than 0
-        let cancel_idx = ANIMATION_MANAGER.lock().init_cancel(animation_key);
-        let max_duration = Duration::from_secs(5);
-        let spent_duration = Instant::now();
-
-        while ANIMATION_MANAGER.lock().in_progress(animation_key) {
-            if spent_duration.elapsed() >= max_duration {
-                ANIMATION_MANAGER.lock().end(animation_key);
-            }
-
-            std::thread::sleep(Duration::from_millis(250 / 2));
-        }
-
-        let latest_cancel_idx = ANIMATION_MANAGER.lock().latest_cancel_idx(animation_key);
-
-        ANIMATION_MANAGER.lock().end_cancel(animation_key);
-
-        latest_cancel_idx == cancel_idx
+    /// The sender for the scheduler thread, which is started the first time it is needed
+    fn scheduler() -> &'static Sender<(BoxedRenderDispatcher, Duration)> {
+        SCHEDULER.get_or_init(|| {
+            let (sender, receiver) = crossbeam_channel::unbounded();
+            std::thread::spawn(move || Self::schedule(&receiver));
+            sender
+        })
     }
 
-    #[allow(clippy::cast_precision_loss)]
+    /// Queues an animation on the scheduler thread, replacing any animation in progress with the
+    /// same key so that it is retargeted from wherever it currently is
     pub fn animate(
         render_dispatcher: (impl RenderDispatcher + Send + 'static),
         duration: Duration,
     ) -> Result<()> {
-        std::thread::spawn(move || {
-            let animation_key = render_dispatcher.get_animation_key();
-            if ANIMATION_MANAGER.lock().in_progress(animation_key.as_str()) {
-                let should_animate = Self::cancel(animation_key.as_str());
+        let animation_key = render_dispatcher.get_animation_key();
+
+        // the animation counts as in progress as soon as it has been queued
+        ANIMATION_MANAGER.lock().start(animation_key.as_str());
+
+        if Self::scheduler()
+            .send((Box::new(render_dispatcher), duration))
+            .is_err()
+        {
+            ANIMATION_MANAGER.lock().end(animation_key.as_str());
+            bail!("the animation scheduler has stopped");
+        }
 
-                if !should_animate {
-                    return Ok(());
+        Ok(())
+    }
+
+    /// Renders every animation in progress on the same frame, one frame at a time
+    fn schedule(receiver: &Receiver<(BoxedRenderDispatcher, Duration)>) {
+        let mut animations: HashMap<String, Animation> = HashMap::new();
+
+        loop {
+            // block until there is something to animate
+            if animations.is_empty() {
+                match receiver.recv() {
+                    Ok(queued) => Self::start(&mut animations, queued),
+                    Err(_) => return,
                 }
             }
 
-            render_dispatcher.pre_render()?;
-
-            ANIMATION_MANAGER.lock().start(animation_key.as_str());
+            while let Ok(queued) = receiver.try_recv() {
+                Self::start(&mut animations, queued);
+            }
 
+            let frame_start = Instant::now();
             let target_frame_time =
-                Duration::from_millis(1000 / ANIMATION_FPS.load(Ordering::Relaxed));
-            let mut progress = 0.0;
-            let animation_start = Instant::now();
-
-            // start animation
-            while progress < 1.0 {
-                // check if animation is cancelled
-                if ANIMATION_MANAGER
-                    .lock()
-                    .is_cancelled(animation_key.as_str())
-                {
-                    // cancel animation
-                    ANIMATION_MANAGER.lock().cancel(animation_key.as_str());
-                    return Ok(());
-                }
+                Duration::from_millis(1000 / ANIMATION_FPS.load(Ordering::Relaxed).max(1));
 
-                let frame_start = Instant::now();
-                // calculate progress
-                progress =
-                    animation_start.elapsed().as_millis() as f64 / duration.as_millis() as f64;
-                render_dispatcher.render(progress).ok();
+            let mut frame = Frame::default();
+            let mut finished = vec![];
 
-                // sleep until next frame
-                let frame_time_elapsed = frame_start.elapsed();
+            for (animation_key, animation) in &animations {
+                let progress = animation.progress();
+                animation
+                    .render_dispatcher
+                    .render(progress, &mut frame)
+                    .ok();
 
-                if frame_time_elapsed < target_frame_time {
-                    std::thread::sleep(target_frame_time - frame_time_elapsed);
+                if progress >= 1.0 {
+                    finished.push(animation_key.clone());
                 }
             }
 
-            ANIMATION_MANAGER.lock().end(animation_key.as_str());
+            if let Err(error) = frame.apply() {
+                tracing::error!("could not move windows: {error}");
+            }
 
-            // limit progress to 1.0 if animation took longer
-            if progress != 1.0 {
-                progress = 1.0;
+            if !finished.is_empty() {
+                let mut animation_manager = ANIMATION_MANAGER.lock();
+                for animation_key in &finished {
+                    animation_manager.end(animation_key);
+                }
+            }
 
-                // process animation for 1.0 to set target position
-                render_dispatcher.render(progress).ok();
+            for animation_key in finished {
+                if let Some(animation) = animations.remove(&animation_key) {
+                    if let Err(error) = animation.render_dispatcher.post_render() {
+                        tracing::error!("could not finish animation {animation_key}: {error}");
+                    }
+                }
             }
 
-            render_dispatcher.post_render()
-        });
+            // sleep until the next frame
+            let frame_time_elapsed = frame_start.elapsed();
 
-        Ok(())
+            if frame_time_elapsed < target_frame_time {
+                std::thread::sleep(target_frame_time - frame_time_elapsed);
+            }
+        }
+    }
+
+    fn start(
+        animations: &mut HashMap<String, Animation>,
+        (render_dispatcher, duration): (BoxedRenderDispatcher, Duration),
+    ) {
+        let animation_key = render_dispatcher.get_animation_key();
+
+        if let Err(error) = render_dispatcher.pre_render() {
+            tracing::error!("could not start animation {animation_key}: {error}");
+            ANIMATION_MANAGER.lock().end(animation_key.as_str());
+            return;
+        }
+
+        // the new render dispatcher starts from the current state of the animation it replaces,
+        // so it takes over without the replaced animation having to finish first
+        let replaced = animations.insert(
+            animation_key.clone(),
+            Animation {
+                render_dispatcher,
+                duration,
+                start: Instant::now(),
+            },
+        );
+
+        if replaced.is_some() {
+            ANIMATION_MANAGER.lock().end(animation_key.as_str());
+        }
     }
 }
@@ -1,5 +1,8 @@
 use crate::core::Rect;
+use crate::core::Spring;
 use crate::AnimationStyle;
+use crate::Oklab;
+use crate::Rgb;
 
 use super::style::apply_ease_func;
 
@@ -40,3 +43,59 @@ impl Lerp for Rect {
         }
     }
 }
+
+impl Lerp for Rgb {
+    /// Interpolates through OKLab so that the colours in between don't get muddy
+    fn lerp(self, end: Rgb, time: f64, style: AnimationStyle) -> Rgb {
+        let start = Oklab::from(self);
+        let end = Oklab::from(end);
+
+        Rgb::from(Oklab {
+            l: start.l.lerp(end.l, time, style),
+            a: start.a.lerp(end.a, time, style),
+            b: start.b.lerp(end.b, time, style),
+        })
+    }
+}
+
+pub trait SpringMotion<T = Self> {
+    /// The value and velocity (per second) at time t (in seconds) of a spring released from self
+    /// towards end with an initial velocity
+    fn spring(self, end: T, velocity: T, t: f64, spring: Spring) -> (T, T);
+}
+
+impl SpringMotion for i32 {
+    #[allow(clippy::cast_possible_truncation)]
+    fn spring(self, end: i32, velocity: i32, t: f64, spring: Spring) -> (i32, i32) {
+        let (displacement, velocity) = spring.state(f64::from(self - end), f64::from(velocity), t);
+
+        (
+            (f64::from(end) + displacement).round() as i32,
+            velocity.round() as i32,
+        )
+    }
+}
+
+impl SpringMotion for Rect {
+    fn spring(self, end: Rect, velocity: Rect, t: f64, spring: Spring) -> (Rect, Rect) {
+        let (left, left_velocity) = self.left.spring(end.left, velocity.left, t, spring);
+        let (top, top_velocity) = self.top.spring(end.top, velocity.top, t, spring);
+        let (right, right_velocity) = self.right.spring(end.right, velocity.right, t, spring);
+        let (bottom, bottom_velocity) = self.bottom.spring(end.bottom, velocity.bottom, t, spring);
+
+        (
+            Rect {
+                left,
+                top,
+                right,
+                bottom,
+            },
+            Rect {
+                left: left_velocity,
+                top: top_velocity,
+                right: right_velocity,
+                bottom: bottom_velocity,
+            },
+        )
+    }
+}
@@ -1,5 +1,7 @@
 use crate::animation::animation_manager::AnimationManager;
 use crate::core::animation::AnimationStyle;
+use crate::core::Rect;
+use crate::styles::ExtendedWindowStyle;
 
 use lazy_static::lazy_static;
 use prefix::AnimationPrefix;
@@ -50,6 +52,17 @@ lazy_static! {
         Arc::new(Mutex::new(HashMap::new()));
     pub static ref ANIMATION_DURATION_PER_ANIMATION: Arc<Mutex<HashMap<AnimationPrefix, u64>>> =
         Arc::new(Mutex::new(HashMap::new()));
+    /// Velocity of each window on the last rendered frame of its spring movement animation, in
+    /// pixels per second, so that an interrupted animation continues from the current velocity
+    pub static ref MOVEMENT_VELOCITY: Arc<Mutex<HashMap<isize, Rect>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+    /// Where each window taking part in a workspace transition rests once the transition is over
+    pub static ref WORKSPACE_TRANSITION_RECTS: Arc<Mutex<HashMap<isize, Rect>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+    /// The extended style of each window taking part in a workspace fade from before the fade
+    /// made it layered, which is put back once the fade is over
+    pub static ref WORKSPACE_TRANSITION_EX_STYLES: Arc<Mutex<HashMap<isize, ExtendedWindowStyle>>> =
+        Arc::new(Mutex::new(HashMap::new()));
 }
 
 pub static ANIMATION_FPS: AtomicU64 = AtomicU64::new(DEFAULT_ANIMATION_FPS);
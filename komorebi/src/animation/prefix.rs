@@ -14,6 +14,14 @@ use strum::EnumString;
 pub enum AnimationPrefix {
     Movement,
     Transparency,
+    /// Border colour, width and offset changes when the kind of the bordered window changes
+    Border,
+    /// Slide workspaces horizontally when switching between them (must be enabled explicitly)
+    WorkspaceSlideHorizontal,
+    /// Slide workspaces vertically when switching between them (must be enabled explicitly)
+    WorkspaceSlideVertical,
+    /// Fade between workspaces when switching between them (must be enabled explicitly)
+    WorkspaceFade,
 }
 
 pub fn new_animation_key(prefix: AnimationPrefix, key: String) -> String {
@@ -1,8 +1,37 @@
+use crate::core::Rect;
+use crate::windows_api::WindowsApi;
 use color_eyre::Result;
 
 pub trait RenderDispatcher {
     fn get_animation_key(&self) -> String;
     fn pre_render(&self) -> Result<()>;
-    fn render(&self, delta: f64) -> Result<()>;
+    fn render(&self, delta: f64, frame: &mut Frame) -> Result<()>;
     fn post_render(&self) -> Result<()>;
 }
+
+/// The window moves requested while rendering every animation for a single frame, which are
+/// applied together once all of the animations have been rendered
+#[derive(Debug, Default)]
+pub struct Frame {
+    moves: Vec<(isize, Rect)>,
+}
+
+impl Frame {
+    pub fn move_window(&mut self, hwnd: isize, rect: Rect) {
+        self.moves.push((hwnd, rect));
+    }
+
+    pub fn apply(self) -> Result<()> {
+        if self.moves.is_empty() {
+            return Ok(());
+        }
+
+        WindowsApi::move_windows(&self.moves)?;
+
+        for (hwnd, _) in &self.moves {
+            WindowsApi::invalidate_rect(*hwnd, None, false);
+        }
+
+        Ok(())
+    }
+}
@@ -1,6 +1,8 @@
 use crate::core::AnimationStyle;
+use crate::core::Spring;
 
 use std::f64::consts::PI;
+use std::time::Duration;
 
 pub trait Ease {
     fn evaluate(t: f64) -> f64;
@@ -355,6 +357,177 @@ impl Ease for EaseInOutBounce {
     }
 }
 
+pub struct CubicBezier {
+    x1: f64,
+    y1: f64,
+    x2: f64,
+    y2: f64,
+}
+
+impl CubicBezier {
+    pub fn new(x1: f64, y1: f64, x2: f64, y2: f64) -> Self {
+        // the curve has to be a function of time, which it is when x stays within 0.0..=1.0
+        Self {
+            x1: x1.clamp(0.0, 1.0),
+            y1,
+            x2: x2.clamp(0.0, 1.0),
+            y2,
+        }
+    }
+
+    fn sample(a1: f64, a2: f64, s: f64) -> f64 {
+        let c = 3.0 * a1;
+        let b = 3.0f64.mul_add(a2 - a1, -c);
+        let a = 1.0 - c - b;
+
+        a.mul_add(s, b).mul_add(s, c) * s
+    }
+
+    fn sample_derivative(a1: f64, a2: f64, s: f64) -> f64 {
+        let c = 3.0 * a1;
+        let b = 3.0f64.mul_add(a2 - a1, -c);
+        let a = 1.0 - c - b;
+
+        (3.0 * a).mul_add(s, 2.0 * b).mul_add(s, c)
+    }
+
+    /// Finds the curve parameter at which the curve reaches time t
+    fn solve(&self, t: f64) -> f64 {
+        // newton's method converges quickly for most curves
+        let mut s = t;
+        for _ in 0..8 {
+            let error = Self::sample(self.x1, self.x2, s) - t;
+            if error.abs() < 1e-7 {
+                return s;
+            }
+
+            let derivative = Self::sample_derivative(self.x1, self.x2, s);
+            if derivative.abs() < 1e-6 {
+                break;
+            }
+
+            s -= error / derivative;
+        }
+
+        // fall back to bisection for flat sections of the curve
+        let (mut low, mut high) = (0.0, 1.0);
+        s = t;
+        while high - low > 1e-7 {
+            if Self::sample(self.x1, self.x2, s) < t {
+                low = s;
+            } else {
+                high = s;
+            }
+
+            s = (low + high) / 2.0;
+        }
+
+        s
+    }
+
+    pub fn evaluate(&self, t: f64) -> f64 {
+        if t <= 0.0 || t >= 1.0 {
+            return t.clamp(0.0, 1.0);
+        }
+
+        Self::sample(self.y1, self.y2, self.solve(t))
+    }
+}
+
+/// Remaining displacement relative to the initial amplitude below which a spring is settled
+const SPRING_SETTLED: f64 = 0.001;
+
+impl Spring {
+    fn angular_frequency(&self) -> f64 {
+        (self.stiffness.max(f64::EPSILON) / self.mass.max(f64::EPSILON)).sqrt()
+    }
+
+    fn damping_ratio(&self) -> f64 {
+        self.damping.max(0.0)
+            / (2.0 * (self.stiffness.max(f64::EPSILON) * self.mass.max(f64::EPSILON)).sqrt())
+    }
+
+    /// How long it takes for the spring to settle at its target, capped at 10 seconds
+    pub fn duration(&self) -> Duration {
+        let omega = self.angular_frequency();
+        let zeta = self.damping_ratio();
+
+        // the slowest rate at which the motion decays
+        let decay = if zeta <= 1.0 {
+            zeta * omega
+        } else {
+            omega * (zeta - zeta.mul_add(zeta, -1.0).sqrt())
+        };
+
+        if decay <= f64::EPSILON {
+            return Duration::from_secs(10);
+        }
+
+        Duration::from_secs_f64((-SPRING_SETTLED.ln() / decay).min(10.0))
+    }
+
+    /// The displacement from the target and the velocity at time t (in seconds) of a spring
+    /// released at displacement x0 with velocity v0
+    pub fn state(&self, x0: f64, v0: f64, t: f64) -> (f64, f64) {
+        let omega = self.angular_frequency();
+        let zeta = self.damping_ratio();
+
+        if (zeta - 1.0).abs() < 1e-6 {
+            // critically damped
+            let b = omega.mul_add(x0, v0);
+            let envelope = (-omega * t).exp();
+
+            (
+                envelope * b.mul_add(t, x0),
+                envelope * (omega * b).mul_add(-t, v0),
+            )
+        } else if zeta < 1.0 {
+            // underdamped
+            let decay = zeta * omega;
+            let omega_d = omega * zeta.mul_add(-zeta, 1.0).sqrt();
+            let b = decay.mul_add(x0, v0) / omega_d;
+            let envelope = (-decay * t).exp();
+            let (sin, cos) = (omega_d * t).sin_cos();
+
+            (
+                envelope * x0.mul_add(cos, b * sin),
+                envelope
+                    * v0.mul_add(
+                        cos,
+                        -(decay.mul_add(v0, omega * omega * x0) / omega_d) * sin,
+                    ),
+            )
+        } else {
+            // overdamped
+            let root = zeta.mul_add(zeta, -1.0).sqrt();
+            let r1 = -omega * (zeta - root);
+            let r2 = -omega * (zeta + root);
+            let c2 = r1.mul_add(-x0, v0) / (r2 - r1);
+            let c1 = x0 - c2;
+            let (e1, e2) = ((r1 * t).exp(), (r2 * t).exp());
+
+            (c1.mul_add(e1, c2 * e2), (c1 * r1).mul_add(e1, c2 * r2 * e2))
+        }
+    }
+
+    /// Progress from 0.0 to 1.0 of a spring released from rest, at t from 0.0 to 1.0 of its duration
+    pub fn evaluate(&self, t: f64) -> f64 {
+        let (displacement, _) = self.state(1.0, 0.0, t * self.duration().as_secs_f64());
+
+        1.0 - displacement
+    }
+}
+
+impl AnimationStyle {
+    /// The duration of an animation with this style, which is derived from the physics for springs
+    pub fn duration(self, configured: Duration) -> Duration {
+        match self {
+            AnimationStyle::Spring(spring) => spring.duration(),
+            _ => configured,
+        }
+    }
+}
+
 pub fn apply_ease_func(t: f64, style: AnimationStyle) -> f64 {
     match style {
         AnimationStyle::Linear => Linear::evaluate(t),
@@ -365,6 +538,7 @@ pub fn apply_ease_func(t: f64, style: AnimationStyle) -> f64 {
         AnimationStyle::EaseOutQuad => EaseOutQuad::evaluate(t),
         AnimationStyle::EaseInOutQuad => EaseInOutQuad::evaluate(t),
         AnimationStyle::EaseInCubic => EaseInCubic::evaluate(t),
+        AnimationStyle::EaseOutCubic => EaseOutCubic::evaluate(t),
         AnimationStyle::EaseInOutCubic => EaseInOutCubic::evaluate(t),
         AnimationStyle::EaseInQuart => EaseInQuart::evaluate(t),
         AnimationStyle::EaseOutQuart => EaseOutQuart::evaluate(t),
@@ -387,5 +561,7 @@ pub fn apply_ease_func(t: f64, style: AnimationStyle) -> f64 {
         AnimationStyle::EaseInBounce => EaseInBounce::evaluate(t),
         AnimationStyle::EaseOutBounce => EaseOutBounce::evaluate(t),
         AnimationStyle::EaseInOutBounce => EaseInOutBounce::evaluate(t),
+        AnimationStyle::CubicBezier(x1, y1, x2, y2) => CubicBezier::new(x1, y1, x2, y2).evaluate(t),
+        AnimationStyle::Spring(spring) => spring.evaluate(t),
     }
 }
@@ -1,8 +1,9 @@
-use crate::border_manager::window_kind_colour;
+use crate::border_manager::paint::BorderPaint;
+use crate::border_manager::paint::BorderRenderDispatcher;
+use crate::border_manager::BorderSettings;
 use crate::border_manager::RenderTarget;
 use crate::border_manager::WindowKind;
-use crate::border_manager::BORDER_OFFSET;
-use crate::border_manager::BORDER_WIDTH;
+use crate::border_manager::ANIMATED_PAINTS;
 use crate::border_manager::STYLE;
 use crate::core::BorderStyle;
 use crate::core::Rect;
@@ -10,9 +11,7 @@ use crate::windows_api;
 use crate::WindowsApi;
 use crate::WINDOWS_11;
 use color_eyre::eyre::anyhow;
-use std::collections::HashMap;
 use std::ops::Deref;
-use std::sync::atomic::Ordering;
 use std::sync::mpsc;
 use std::sync::LazyLock;
 use std::sync::OnceLock;
@@ -23,13 +22,11 @@ use windows::Win32::Foundation::LRESULT;
 use windows::Win32::Foundation::TRUE;
 use windows::Win32::Foundation::WPARAM;
 use windows::Win32::Graphics::Direct2D::Common::D2D1_ALPHA_MODE_PREMULTIPLIED;
-use windows::Win32::Graphics::Direct2D::Common::D2D1_COLOR_F;
 use windows::Win32::Graphics::Direct2D::Common::D2D1_PIXEL_FORMAT;
 use windows::Win32::Graphics::Direct2D::Common::D2D_RECT_F;
 use windows::Win32::Graphics::Direct2D::Common::D2D_SIZE_U;
 use windows::Win32::Graphics::Direct2D::D2D1CreateFactory;
 use windows::Win32::Graphics::Direct2D::ID2D1Factory;
-use windows::Win32::Graphics::Direct2D::ID2D1SolidColorBrush;
 use windows::Win32::Graphics::Direct2D::D2D1_ANTIALIAS_MODE_PER_PRIMITIVE;
 use windows::Win32::Graphics::Direct2D::D2D1_BRUSH_PROPERTIES;
 use windows::Win32::Graphics::Direct2D::D2D1_FACTORY_TYPE_MULTI_THREADED;
@@ -123,7 +120,10 @@ pub struct Border {
     pub offset: i32,
     pub brush_properties: D2D1_BRUSH_PROPERTIES,
     pub rounded_rect: D2D1_ROUNDED_RECT,
-    pub brushes: HashMap<WindowKind, ID2D1SolidColorBrush>,
+    /// The paint for the window kind that the border was last drawn for
+    pub paint: Option<BorderPaint>,
+    /// The border settings of the workspace that the border is on
+    pub settings: BorderSettings,
 }
 
 impl From<isize> for Border {
@@ -137,11 +137,12 @@ impl From<isize> for Border {
             window_rect: Rect::default(),
             window_kind: WindowKind::Unfocused,
             style: STYLE.load(),
-            width: BORDER_WIDTH.load(Ordering::Relaxed),
-            offset: BORDER_OFFSET.load(Ordering::Relaxed),
+            width: BorderSettings::default().width(),
+            offset: BorderSettings::default().offset(),
             brush_properties: D2D1_BRUSH_PROPERTIES::default(),
             rounded_rect: D2D1_ROUNDED_RECT::default(),
-            brushes: HashMap::new(),
+            paint: None,
+            settings: BorderSettings::default(),
         }
     }
 }
@@ -155,6 +156,7 @@ impl Border {
         id: &str,
         tracking_hwnd: isize,
         monitor_idx: usize,
+        settings: &BorderSettings,
     ) -> color_eyre::Result<Box<Self>> {
         let name: Vec<u16> = format!("komoborder-{id}\0").encode_utf16().collect();
         let class_name = PCWSTR(name.as_ptr());
@@ -175,6 +177,7 @@ impl Border {
 
         let instance = h_module.0 as isize;
         let container_id = id.to_owned();
+        let settings = settings.clone();
         std::thread::spawn(move || -> color_eyre::Result<()> {
             let mut border = Self {
                 hwnd: 0,
@@ -185,11 +188,12 @@ impl Border {
                 window_rect: WindowsApi::window_rect(tracking_hwnd).unwrap_or_default(),
                 window_kind: WindowKind::Unfocused,
                 style: STYLE.load(),
-                width: BORDER_WIDTH.load(Ordering::Relaxed),
-                offset: BORDER_OFFSET.load(Ordering::Relaxed),
+                width: settings.width(),
+                offset: settings.offset(),
                 brush_properties: Default::default(),
                 rounded_rect: Default::default(),
-                brushes: HashMap::new(),
+                paint: None,
+                settings,
             };
 
             let border_pointer = &raw mut border;
@@ -262,27 +266,6 @@ impl Border {
         } {
             Ok(render_target) => unsafe {
                 border.brush_properties = *BRUSH_PROPERTIES.deref();
-                for window_kind in [
-                    WindowKind::Single,
-                    WindowKind::Stack,
-                    WindowKind::Monocle,
-                    WindowKind::Unfocused,
-                    WindowKind::Floating,
-                ] {
-                    let color = window_kind_colour(window_kind);
-                    let color = D2D1_COLOR_F {
-                        r: ((color & 0xFF) as f32) / 255.0,
-                        g: (((color >> 8) & 0xFF) as f32) / 255.0,
-                        b: (((color >> 16) & 0xFF) as f32) / 255.0,
-                        a: 1.0,
-                    };
-
-                    if let Ok(brush) =
-                        render_target.CreateSolidColorBrush(&color, Some(&border.brush_properties))
-                    {
-                        border.brushes.insert(window_kind, brush);
-                    }
-                }
 
                 render_target.SetAntialiasMode(D2D1_ANTIALIAS_MODE_PER_PRIMITIVE);
 
@@ -328,6 +311,109 @@ impl Border {
         let _ = unsafe { InvalidateRect(Option::from(self.hwnd()), None, false) };
     }
 
+    /// The paint that the border is currently drawn with
+    fn displayed_paint(&self) -> BorderPaint {
+        ANIMATED_PAINTS
+            .lock()
+            .get(&self.hwnd)
+            .cloned()
+            .or_else(|| self.paint.clone())
+            .unwrap_or_else(|| BorderPaint::for_window_kind(self.window_kind, &self.settings))
+    }
+
+    /// The paint to draw the border with next, which starts an animation from the paint that is
+    /// currently displayed if the paint for the border's window kind has changed since it was
+    /// last drawn
+    fn next_paint(&mut self) -> BorderPaint {
+        let target = BorderPaint::for_window_kind(self.window_kind, &self.settings);
+
+        if self.paint.as_ref().is_some_and(|paint| *paint != target)
+            && BorderRenderDispatcher::is_enabled()
+        {
+            let start = self.displayed_paint();
+            ANIMATED_PAINTS.lock().insert(self.hwnd, start.clone());
+
+            let render_dispatcher = BorderRenderDispatcher::new(self.hwnd, start, target.clone());
+
+            if let Err(error) = render_dispatcher.animate() {
+                tracing::error!("could not animate border: {error}");
+                ANIMATED_PAINTS.lock().remove(&self.hwnd);
+            }
+        }
+
+        self.paint = Some(target);
+
+        let paint = self.displayed_paint();
+        self.width = paint.width;
+        self.offset = paint.offset;
+
+        paint
+    }
+
+    /// Draws the border with a paint into a border window of the given size
+    unsafe fn draw(&mut self, rect: &Rect, paint: &BorderPaint) {
+        let Some(render_target) = self.render_target.get() else {
+            return;
+        };
+
+        let border_width = paint.width;
+        let border_offset = paint.offset;
+
+        self.rounded_rect.rect = D2D_RECT_F {
+            left: (border_width / 2 - border_offset) as f32,
+            top: (border_width / 2 - border_offset) as f32,
+            right: (rect.right - border_width / 2 + border_offset) as f32,
+            bottom: (rect.bottom - border_width / 2 + border_offset) as f32,
+        };
+
+        let _ = render_target.Resize(&D2D_SIZE_U {
+            width: rect.right as u32,
+            height: rect.bottom as u32,
+        });
+
+        let Ok(brush) = paint.brush(render_target, &self.brush_properties, rect) else {
+            return;
+        };
+
+        render_target.BeginDraw();
+        render_target.Clear(None);
+
+        // Calculate border radius based on style
+        let style = match self.style {
+            BorderStyle::System => {
+                if *WINDOWS_11 {
+                    BorderStyle::Rounded
+                } else {
+                    BorderStyle::Square
+                }
+            }
+            BorderStyle::Rounded => BorderStyle::Rounded,
+            BorderStyle::Square => BorderStyle::Square,
+        };
+
+        match style {
+            BorderStyle::Rounded => {
+                render_target.DrawRoundedRectangle(
+                    &self.rounded_rect,
+                    &brush,
+                    border_width as f32,
+                    None,
+                );
+            }
+            BorderStyle::Square => {
+                render_target.DrawRectangle(
+                    &self.rounded_rect.rect,
+                    &brush,
+                    border_width as f32,
+                    None,
+                );
+            }
+            _ => {}
+        }
+
+        let _ = render_target.EndDraw(None, None);
+    }
+
     pub extern "system" fn callback(
         window: HWND,
         message: u32,
@@ -380,63 +466,8 @@ impl Border {
                     }
 
                     if !rect.is_same_size_as(&old_rect) {
-                        if let Some(render_target) = (*border_pointer).render_target.get() {
-                            let border_width = (*border_pointer).width;
-                            let border_offset = (*border_pointer).offset;
-
-                            (*border_pointer).rounded_rect.rect = D2D_RECT_F {
-                                left: (border_width / 2 - border_offset) as f32,
-                                top: (border_width / 2 - border_offset) as f32,
-                                right: (rect.right - border_width / 2 + border_offset) as f32,
-                                bottom: (rect.bottom - border_width / 2 + border_offset) as f32,
-                            };
-
-                            let _ = render_target.Resize(&D2D_SIZE_U {
-                                width: rect.right as u32,
-                                height: rect.bottom as u32,
-                            });
-
-                            let window_kind = (*border_pointer).window_kind;
-                            if let Some(brush) = (*border_pointer).brushes.get(&window_kind) {
-                                render_target.BeginDraw();
-                                render_target.Clear(None);
-
-                                // Calculate border radius based on style
-                                let style = match (*border_pointer).style {
-                                    BorderStyle::System => {
-                                        if *WINDOWS_11 {
-                                            BorderStyle::Rounded
-                                        } else {
-                                            BorderStyle::Square
-                                        }
-                                    }
-                                    BorderStyle::Rounded => BorderStyle::Rounded,
-                                    BorderStyle::Square => BorderStyle::Square,
-                                };
-
-                                match style {
-                                    BorderStyle::Rounded => {
-                                        render_target.DrawRoundedRectangle(
-                                            &(*border_pointer).rounded_rect,
-                                            brush,
-                                            border_width as f32,
-                                            None,
-                                        );
-                                    }
-                                    BorderStyle::Square => {
-                                        render_target.DrawRectangle(
-                                            &(*border_pointer).rounded_rect.rect,
-                                            brush,
-                                            border_width as f32,
-                                            None,
-                                        );
-                                    }
-                                    _ => {}
-                                }
-
-                                let _ = render_target.EndDraw(None, None);
-                            }
-                        }
+                        let paint = (*border_pointer).displayed_paint();
+                        (*border_pointer).draw(&rect, &paint);
                     }
 
                     LRESULT(0)
@@ -452,6 +483,9 @@ impl Border {
 
                         let reference_hwnd = (*border_pointer).tracking_hwnd;
 
+                        (*border_pointer).style = STYLE.load();
+                        let paint = (*border_pointer).next_paint();
+
                         // Update position to update the ZOrder
                         let border_window_rect = (*border_pointer).window_rect;
 
@@ -462,69 +496,7 @@ impl Border {
                             tracing::error!("failed to update border position {error}");
                         }
 
-                        if let Some(render_target) = (*border_pointer).render_target.get() {
-                            (*border_pointer).width = BORDER_WIDTH.load(Ordering::Relaxed);
-                            (*border_pointer).offset = BORDER_OFFSET.load(Ordering::Relaxed);
-
-                            let border_width = (*border_pointer).width;
-                            let border_offset = (*border_pointer).offset;
-
-                            (*border_pointer).rounded_rect.rect = D2D_RECT_F {
-                                left: (border_width / 2 - border_offset) as f32,
-                                top: (border_width / 2 - border_offset) as f32,
-                                right: (rect.right - border_width / 2 + border_offset) as f32,
-                                bottom: (rect.bottom - border_width / 2 + border_offset) as f32,
-                            };
-
-                            let _ = render_target.Resize(&D2D_SIZE_U {
-                                width: rect.right as u32,
-                                height: rect.bottom as u32,
-                            });
-
-                            // Get window kind and color
-                            let window_kind = (*border_pointer).window_kind;
-                            if let Some(brush) = (*border_pointer).brushes.get(&window_kind) {
-                                render_target.BeginDraw();
-                                render_target.Clear(None);
-
-                                (*border_pointer).style = STYLE.load();
-
-                                // Calculate border radius based on style
-                                let style = match (*border_pointer).style {
-                                    BorderStyle::System => {
-                                        if *WINDOWS_11 {
-                                            BorderStyle::Rounded
-                                        } else {
-                                            BorderStyle::Square
-                                        }
-                                    }
-                                    BorderStyle::Rounded => BorderStyle::Rounded,
-                                    BorderStyle::Square => BorderStyle::Square,
-                                };
-
-                                match style {
-                                    BorderStyle::Rounded => {
-                                        render_target.DrawRoundedRectangle(
-                                            &(*border_pointer).rounded_rect,
-                                            brush,
-                                            border_width as f32,
-                                            None,
-                                        );
-                                    }
-                                    BorderStyle::Square => {
-                                        render_target.DrawRectangle(
-                                            &(*border_pointer).rounded_rect.rect,
-                                            brush,
-                                            border_width as f32,
-                                            None,
-                                        );
-                                    }
-                                    _ => {}
-                                }
-
-                                let _ = render_target.EndDraw(None, None);
-                            }
-                        }
+                        (*border_pointer).draw(&rect, &paint);
                     }
                     let _ = ValidateRect(Option::from(window), None);
                     LRESULT(0)
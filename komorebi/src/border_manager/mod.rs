@@ -1,14 +1,19 @@
 #![deny(clippy::unwrap_used, clippy::expect_used)]
 
 mod border;
+mod paint;
 use crate::core::BorderImplementation;
 use crate::core::BorderStyle;
 use crate::core::WindowKind;
 use crate::ring::Ring;
+use crate::static_config::BorderOverrides;
 use crate::windows_api;
 use crate::workspace::WorkspaceLayer;
 use crate::workspace_reconciliator::ALT_TAB_HWND;
+use crate::BorderColour;
+use crate::BorderColours;
 use crate::Colour;
+use crate::Gradient;
 use crate::Rgb;
 use crate::WindowManager;
 use crate::WindowsApi;
@@ -19,6 +24,7 @@ use crossbeam_channel::Sender;
 use crossbeam_utils::atomic::AtomicCell;
 use crossbeam_utils::atomic::AtomicConsume;
 use lazy_static::lazy_static;
+pub use paint::BorderPaint;
 use parking_lot::Mutex;
 use serde::Deserialize;
 use serde::Serialize;
@@ -53,11 +59,14 @@ lazy_static! {
     pub static ref STACK: AtomicU32 = AtomicU32::new(u32::from(Colour::Rgb(Rgb::new(0, 165, 66))));
     pub static ref FLOATING: AtomicU32 =
         AtomicU32::new(u32::from(Colour::Rgb(Rgb::new(245, 245, 165))));
+    /// Gradients which are drawn instead of the border colour of a window kind
+    pub static ref GRADIENTS: Mutex<HashMap<WindowKind, Gradient>> = Mutex::new(HashMap::new());
 }
 
 lazy_static! {
     static ref BORDER_STATE: Mutex<HashMap<String, Box<Border>>> = Mutex::new(HashMap::new());
     static ref WINDOWS_BORDERS: Mutex<HashMap<isize, String>> = Mutex::new(HashMap::new());
+    static ref ANIMATED_PAINTS: Mutex<HashMap<isize, BorderPaint>> = Mutex::new(HashMap::new());
 }
 
 #[derive(Debug, Clone)]
@@ -74,6 +83,66 @@ impl Deref for RenderTarget {
 
 pub struct Notification(pub Option<isize>);
 
+/// Border settings for the borders of a workspace, where anything which is not set falls back to
+/// the global setting
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BorderSettings {
+    pub width: Option<i32>,
+    pub offset: Option<i32>,
+    pub colours: Option<BorderColours>,
+}
+
+impl BorderSettings {
+    /// Applies overrides on top of these settings, scaling sizes by a monitor's scale factor
+    pub fn apply(&mut self, overrides: &BorderOverrides, scale_factor: f32) {
+        if let Some(width) = overrides.width {
+            self.width = Option::from(width.to_pixels(scale_factor));
+        }
+
+        if let Some(offset) = overrides.offset {
+            self.offset = Option::from(offset.to_pixels(scale_factor));
+        }
+
+        // colours which are not overridden keep falling back
+        if let Some(overrides) = &overrides.colours {
+            let colours = self.colours.get_or_insert_with(BorderColours::default);
+            colours.single = overrides.single.clone().or(colours.single.take());
+            colours.stack = overrides.stack.clone().or(colours.stack.take());
+            colours.monocle = overrides.monocle.clone().or(colours.monocle.take());
+            colours.floating = overrides.floating.clone().or(colours.floating.take());
+            colours.unfocused = overrides.unfocused.clone().or(colours.unfocused.take());
+        }
+    }
+
+    pub fn width(&self) -> i32 {
+        self.width
+            .unwrap_or_else(|| BORDER_WIDTH.load(Ordering::SeqCst))
+    }
+
+    pub fn offset(&self) -> i32 {
+        self.offset
+            .unwrap_or_else(|| BORDER_OFFSET.load(Ordering::SeqCst))
+    }
+
+    pub fn colour(&self, window_kind: WindowKind) -> BorderColour {
+        self.colours
+            .as_ref()
+            .and_then(|colours| match window_kind {
+                WindowKind::Single => colours.single.clone(),
+                WindowKind::Stack => colours.stack.clone(),
+                WindowKind::Monocle => colours.monocle.clone(),
+                WindowKind::Floating => colours.floating.clone(),
+                WindowKind::Unfocused => colours.unfocused.clone(),
+            })
+            .unwrap_or_else(|| window_kind_border_colour(window_kind))
+    }
+
+    /// The border colour of a window kind as a single colour, using the first colour of a gradient
+    pub fn solid_colour(&self, window_kind: WindowKind) -> u32 {
+        u32::from(Colour::from(self.colour(window_kind)))
+    }
+}
+
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub struct BorderInfo {
     pub border_hwnd: isize,
@@ -156,6 +225,36 @@ fn window_kind_colour(focus_kind: WindowKind) -> u32 {
     }
 }
 
+/// Sets the border colour of a window kind, replacing any gradient that was set for it before
+pub fn set_window_kind_colour(window_kind: WindowKind, colour: &BorderColour) {
+    let solid = match window_kind {
+        WindowKind::Unfocused => &*UNFOCUSED,
+        WindowKind::Single => &*FOCUSED,
+        WindowKind::Stack => &*STACK,
+        WindowKind::Monocle => &*MONOCLE,
+        WindowKind::Floating => &*FLOATING,
+    };
+
+    solid.store(u32::from(Colour::from(colour.clone())), Ordering::SeqCst);
+
+    match colour {
+        BorderColour::Colour(_) => {
+            GRADIENTS.lock().remove(&window_kind);
+        }
+        BorderColour::Gradient(gradient) => {
+            GRADIENTS.lock().insert(window_kind, gradient.clone());
+        }
+    }
+}
+
+/// The border colour of a window kind, including its gradient if it has one
+pub fn window_kind_border_colour(window_kind: WindowKind) -> BorderColour {
+    match GRADIENTS.lock().get(&window_kind) {
+        Some(gradient) => BorderColour::Gradient(gradient.clone()),
+        None => BorderColour::Colour(Colour::from(window_kind_colour(window_kind))),
+    }
+}
+
 pub fn listen_for_notifications(wm: Arc<Mutex<WindowManager>>) {
     std::thread::spawn(move || loop {
         match handle_notifications(wm.clone()) {
@@ -208,6 +307,8 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                 'monitors: for (monitor_idx, m) in monitors.elements().iter().enumerate() {
                     // Only operate on the focused workspace of each monitor
                     if let Some(ws) = m.focused_workspace() {
+                        let settings = ws.border_settings();
+
                         // Handle the monocle container separately
                         if let Some(monocle) = ws.monocle_container() {
                             let window_kind = if monitor_idx != focused_monitor_idx {
@@ -220,7 +321,7 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                                 .focused_window()
                                 .copied()
                                 .unwrap_or_default()
-                                .set_accent(window_kind_colour(window_kind))?;
+                                .set_accent(settings.solid_colour(window_kind))?;
 
                             continue 'monitors;
                         }
@@ -239,7 +340,7 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                             c.focused_window()
                                 .copied()
                                 .unwrap_or_default()
-                                .set_accent(window_kind_colour(window_kind))?;
+                                .set_accent(settings.solid_colour(window_kind))?;
                         }
 
                         for window in ws.floating_windows() {
@@ -249,7 +350,7 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                                 window_kind = WindowKind::Floating;
                             }
 
-                            window.set_accent(window_kind_colour(window_kind))?;
+                            window.set_accent(settings.solid_colour(window_kind))?;
                         }
                     }
                 }
@@ -343,6 +444,8 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                 'monitors: for (monitor_idx, m) in monitors.elements().iter().enumerate() {
                     // Only operate on the focused workspace of each monitor
                     if let Some(ws) = m.focused_workspace() {
+                        let settings = ws.border_settings();
+
                         // Workspaces with tiling disabled don't have borders
                         if !ws.tile() {
                             // Remove all borders on this monitor
@@ -369,6 +472,7 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                                         monocle.id(),
                                         focused_window_hwnd,
                                         monitor_idx,
+                                        &settings,
                                     ) {
                                         new_border = true;
                                         entry.insert(border)
@@ -384,6 +488,7 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                                 WindowKind::Monocle
                             };
                             border.window_kind = new_focus_state;
+                            border.settings = settings.clone();
 
                             // Update the borders tracking_hwnd in case it changed and remove the
                             // old `tracking_hwnd` from `WINDOWS_BORDERS` if needed.
@@ -473,9 +578,12 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                             let border = match borders.entry(id.clone()) {
                                 Entry::Occupied(entry) => entry.into_mut(),
                                 Entry::Vacant(entry) => {
-                                    if let Ok(border) =
-                                        Border::create(c.id(), focused_window_hwnd, monitor_idx)
-                                    {
+                                    if let Ok(border) = Border::create(
+                                        c.id(),
+                                        focused_window_hwnd,
+                                        monitor_idx,
+                                        &settings,
+                                    ) {
                                         new_border = true;
                                         entry.insert(border)
                                     } else {
@@ -485,6 +593,7 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                             };
 
                             let last_focus_state = border.window_kind;
+                            let settings_changed = border.settings != settings;
 
                             let new_focus_state = if idx != ws.focused_container_idx()
                                 || monitor_idx != focused_monitor_idx
@@ -498,6 +607,7 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                             };
 
                             border.window_kind = new_focus_state;
+                            border.settings = settings.clone();
 
                             // Update the borders `tracking_hwnd` in case it changed and remove the
                             // old `tracking_hwnd` from `WINDOWS_BORDERS` if needed.
@@ -533,7 +643,8 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
 
                             let should_invalidate = new_border
                                 || (last_focus_state != new_focus_state)
-                                || layer_changed;
+                                || layer_changed
+                                || settings_changed;
 
                             if should_invalidate {
                                 border.set_position(&rect, focused_window_hwnd)?;
@@ -554,6 +665,7 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                                             &window.hwnd.to_string(),
                                             window.hwnd,
                                             monitor_idx,
+                                            &settings,
                                         ) {
                                             new_border = true;
                                             entry.insert(border)
@@ -564,6 +676,7 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                                 };
 
                                 let last_focus_state = border.window_kind;
+                                let settings_changed = border.settings != settings;
 
                                 let new_focus_state = if foreground_window == window.hwnd {
                                     WindowKind::Floating
@@ -572,6 +685,7 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                                 };
 
                                 border.window_kind = new_focus_state;
+                                border.settings = settings.clone();
 
                                 let rect = WindowsApi::window_rect(window.hwnd)?;
                                 border.window_rect = rect;
@@ -580,7 +694,8 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
 
                                 let should_invalidate = new_border
                                     || (last_focus_state != new_focus_state)
-                                    || layer_changed;
+                                    || layer_changed
+                                    || settings_changed;
 
                                 if should_invalidate {
                                     border.set_position(&rect, window.hwnd)?;
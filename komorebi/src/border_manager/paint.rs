@@ -0,0 +1,269 @@
+use crate::animation::lerp::Lerp;
+use crate::animation::prefix::new_animation_key;
+use crate::animation::prefix::AnimationPrefix;
+use crate::animation::render_dispatcher::Frame;
+use crate::animation::AnimationEngine;
+use crate::animation::RenderDispatcher;
+use crate::animation::ANIMATION_DURATION_GLOBAL;
+use crate::animation::ANIMATION_DURATION_PER_ANIMATION;
+use crate::animation::ANIMATION_ENABLED_GLOBAL;
+use crate::animation::ANIMATION_ENABLED_PER_ANIMATION;
+use crate::animation::ANIMATION_STYLE_GLOBAL;
+use crate::animation::ANIMATION_STYLE_PER_ANIMATION;
+use crate::border_manager::BorderSettings;
+use crate::border_manager::ANIMATED_PAINTS;
+use crate::core::Rect;
+use crate::core::WindowKind;
+use crate::AnimationStyle;
+use crate::BorderColour;
+use crate::Colour;
+use crate::Rgb;
+use crate::WindowsApi;
+use color_eyre::Result;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use windows::Win32::Graphics::Direct2D::Common::D2D1_COLOR_F;
+use windows::Win32::Graphics::Direct2D::Common::D2D1_GRADIENT_STOP;
+use windows::Win32::Graphics::Direct2D::Common::D2D_POINT_2F;
+use windows::Win32::Graphics::Direct2D::ID2D1Brush;
+use windows::Win32::Graphics::Direct2D::ID2D1HwndRenderTarget;
+use windows::Win32::Graphics::Direct2D::D2D1_BRUSH_PROPERTIES;
+use windows::Win32::Graphics::Direct2D::D2D1_EXTEND_MODE_CLAMP;
+use windows::Win32::Graphics::Direct2D::D2D1_GAMMA_2_2;
+use windows::Win32::Graphics::Direct2D::D2D1_LINEAR_GRADIENT_BRUSH_PROPERTIES;
+use windows_core::Interface;
+
+/// Everything that a border is drawn with, which can be interpolated between window kinds
+#[derive(Debug, Clone, PartialEq)]
+pub struct BorderPaint {
+    /// Colours spread evenly along the gradient, where a single colour is a solid border
+    pub colours: Vec<Rgb>,
+    /// Direction of the gradient in degrees clockwise, where 0 runs from left to right
+    pub angle: f64,
+    pub width: i32,
+    pub offset: i32,
+}
+
+impl BorderPaint {
+    pub fn for_window_kind(window_kind: WindowKind, settings: &BorderSettings) -> Self {
+        let (colours, angle) = match settings.colour(window_kind) {
+            BorderColour::Gradient(gradient) if !gradient.colours.is_empty() => (
+                gradient
+                    .colours
+                    .iter()
+                    .map(|colour| Rgb::from(*colour))
+                    .collect(),
+                f64::from(gradient.angle.unwrap_or_default()),
+            ),
+            colour => (vec![Rgb::from(Colour::from(colour))], 0.0),
+        };
+
+        Self {
+            colours,
+            angle,
+            width: settings.width(),
+            offset: settings.offset(),
+        }
+    }
+
+    /// The colour at a position between 0 (the start) and 1 (the end) of the gradient
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn colour_at(&self, position: f64) -> Rgb {
+        match self.colours.as_slice() {
+            [] => Rgb::new(0, 0, 0),
+            [colour] => *colour,
+            colours => {
+                let scaled = position.clamp(0.0, 1.0) * (colours.len() - 1) as f64;
+                let idx = (scaled.floor() as usize).min(colours.len() - 2);
+
+                colours[idx].lerp(
+                    colours[idx + 1],
+                    scaled - idx as f64,
+                    AnimationStyle::Linear,
+                )
+            }
+        }
+    }
+
+    /// Creates a brush for a border window of the given size, which is a linear gradient brush
+    /// when there is more than one colour
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    pub fn brush(
+        &self,
+        render_target: &ID2D1HwndRenderTarget,
+        brush_properties: &D2D1_BRUSH_PROPERTIES,
+        rect: &Rect,
+    ) -> windows_core::Result<ID2D1Brush> {
+        unsafe {
+            let colour = |rgb: Rgb| D2D1_COLOR_F {
+                r: rgb.r as f32 / 255.0,
+                g: rgb.g as f32 / 255.0,
+                b: rgb.b as f32 / 255.0,
+                a: 1.0,
+            };
+
+            if self.colours.len() < 2 {
+                return render_target
+                    .CreateSolidColorBrush(&colour(self.colour_at(0.0)), Some(brush_properties))?
+                    .cast();
+            }
+
+            let last = (self.colours.len() - 1) as f32;
+            let stops = self
+                .colours
+                .iter()
+                .enumerate()
+                .map(|(i, rgb)| D2D1_GRADIENT_STOP {
+                    position: i as f32 / last,
+                    color: colour(*rgb),
+                })
+                .collect::<Vec<_>>();
+
+            let stop_collection = render_target.CreateGradientStopCollection(
+                &stops,
+                D2D1_GAMMA_2_2,
+                D2D1_EXTEND_MODE_CLAMP,
+            )?;
+
+            // the gradient line runs through the centre and reaches the furthest corners
+            let (width, height) = (rect.right as f32, rect.bottom as f32);
+            let (sin, cos) = (self.angle as f32).to_radians().sin_cos();
+            let half_length = (width * cos.abs() + height * sin.abs()) / 2.0;
+
+            let properties = D2D1_LINEAR_GRADIENT_BRUSH_PROPERTIES {
+                startPoint: D2D_POINT_2F {
+                    x: width / 2.0 - cos * half_length,
+                    y: height / 2.0 - sin * half_length,
+                },
+                endPoint: D2D_POINT_2F {
+                    x: width / 2.0 + cos * half_length,
+                    y: height / 2.0 + sin * half_length,
+                },
+            };
+
+            render_target
+                .CreateLinearGradientBrush(&properties, Some(brush_properties), &stop_collection)?
+                .cast()
+        }
+    }
+}
+
+impl Lerp for BorderPaint {
+    #[allow(clippy::cast_precision_loss)]
+    fn lerp(self, end: BorderPaint, time: f64, style: AnimationStyle) -> BorderPaint {
+        // gradients with a different number of colours are compared at the same positions
+        let stops = self.colours.len().max(end.colours.len());
+        let colours = (0..stops)
+            .map(|i| {
+                let position = if stops > 1 {
+                    i as f64 / (stops - 1) as f64
+                } else {
+                    0.0
+                };
+
+                self.colour_at(position)
+                    .lerp(end.colour_at(position), time, style)
+            })
+            .collect();
+
+        // a solid colour has no direction, so it takes the direction of the gradient
+        let start_angle = if self.colours.len() > 1 {
+            self.angle
+        } else {
+            end.angle
+        };
+        let end_angle = if end.colours.len() > 1 {
+            end.angle
+        } else {
+            start_angle
+        };
+
+        // turn the shortest way around
+        let turn = (end_angle - start_angle + 540.0).rem_euclid(360.0) - 180.0;
+
+        Self {
+            colours,
+            angle: start_angle.lerp(start_angle + turn, time, style),
+            width: self.width.lerp(end.width, time, style),
+            offset: self.offset.lerp(end.offset, time, style),
+        }
+    }
+}
+
+pub struct BorderRenderDispatcher {
+    border_hwnd: isize,
+    start: BorderPaint,
+    end: BorderPaint,
+    style: AnimationStyle,
+}
+
+impl BorderRenderDispatcher {
+    const PREFIX: AnimationPrefix = AnimationPrefix::Border;
+
+    pub fn new(border_hwnd: isize, start: BorderPaint, end: BorderPaint) -> Self {
+        let style = *ANIMATION_STYLE_PER_ANIMATION
+            .lock()
+            .get(&Self::PREFIX)
+            .unwrap_or(&ANIMATION_STYLE_GLOBAL.lock());
+
+        Self {
+            border_hwnd,
+            start,
+            end,
+            style,
+        }
+    }
+
+    pub fn is_enabled() -> bool {
+        ANIMATION_ENABLED_PER_ANIMATION
+            .lock()
+            .get(&Self::PREFIX)
+            .is_some_and(|enabled| *enabled)
+            || ANIMATION_ENABLED_GLOBAL.load(Ordering::SeqCst)
+    }
+
+    pub fn animate(self) -> Result<()> {
+        let duration = self.style.duration(Duration::from_millis(
+            *ANIMATION_DURATION_PER_ANIMATION
+                .lock()
+                .get(&Self::PREFIX)
+                .unwrap_or(&ANIMATION_DURATION_GLOBAL.load(Ordering::SeqCst)),
+        ));
+
+        AnimationEngine::animate(self, duration)
+    }
+}
+
+impl RenderDispatcher for BorderRenderDispatcher {
+    fn get_animation_key(&self) -> String {
+        new_animation_key(Self::PREFIX, self.border_hwnd.to_string())
+    }
+
+    fn pre_render(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn render(&self, progress: f64, _frame: &mut Frame) -> Result<()> {
+        let paint = self
+            .start
+            .clone()
+            .lerp(self.end.clone(), progress, self.style);
+        ANIMATED_PAINTS.lock().insert(self.border_hwnd, paint);
+
+        // this triggers WM_PAINT in the border window, which draws the animated paint
+        WindowsApi::invalidate_rect(self.border_hwnd, None, false);
+
+        Ok(())
+    }
+
+    fn post_render(&self) -> Result<()> {
+        ANIMATED_PAINTS.lock().remove(&self.border_hwnd);
+        WindowsApi::invalidate_rect(self.border_hwnd, None, false);
+
+        Ok(())
+    }
+}
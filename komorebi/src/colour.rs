@@ -1,3 +1,5 @@
+use color_eyre::eyre::bail;
+use color_eyre::Result;
 use hex_color::HexColor;
 use komorebi_themes::Color32;
 #[cfg(feature = "schemars")]
@@ -132,3 +134,125 @@ impl From<u32> for Rgb {
         }
     }
 }
+
+/// A border colour, which can be a single colour or a gradient
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum BorderColour {
+    /// A single colour
+    Colour(Colour),
+    /// A linear gradient through multiple colours
+    Gradient(Gradient),
+}
+
+impl From<Colour> for BorderColour {
+    fn from(value: Colour) -> Self {
+        Self::Colour(value)
+    }
+}
+
+impl BorderColour {
+    /// Ensures that a gradient has at least one colour to draw
+    pub fn validate(&self) -> Result<()> {
+        if let BorderColour::Gradient(gradient) = self {
+            if gradient.colours.is_empty() {
+                bail!("border gradients need at least one colour");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl From<BorderColour> for Colour {
+    /// The first colour of a gradient, for where only a single colour can be used
+    fn from(value: BorderColour) -> Self {
+        match value {
+            BorderColour::Colour(colour) => colour,
+            BorderColour::Gradient(gradient) => gradient
+                .colours
+                .first()
+                .copied()
+                .unwrap_or(Colour::Rgb(Rgb::new(0, 0, 0))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Gradient {
+    /// Colours of the gradient, spread evenly from its start to its end
+    pub colours: Vec<Colour>,
+    /// Direction of the gradient in degrees clockwise, where 0 runs from left to right (default: 0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub angle: Option<f32>,
+}
+
+/// A colour in the OKLab colour space, where interpolating between two colours looks even to the
+/// eye
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Oklab {
+    pub l: f64,
+    pub a: f64,
+    pub b: f64,
+}
+
+impl From<Rgb> for Oklab {
+    fn from(value: Rgb) -> Self {
+        let linear = |channel: u32| {
+            let channel = f64::from(channel.min(255)) / 255.0;
+            if channel <= 0.04045 {
+                channel / 12.92
+            } else {
+                ((channel + 0.055) / 1.055).powf(2.4)
+            }
+        };
+
+        let (r, g, b) = (linear(value.r), linear(value.g), linear(value.b));
+
+        let l = (0.412_221_470_8 * r + 0.536_332_536_3 * g + 0.051_445_992_9 * b).cbrt();
+        let m = (0.211_903_498_2 * r + 0.680_699_545_1 * g + 0.107_396_956_6 * b).cbrt();
+        let s = (0.088_302_461_9 * r + 0.281_718_837_6 * g + 0.629_978_700_5 * b).cbrt();
+
+        Self {
+            l: 0.210_454_255_3 * l + 0.793_617_785 * m - 0.004_072_046_8 * s,
+            a: 1.977_998_495_1 * l - 2.428_592_205 * m + 0.450_593_709_9 * s,
+            b: 0.025_904_037_1 * l + 0.782_771_766_2 * m - 0.808_675_766 * s,
+        }
+    }
+}
+
+impl From<Oklab> for Rgb {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn from(value: Oklab) -> Self {
+        let l = (value.l + 0.396_337_777_4 * value.a + 0.215_803_757_3 * value.b).powi(3);
+        let m = (value.l - 0.105_561_345_8 * value.a - 0.063_854_172_8 * value.b).powi(3);
+        let s = (value.l - 0.089_484_177_5 * value.a - 1.291_485_548 * value.b).powi(3);
+
+        let srgb = |channel: f64| {
+            let channel = if channel <= 0.003_130_8 {
+                12.92 * channel
+            } else {
+                1.055 * channel.powf(1.0 / 2.4) - 0.055
+            };
+
+            (channel.clamp(0.0, 1.0) * 255.0).round() as u32
+        };
+
+        Self {
+            r: srgb(4.076_741_662_1 * l - 3.307_711_591_3 * m + 0.230_969_929_2 * s),
+            g: srgb(-1.268_438_004_6 * l + 2.609_757_401_1 * m - 0.341_319_396_5 * s),
+            b: srgb(-0.004_196_086_3 * l - 0.703_418_614_7 * m + 1.707_614_701 * s),
+        }
+    }
+}
+
+impl From<Colour> for Rgb {
+    fn from(value: Colour) -> Self {
+        match value {
+            Colour::Rgb(rgb) => rgb,
+            Colour::Hex(hex) => Rgb::from(hex),
+        }
+    }
+}
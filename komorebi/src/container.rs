@@ -139,4 +139,22 @@ impl Container {
         tracing::info!("focusing window");
         self.windows.focus(idx);
     }
+
+    /// Moves the window at index `from` to index `to`, shifting the windows in between, while
+    /// keeping the same window focused
+    pub fn move_window(&mut self, from: usize, to: usize) {
+        if from == to || to >= self.windows().len() {
+            return;
+        }
+
+        let focused_hwnd = self.focused_window().map(|window| window.hwnd);
+
+        if let Some(window) = self.windows_mut().remove(from) {
+            self.windows_mut().insert(to, window);
+        }
+
+        if let Some(idx) = focused_hwnd.and_then(|hwnd| self.idx_for_window(hwnd)) {
+            self.focus_window(idx);
+        }
+    }
 }
@@ -1,11 +1,12 @@
-use clap::ValueEnum;
+use std::str::FromStr;
 
 use serde::Deserialize;
 use serde::Serialize;
 use strum::Display;
-use strum::EnumString;
+use strum::EnumIter;
+use strum::IntoEnumIterator;
 
-#[derive(Copy, Clone, Debug, Serialize, Deserialize, Display, EnumString, ValueEnum, PartialEq)]
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, Display, EnumIter, PartialEq)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum AnimationStyle {
     Linear,
@@ -16,6 +17,7 @@ pub enum AnimationStyle {
     EaseOutQuad,
     EaseInOutQuad,
     EaseInCubic,
+    EaseOutCubic,
     EaseInOutCubic,
     EaseInQuart,
     EaseOutQuart,
@@ -38,4 +40,93 @@ pub enum AnimationStyle {
     EaseInBounce,
     EaseOutBounce,
     EaseInOutBounce,
+    /// A CSS-style cubic bezier curve with the control points (x1, y1) and (x2, y2)
+    CubicBezier(f64, f64, f64, f64),
+    /// A spring simulation, which determines the duration of the animation and keeps the current
+    /// velocity when an animation is interrupted
+    Spring(Spring),
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct Spring {
+    /// How strongly the spring pulls towards the target (default: 170.0)
+    pub stiffness: f64,
+    /// How strongly the spring resists motion, where lower values bounce more (default: 26.0)
+    pub damping: f64,
+    /// Mass of the object on the spring, where higher values move more slowly (default: 1.0)
+    pub mass: f64,
+}
+
+impl Default for Spring {
+    fn default() -> Self {
+        Self {
+            stiffness: 170.0,
+            damping: 26.0,
+            mass: 1.0,
+        }
+    }
+}
+
+impl FromStr for AnimationStyle {
+    type Err = String;
+
+    /// Parses a named easing ("EaseOutCubic", "ease-out-cubic"), "cubic-bezier(x1, y1, x2, y2)"
+    /// or "spring(stiffness, damping, mass)"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalize = |s: &str| s.replace(['-', '_', ' '], "").to_lowercase();
+
+        let (name, arguments) = match s.trim().split_once('(') {
+            Some((name, rest)) => {
+                let arguments = rest
+                    .strip_suffix(')')
+                    .ok_or_else(|| format!("missing closing parenthesis: {s}"))?
+                    .split(',')
+                    .map(|argument| {
+                        argument
+                            .trim()
+                            .parse::<f64>()
+                            .map_err(|_| format!("invalid number '{argument}' in {s}"))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                (normalize(name), Some(arguments))
+            }
+            None => (normalize(s), None),
+        };
+
+        match (name.as_str(), arguments.as_deref()) {
+            ("cubicbezier", Some([x1, y1, x2, y2])) => {
+                return Ok(AnimationStyle::CubicBezier(*x1, *y1, *x2, *y2));
+            }
+            ("cubicbezier", _) => return Err(format!("cubic-bezier takes four numbers: {s}")),
+            ("spring", None) => return Ok(AnimationStyle::Spring(Spring::default())),
+            ("spring", Some([stiffness, damping, mass])) => {
+                return Ok(AnimationStyle::Spring(Spring {
+                    stiffness: *stiffness,
+                    damping: *damping,
+                    mass: *mass,
+                }));
+            }
+            ("spring", _) => {
+                return Err(format!(
+                    "spring takes three numbers (stiffness, damping, mass): {s}"
+                ))
+            }
+            (_, Some(_)) => return Err(format!("unknown animation style: {s}")),
+            (_, None) => {}
+        }
+
+        // the parameterised styles have been handled above
+        AnimationStyle::iter()
+            .filter(|style| {
+                !matches!(
+                    style,
+                    AnimationStyle::CubicBezier(..) | AnimationStyle::Spring(..)
+                )
+            })
+            .find(|style| normalize(&style.to_string()) == name)
+            .ok_or_else(|| format!("unknown animation style: {s}"))
+    }
 }
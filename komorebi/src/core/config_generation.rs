@@ -56,6 +56,47 @@ impl ApplicationOptions {
 pub enum MatchingRule {
     Simple(IdWithIdentifier),
     Composite(Vec<IdWithIdentifier>),
+    /// Matches if any of the nested rules match
+    Any {
+        any: Vec<MatchingRule>,
+    },
+    /// Matches if all of the nested rules match
+    All {
+        all: Vec<MatchingRule>,
+    },
+    /// Matches if the nested rule does not match
+    Not {
+        not: Box<MatchingRule>,
+    },
+}
+
+impl MatchingRule {
+    /// All of the individual identifiers used by this rule, including those in nested rules
+    #[must_use]
+    pub fn identifiers(&self) -> Vec<&IdWithIdentifier> {
+        match self {
+            MatchingRule::Simple(identifier) => vec![identifier],
+            MatchingRule::Composite(identifiers) => identifiers.iter().collect(),
+            MatchingRule::Any { any: rules } | MatchingRule::All { all: rules } => {
+                rules.iter().flat_map(MatchingRule::identifiers).collect()
+            }
+            MatchingRule::Not { not } => not.identifiers(),
+        }
+    }
+
+    /// Mutable references to all of the individual identifiers used by this rule, including
+    /// those in nested rules
+    pub fn identifiers_mut(&mut self) -> Vec<&mut IdWithIdentifier> {
+        match self {
+            MatchingRule::Simple(identifier) => vec![identifier],
+            MatchingRule::Composite(identifiers) => identifiers.iter_mut().collect(),
+            MatchingRule::Any { any: rules } | MatchingRule::All { all: rules } => rules
+                .iter_mut()
+                .flat_map(MatchingRule::identifiers_mut)
+                .collect(),
+            MatchingRule::Not { not } => not.identifiers_mut(),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -67,6 +108,40 @@ pub struct WorkspaceMatchingRule {
     pub initial_only: bool,
 }
 
+/// A workspace which is looked up when a workspace rule is enforced rather than when it is loaded
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum WorkspaceTarget {
+    /// The workspace with this name, on whichever monitor it is currently loaded
+    Named(String),
+    /// The workspace at this index on the monitor with this serial number id or device id
+    Monitor { id: String, workspace: usize },
+    /// The first workspace without any windows, optionally restricted to the monitor with this
+    /// serial number id or device id
+    FirstEmpty {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        monitor: Option<String>,
+    },
+    /// The workspace with the fewest containers and floating windows, optionally restricted to
+    /// the monitor with this serial number id or device id
+    FewestContainers {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        monitor: Option<String>,
+    },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct WorkspaceTargetRule {
+    /// The workspace that matching windows should be moved to
+    pub target: WorkspaceTarget,
+    /// Rule identifying the windows which should be moved
+    pub matching_rule: MatchingRule,
+    /// Only move matching windows the first time they are seen
+    #[serde(default)]
+    pub initial_only: bool,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct IdWithIdentifier {
@@ -89,6 +164,26 @@ pub enum MatchingStrategy {
     DoesNotStartWith,
     DoesNotEqual,
     DoesNotContain,
+    /// Numeric comparison, for use with the Width and Height identifiers
+    GreaterThan,
+    /// Numeric comparison, for use with the Width and Height identifiers
+    LessThan,
+}
+
+impl MatchingStrategy {
+    /// Negated strategies must hold for every value of a multi-valued identifier (eg. the
+    /// names of the style bits set on a window), while all other strategies only need to hold
+    /// for one of them
+    #[must_use]
+    pub const fn is_negated(&self) -> bool {
+        matches!(
+            self,
+            MatchingStrategy::DoesNotEndWith
+                | MatchingStrategy::DoesNotStartWith
+                | MatchingStrategy::DoesNotEqual
+                | MatchingStrategy::DoesNotContain
+        )
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -128,7 +223,15 @@ impl ApplicationConfiguration {
     pub fn populate_default_matching_strategies(&mut self) {
         if self.identifier.matching_strategy.is_none() {
             match self.identifier.kind {
-                ApplicationIdentifier::Exe | ApplicationIdentifier::Path => {
+                ApplicationIdentifier::Exe
+                | ApplicationIdentifier::Path
+                | ApplicationIdentifier::CommandLine
+                | ApplicationIdentifier::ParentProcess
+                | ApplicationIdentifier::Monitor
+                | ApplicationIdentifier::WindowStyle
+                | ApplicationIdentifier::ExtendedWindowStyle
+                | ApplicationIdentifier::Width
+                | ApplicationIdentifier::Height => {
                     self.identifier.matching_strategy = Option::from(MatchingStrategy::Equals);
                 }
                 ApplicationIdentifier::Class | ApplicationIdentifier::Title => {}
@@ -16,6 +16,7 @@ use strum::EnumString;
 use crate::animation::prefix::AnimationPrefix;
 use crate::KomorebiTheme;
 pub use animation::AnimationStyle;
+pub use animation::Spring;
 pub use arrangement::Arrangement;
 pub use arrangement::Axis;
 pub use custom_layout::Column;
@@ -58,6 +59,9 @@ pub enum SocketMessage {
     CycleStack(CycleDirection),
     CycleStackIndex(CycleDirection),
     FocusStackWindow(usize),
+    /// Moves the window at the first index of the stack with the given container id to the second
+    /// index, which is how stackbar tabs are reordered by dragging them
+    MoveStackWindow(String, usize, usize),
     StackAll,
     UnstackAll,
     ResizeWindowEdge(OperationDirection, Sizing),
@@ -219,6 +223,7 @@ pub enum SocketMessage {
     RemoveSubscriberSocket(String),
     AddSubscriberPipe(String),
     RemoveSubscriberPipe(String),
+    PublishNotification(String),
     ApplicationSpecificConfigurationSchema,
     NotificationSchema,
     SocketSchema,
@@ -325,7 +330,7 @@ pub enum StateQuery {
 }
 
 #[derive(
-    Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Display, EnumString, ValueEnum,
+    Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, Display, EnumString, ValueEnum,
 )]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum ApplicationIdentifier {
@@ -337,6 +342,27 @@ pub enum ApplicationIdentifier {
     Title,
     #[serde(alias = "path")]
     Path,
+    /// The full command line of the process which owns the window
+    #[serde(alias = "command_line")]
+    CommandLine,
+    /// The executable name of the parent of the process which owns the window
+    #[serde(alias = "parent_process")]
+    ParentProcess,
+    /// The name, device, device id or serial number id of the monitor the window is on
+    #[serde(alias = "monitor")]
+    Monitor,
+    /// The names of the window style bits set on the window (eg. CAPTION, THICKFRAME)
+    #[serde(alias = "window_style")]
+    WindowStyle,
+    /// The names of the extended window style bits set on the window (eg. TOOLWINDOW, LAYERED)
+    #[serde(alias = "extended_window_style")]
+    ExtendedWindowStyle,
+    /// The width of the window in pixels
+    #[serde(alias = "width")]
+    Width,
+    /// The height of the window in pixels
+    #[serde(alias = "height")]
+    Height,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize, Display, EnumString, ValueEnum)]
@@ -0,0 +1,434 @@
+use crate::core::asc::ApplicationSpecificConfiguration;
+use crate::core::asc::AscApplicationRules;
+use crate::core::asc::AscApplicationRulesOrSchema;
+use crate::core::config_generation::ApplicationConfigurationGenerator;
+use crate::core::config_generation::MatchingRule;
+use crate::core::config_generation::MatchingStrategy;
+use crate::static_config::StaticConfig;
+use crate::window::matches_rule;
+use crate::window::RuleTarget;
+use crate::FLOATING_APPLICATIONS;
+use crate::IGNORE_IDENTIFIERS;
+use crate::LAYERED_WHITELIST;
+use crate::MANAGE_IDENTIFIERS;
+use crate::NO_TITLEBAR;
+use crate::OBJECT_NAME_CHANGE_ON_LAUNCH;
+use crate::SLOW_APPLICATION_IDENTIFIERS;
+use crate::TRANSPARENCY_BLACKLIST;
+use crate::TRAY_AND_MULTI_WINDOW_IDENTIFIERS;
+use color_eyre::Result;
+use regex::Regex;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use strum::Display;
+
+/// The rule lists that komorebi evaluates windows against
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Display)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum RuleList {
+    Ignore,
+    Manage,
+    Floating,
+    FloatingPlacement,
+    Layered,
+    TrayAndMultiWindow,
+    ObjectNameChange,
+    TransparencyIgnore,
+    SlowApplication,
+    RemoveTitlebar,
+    Workspace,
+    InitialWorkspace,
+    WorkspaceTarget,
+}
+
+/// Where an evaluated rule was defined
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", content = "content")]
+pub enum RuleSource {
+    /// A default rule built into komorebi
+    BuiltIn,
+    /// A rule in komorebi.json, identified by its location in the file (eg. `ignore_rules[2]`)
+    StaticConfig(String),
+    /// A rule in the named entry of the application specific configuration file
+    ApplicationSpecificConfiguration(String),
+}
+
+impl Display for RuleSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuleSource::BuiltIn => write!(f, "built-in"),
+            RuleSource::StaticConfig(location) => write!(f, "komorebi.json {location}"),
+            RuleSource::ApplicationSpecificConfiguration(name) => {
+                write!(f, "applications.json \"{name}\"")
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RuleEvaluation {
+    pub list: RuleList,
+    pub source: RuleSource,
+    pub rule: MatchingRule,
+    pub matched: bool,
+    /// Whether this is the rule komorebi acts on for this list, ie. the first match
+    pub decisive: bool,
+}
+
+/// A trace of every rule evaluated against a window, in the order komorebi evaluates them
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Explanation {
+    pub title: String,
+    pub exe: String,
+    pub class: String,
+    pub path: String,
+    pub evaluations: Vec<RuleEvaluation>,
+}
+
+impl Explanation {
+    /// The rule komorebi acts on for the given list, if any
+    pub fn decisive(&self, list: RuleList) -> Option<&RuleEvaluation> {
+        self.evaluations
+            .iter()
+            .find(|evaluation| evaluation.list == list && evaluation.decisive)
+    }
+
+    pub fn decisions(&self) -> Vec<String> {
+        let mut decisions = vec![];
+
+        let ignore = self.decisive(RuleList::Ignore);
+        let manage = self.decisive(RuleList::Manage);
+
+        match (ignore, manage) {
+            (Some(ignore), None) => {
+                decisions.push(format!("ignored by {} rule", ignore.source));
+            }
+            (Some(ignore), Some(manage)) => decisions.push(format!(
+                "ignored by {} rule, but force-managed by {} rule",
+                ignore.source, manage.source
+            )),
+            (None, Some(manage)) => {
+                decisions.push(format!("force-managed by {} rule", manage.source));
+            }
+            (None, None) => {}
+        }
+
+        for (list, description) in [
+            (RuleList::Floating, "managed as a floating window"),
+            (
+                RuleList::FloatingPlacement,
+                "positioned as a floating window",
+            ),
+            (RuleList::Layered, "allowed to have WS_EX_LAYERED"),
+            (
+                RuleList::TrayAndMultiWindow,
+                "treated as a tray or multi-window application",
+            ),
+            (
+                RuleList::ObjectNameChange,
+                "handled on EVENT_OBJECT_NAMECHANGE",
+            ),
+            (RuleList::TransparencyIgnore, "excluded from transparency"),
+            (
+                RuleList::SlowApplication,
+                "given slow application compensation",
+            ),
+            (RuleList::RemoveTitlebar, "having its title bar removed"),
+        ] {
+            if let Some(evaluation) = self.decisive(list) {
+                decisions.push(format!("{description} by {} rule", evaluation.source));
+            }
+        }
+
+        for list in [RuleList::InitialWorkspace, RuleList::Workspace] {
+            for evaluation in self
+                .evaluations
+                .iter()
+                .filter(|evaluation| evaluation.list == list && evaluation.matched)
+            {
+                decisions.push(format!("moved by {list} {} rule", evaluation.source));
+            }
+        }
+
+        if let Some(evaluation) = self.decisive(RuleList::WorkspaceTarget) {
+            decisions.push(format!(
+                "moved by {} {} rule",
+                RuleList::WorkspaceTarget,
+                evaluation.source
+            ));
+        }
+
+        decisions
+    }
+}
+
+impl Display for Explanation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Window (title: {}, exe: {}, class: {}, path: {})",
+            self.title, self.exe, self.class, self.path
+        )?;
+
+        let mut current = None;
+        for evaluation in &self.evaluations {
+            if current != Some(evaluation.list) {
+                current = Some(evaluation.list);
+                writeln!(f, "\n{}", evaluation.list)?;
+            }
+
+            let marker = match (evaluation.matched, evaluation.decisive) {
+                (true, true) => "=>",
+                (true, false) => " +",
+                (false, _) => " -",
+            };
+
+            writeln!(
+                f,
+                "  {marker} {}: {}",
+                evaluation.source,
+                serde_json::to_string(&evaluation.rule).unwrap_or_default()
+            )?;
+        }
+
+        let decisions = self.decisions();
+        writeln!(f)?;
+        if decisions.is_empty() {
+            writeln!(f, "No rules matched this window")?;
+        } else {
+            for decision in decisions {
+                writeln!(f, "This window is {decision}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Evaluates every rule list from the given static configuration (and the application specific
+/// configuration file it references) against the target
+///
+/// The built-in rules are taken from the rule lists of the calling process, so this should be
+/// called from outside of komorebi itself (eg. komorebic) where those lists have not been
+/// populated from a configuration file.
+pub fn explain(config: &StaticConfig, target: &RuleTarget) -> Result<Explanation> {
+    let mut rules: Vec<(RuleList, RuleSource, MatchingRule)> = vec![];
+
+    for (list, built_in) in [
+        (RuleList::Ignore, IGNORE_IDENTIFIERS.lock().to_vec()),
+        (RuleList::Manage, MANAGE_IDENTIFIERS.lock().to_vec()),
+        (RuleList::Floating, FLOATING_APPLICATIONS.lock().to_vec()),
+        (RuleList::Layered, LAYERED_WHITELIST.lock().to_vec()),
+        (
+            RuleList::TrayAndMultiWindow,
+            TRAY_AND_MULTI_WINDOW_IDENTIFIERS.lock().to_vec(),
+        ),
+        (
+            RuleList::ObjectNameChange,
+            OBJECT_NAME_CHANGE_ON_LAUNCH.lock().to_vec(),
+        ),
+        (
+            RuleList::TransparencyIgnore,
+            TRANSPARENCY_BLACKLIST.lock().to_vec(),
+        ),
+        (
+            RuleList::SlowApplication,
+            SLOW_APPLICATION_IDENTIFIERS.lock().to_vec(),
+        ),
+        (RuleList::RemoveTitlebar, NO_TITLEBAR.lock().to_vec()),
+    ] {
+        for rule in built_in {
+            rules.push((list, RuleSource::BuiltIn, rule));
+        }
+    }
+
+    for (list, name, config_rules) in [
+        (RuleList::Ignore, "ignore_rules", &config.ignore_rules),
+        (RuleList::Manage, "manage_rules", &config.manage_rules),
+        (
+            RuleList::Floating,
+            "floating_applications",
+            &config.floating_applications,
+        ),
+        (
+            RuleList::Layered,
+            "layered_applications",
+            &config.layered_applications,
+        ),
+        (
+            RuleList::TrayAndMultiWindow,
+            "tray_and_multi_window_applications",
+            &config.tray_and_multi_window_applications,
+        ),
+        (
+            RuleList::ObjectNameChange,
+            "object_name_change_applications",
+            &config.object_name_change_applications,
+        ),
+        (
+            RuleList::TransparencyIgnore,
+            "transparency_ignore_rules",
+            &config.transparency_ignore_rules,
+        ),
+        (
+            RuleList::SlowApplication,
+            "slow_application_identifiers",
+            &config.slow_application_identifiers,
+        ),
+        (
+            RuleList::RemoveTitlebar,
+            "remove_titlebar_applications",
+            &config.remove_titlebar_applications,
+        ),
+    ] {
+        for (idx, rule) in config_rules.iter().flatten().enumerate() {
+            rules.push((
+                list,
+                RuleSource::StaticConfig(format!("{name}[{idx}]")),
+                rule.clone(),
+            ));
+        }
+    }
+
+    if let Some(path) = &config.app_specific_configuration_path {
+        let asc = match path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_string())
+        {
+            Some(ext) if ext == "yaml" => {
+                let content = std::fs::read_to_string(path)?;
+                Some(ApplicationSpecificConfiguration::from(
+                    ApplicationConfigurationGenerator::load(&content)?,
+                ))
+            }
+            Some(ext) if ext == "json" => Some(ApplicationSpecificConfiguration::load(path)?),
+            _ => None,
+        };
+
+        for (name, entry) in asc.iter().flat_map(|asc| asc.iter()) {
+            let AscApplicationRulesOrSchema::AscApplicationRules(entry) = entry else {
+                continue;
+            };
+
+            let AscApplicationRules {
+                ignore,
+                manage,
+                floating,
+                transparency_ignore,
+                tray_and_multi_window,
+                layered,
+                object_name_change,
+                slow_application,
+            } = entry;
+
+            for (list, asc_rules) in [
+                (RuleList::Ignore, ignore),
+                (RuleList::Manage, manage),
+                (RuleList::Floating, floating),
+                (RuleList::TransparencyIgnore, transparency_ignore),
+                (RuleList::TrayAndMultiWindow, tray_and_multi_window),
+                (RuleList::Layered, layered),
+                (RuleList::ObjectNameChange, object_name_change),
+                (RuleList::SlowApplication, slow_application),
+            ] {
+                for rule in asc_rules.iter().flatten() {
+                    rules.push((
+                        list,
+                        RuleSource::ApplicationSpecificConfiguration(name.clone()),
+                        rule.clone(),
+                    ));
+                }
+            }
+        }
+    }
+
+    for (i, monitor) in config.monitors.iter().flatten().enumerate() {
+        for (j, workspace) in monitor.workspaces.iter().enumerate() {
+            for (list, name, workspace_rules) in [
+                (
+                    RuleList::InitialWorkspace,
+                    "initial_workspace_rules",
+                    &workspace.initial_workspace_rules,
+                ),
+                (
+                    RuleList::Workspace,
+                    "workspace_rules",
+                    &workspace.workspace_rules,
+                ),
+            ] {
+                for (idx, rule) in workspace_rules.iter().flatten().enumerate() {
+                    rules.push((
+                        list,
+                        RuleSource::StaticConfig(format!(
+                            "monitors[{i}].workspaces[{j}].{name}[{idx}]"
+                        )),
+                        rule.clone(),
+                    ));
+                }
+            }
+        }
+    }
+
+    for (idx, placement_rule) in config
+        .floating_window_placement_rules
+        .iter()
+        .flatten()
+        .enumerate()
+    {
+        rules.push((
+            RuleList::FloatingPlacement,
+            RuleSource::StaticConfig(format!("floating_window_placement_rules[{idx}]")),
+            placement_rule.matching_rule.clone(),
+        ));
+    }
+
+    for (idx, target_rule) in config.workspace_target_rules.iter().flatten().enumerate() {
+        rules.push((
+            RuleList::WorkspaceTarget,
+            RuleSource::StaticConfig(format!("workspace_target_rules[{idx}]")),
+            target_rule.matching_rule.clone(),
+        ));
+    }
+
+    // keep each list together in the output while preserving the order within each list
+    rules.sort_by_key(|(list, _, _)| *list as usize);
+
+    let mut regex_identifiers = HashMap::new();
+    for (_, _, rule) in &rules {
+        for identifier in rule.identifiers() {
+            if matches!(identifier.matching_strategy, Some(MatchingStrategy::Regex)) {
+                regex_identifiers.insert(identifier.id.clone(), Regex::new(&identifier.id)?);
+            }
+        }
+    }
+
+    let mut evaluations: Vec<RuleEvaluation> = vec![];
+    for (list, source, rule) in rules {
+        let matched = matches_rule(target, &rule, &regex_identifiers);
+        let decisive = matched
+            && !matches!(list, RuleList::Workspace | RuleList::InitialWorkspace)
+            && !evaluations
+                .iter()
+                .any(|evaluation| evaluation.list == list && evaluation.decisive);
+
+        evaluations.push(RuleEvaluation {
+            list,
+            source,
+            rule,
+            matched,
+            decisive,
+        });
+    }
+
+    Ok(Explanation {
+        title: target.title.clone(),
+        exe: target.exe.clone(),
+        class: target.class.clone(),
+        path: target.path.clone(),
+        evaluations,
+    })
+}
@@ -8,6 +8,7 @@ pub mod ring;
 pub mod colour;
 pub mod container;
 pub mod core;
+pub mod explain;
 pub mod focus_manager;
 pub mod monitor;
 pub mod monitor_reconciliator;
@@ -15,6 +16,7 @@ pub mod process_command;
 pub mod process_event;
 pub mod process_movement;
 pub mod reaper;
+pub mod rule_index;
 pub mod set_window_position;
 pub mod stackbar_manager;
 pub mod static_config;
@@ -22,6 +24,7 @@ pub mod styles;
 pub mod theme_manager;
 pub mod transparency_manager;
 pub mod window;
+pub mod window_label;
 pub mod window_manager;
 pub mod window_manager_event;
 pub mod windows_api;
@@ -62,6 +65,8 @@ use crate::core::config_generation::IdWithIdentifier;
 use crate::core::config_generation::MatchingRule;
 use crate::core::config_generation::MatchingStrategy;
 use crate::core::config_generation::WorkspaceMatchingRule;
+use crate::core::config_generation::WorkspaceTargetRule;
+use crate::rule_index::RuleIndex;
 use color_eyre::Result;
 use os_info::Version;
 use parking_lot::Mutex;
@@ -75,15 +80,15 @@ use winreg::RegKey;
 
 lazy_static! {
     static ref HIDDEN_HWNDS: Arc<Mutex<Vec<isize>>> = Arc::new(Mutex::new(vec![]));
-    static ref LAYERED_WHITELIST: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(vec![
+    static ref LAYERED_WHITELIST: Arc<Mutex<RuleIndex>> = Arc::new(Mutex::new(RuleIndex::from(vec![
         MatchingRule::Simple(IdWithIdentifier {
             kind: ApplicationIdentifier::Exe,
             id: String::from("steam.exe"),
             matching_strategy: Option::from(MatchingStrategy::Equals),
         }),
-    ]));
-    static ref TRAY_AND_MULTI_WINDOW_IDENTIFIERS: Arc<Mutex<Vec<MatchingRule>>> =
-        Arc::new(Mutex::new(vec![
+    ])));
+    static ref TRAY_AND_MULTI_WINDOW_IDENTIFIERS: Arc<Mutex<RuleIndex>> =
+        Arc::new(Mutex::new(RuleIndex::from(vec![
             MatchingRule::Simple(IdWithIdentifier {
                 kind: ApplicationIdentifier::Exe,
                 id: String::from("explorer.exe"),
@@ -114,8 +119,8 @@ lazy_static! {
                 id: String::from("steam.exe"),
                 matching_strategy: Option::from(MatchingStrategy::Equals),
             })
-        ]));
-    static ref OBJECT_NAME_CHANGE_ON_LAUNCH: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(vec![
+        ])));
+    static ref OBJECT_NAME_CHANGE_ON_LAUNCH: Arc<Mutex<RuleIndex>> = Arc::new(Mutex::new(RuleIndex::from(vec![
         MatchingRule::Simple(IdWithIdentifier {
             kind: ApplicationIdentifier::Exe,
             id: String::from("firefox.exe"),
@@ -126,19 +131,21 @@ lazy_static! {
             id: String::from("idea64.exe"),
             matching_strategy: Option::from(MatchingStrategy::Equals),
         }),
-    ]));
+    ])));
     static ref OBJECT_NAME_CHANGE_TITLE_IGNORE_LIST: Arc<Mutex<Vec<Regex>>> = Arc::new(Mutex::new(Vec::new()));
-    static ref TRANSPARENCY_BLACKLIST: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(Vec::new()));
+    static ref TRANSPARENCY_BLACKLIST: Arc<Mutex<RuleIndex>> = Arc::new(Mutex::new(RuleIndex::default()));
     static ref MONITOR_INDEX_PREFERENCES: Arc<Mutex<HashMap<usize, Rect>>> =
         Arc::new(Mutex::new(HashMap::new()));
     static ref DISPLAY_INDEX_PREFERENCES: Arc<Mutex<HashMap<usize, String>>> =
         Arc::new(Mutex::new(HashMap::new()));
     static ref WORKSPACE_MATCHING_RULES: Arc<Mutex<Vec<WorkspaceMatchingRule>>> =
         Arc::new(Mutex::new(Vec::new()));
+    static ref WORKSPACE_TARGET_RULES: Arc<Mutex<Vec<WorkspaceTargetRule>>> =
+        Arc::new(Mutex::new(Vec::new()));
     static ref REGEX_IDENTIFIERS: Arc<Mutex<HashMap<String, Regex>>> =
         Arc::new(Mutex::new(HashMap::new()));
-    static ref MANAGE_IDENTIFIERS: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(vec![]));
-    static ref IGNORE_IDENTIFIERS: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(vec![
+    static ref MANAGE_IDENTIFIERS: Arc<Mutex<RuleIndex>> = Arc::new(Mutex::new(RuleIndex::default()));
+    static ref IGNORE_IDENTIFIERS: Arc<Mutex<RuleIndex>> = Arc::new(Mutex::new(RuleIndex::from(vec![
         // mstsc.exe creates these on Windows 11 when a WSL process is launched
         // https://github.com/LGUG2Z/komorebi/issues/74
         MatchingRule::Simple(IdWithIdentifier {
@@ -156,8 +163,8 @@ lazy_static! {
             id: String::from("komorebi-bar.exe"),
             matching_strategy: Option::from(MatchingStrategy::Equals),
         })
-    ]));
-    static ref FLOATING_APPLICATIONS: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(Vec::new()));
+    ])));
+    static ref FLOATING_APPLICATIONS: Arc<Mutex<RuleIndex>> = Arc::new(Mutex::new(RuleIndex::default()));
     static ref PERMAIGNORE_CLASSES: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(vec![
         "Chrome_RenderWidgetHostHWND".to_string(),
     ]));
@@ -165,13 +172,13 @@ lazy_static! {
         "X410.exe".to_string(),
         "vcxsrv.exe".to_string(),
     ]));
-    static ref SLOW_APPLICATION_IDENTIFIERS: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(vec![
+    static ref SLOW_APPLICATION_IDENTIFIERS: Arc<Mutex<RuleIndex>> = Arc::new(Mutex::new(RuleIndex::from(vec![
         MatchingRule::Simple(IdWithIdentifier {
             kind: ApplicationIdentifier::Exe,
             id: String::from("firefox.exe"),
             matching_strategy: Option::from(MatchingStrategy::Equals),
         }),
-    ]));
+    ])));
     static ref SUBSCRIPTION_PIPES: Arc<Mutex<HashMap<String, File>>> =
         Arc::new(Mutex::new(HashMap::new()));
     pub static ref SUBSCRIPTION_SOCKETS: Arc<Mutex<HashMap<String, PathBuf>>> =
@@ -216,11 +223,14 @@ lazy_static! {
 
     // Use app-specific titlebar removal options where possible
     // eg. Windows Terminal, IntelliJ IDEA, Firefox
-    static ref NO_TITLEBAR: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(vec![]));
+    static ref NO_TITLEBAR: Arc<Mutex<RuleIndex>> = Arc::new(Mutex::new(RuleIndex::default()));
 
     static ref WINDOWS_BY_BAR_HWNDS: Arc<Mutex<HashMap<isize, VecDeque<isize>>>> =
         Arc::new(Mutex::new(HashMap::new()));
 
+    static ref FLOATING_WINDOW_PLACEMENT_RULES: Arc<Mutex<Vec<FloatingPlacementRule>>> =
+        Arc::new(Mutex::new(Vec::new()));
+
     static ref FLOATING_WINDOW_TOGGLE_ASPECT_RATIO: Arc<Mutex<AspectRatio>> = Arc::new(Mutex::new(AspectRatio::Predefined(PredefinedAspectRatio::Widescreen)));
 }
 
@@ -301,6 +311,7 @@ pub fn notify_subscribers(notification: Notification, state_has_been_modified: b
         NotificationEvent::Socket(SocketMessage::AddSubscriberSocket(_))
             | NotificationEvent::Socket(SocketMessage::AddSubscriberSocketWithOptions(_, _))
             | NotificationEvent::Socket(SocketMessage::Theme(_))
+            | NotificationEvent::Socket(SocketMessage::PublishNotification(_))
             | NotificationEvent::Socket(SocketMessage::ReloadStaticConfiguration(_))
             | NotificationEvent::WindowManager(WindowManagerEvent::TitleUpdate(_, _))
             | NotificationEvent::WindowManager(WindowManagerEvent::Show(_, _))
@@ -282,6 +282,7 @@ fn main() -> Result<()> {
     reaper::listen_for_notifications(wm.clone(), wm.lock().known_hwnds.clone());
     focus_manager::listen_for_notifications(wm.clone());
     theme_manager::listen_for_notifications();
+    theme_manager::listen_for_schedule();
 
     listen_for_commands(wm.clone());
 
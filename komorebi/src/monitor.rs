@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::collections::VecDeque;
 use std::sync::atomic::Ordering;
+use std::time::Duration;
 
 use color_eyre::eyre::anyhow;
 use color_eyre::eyre::bail;
@@ -14,15 +15,41 @@ use serde::Serialize;
 
 use crate::core::Rect;
 
+use crate::animation::lerp::Lerp;
+use crate::animation::prefix::new_animation_key;
+use crate::animation::prefix::AnimationPrefix;
+use crate::animation::render_dispatcher::Frame;
+use crate::animation::AnimationEngine;
+use crate::animation::RenderDispatcher;
+use crate::animation::ANIMATION_DURATION_GLOBAL;
+use crate::animation::ANIMATION_DURATION_PER_ANIMATION;
+use crate::animation::ANIMATION_ENABLED_PER_ANIMATION;
+use crate::animation::ANIMATION_STYLE_GLOBAL;
+use crate::animation::ANIMATION_STYLE_PER_ANIMATION;
+use crate::animation::WORKSPACE_TRANSITION_EX_STYLES;
+use crate::animation::WORKSPACE_TRANSITION_RECTS;
+use crate::border_manager;
+use crate::border_manager::BorderSettings;
 use crate::container::Container;
 use crate::ring::Ring;
+use crate::stackbar_manager;
+use crate::stackbar_manager::StackbarSettings;
+use crate::static_config::BorderOverrides;
+use crate::static_config::StackbarOverrides;
+use crate::styles::ExtendedWindowStyle;
+use crate::transparency_manager;
+use crate::window::Window;
 use crate::workspace::Workspace;
+use crate::AnimationStyle;
 use crate::DefaultLayout;
+use crate::HidingBehaviour;
 use crate::Layout;
 use crate::OperationDirection;
 use crate::WindowsApi;
 use crate::DEFAULT_CONTAINER_PADDING;
 use crate::DEFAULT_WORKSPACE_PADDING;
+use crate::HIDDEN_HWNDS;
+use crate::HIDING_BEHAVIOUR;
 
 #[derive(
     Debug, Clone, Serialize, Deserialize, Getters, CopyGetters, MutGetters, Setters, PartialEq,
@@ -59,10 +86,276 @@ pub struct Monitor {
     pub container_padding: Option<i32>,
     #[getset(get_copy = "pub", set = "pub")]
     pub workspace_padding: Option<i32>,
+    #[getset(get = "pub", set = "pub")]
+    pub border: Option<BorderOverrides>,
+    #[getset(get = "pub", set = "pub")]
+    pub stackbar: Option<StackbarOverrides>,
 }
 
 impl_ring_elements!(Monitor, Workspace);
 
+struct WorkspaceTransitionRenderDispatcher {
+    prefix: AnimationPrefix,
+    monitor_id: isize,
+    /// Indices of the workspaces being switched away from
+    outgoing_idxs: Vec<usize>,
+    /// Windows of the workspaces being switched away from, and where they rest
+    outgoing: Vec<(Window, Rect)>,
+    /// Windows of the workspace being switched to, and where they rest
+    incoming: Vec<(Window, Rect)>,
+    /// How far the windows slide from the incoming workspace towards the outgoing workspaces
+    offset: (i32, i32),
+    style: AnimationStyle,
+    /// Windows whose resting rects were recorded by this transition rather than an interrupted one
+    recorded_hwnds: Vec<isize>,
+}
+
+/// Forgets the resting rects recorded for a workspace transition which never reaches the
+/// animation engine, so that the windows aren't left skipping their layout updates
+struct TransitionRectsGuard {
+    hwnds: Vec<isize>,
+}
+
+impl TransitionRectsGuard {
+    /// Hands the resting rects over to the animation, which forgets them once it has finished
+    fn release(mut self) {
+        self.hwnds.clear();
+    }
+}
+
+impl Drop for TransitionRectsGuard {
+    fn drop(&mut self) {
+        if self.hwnds.is_empty() {
+            return;
+        }
+
+        let mut resting_rects = WORKSPACE_TRANSITION_RECTS.lock();
+        for hwnd in &self.hwnds {
+            resting_rects.remove(hwnd);
+        }
+    }
+}
+
+impl WorkspaceTransitionRenderDispatcher {
+    pub fn new(
+        prefix: AnimationPrefix,
+        monitor_id: isize,
+        outgoing_idxs: Vec<usize>,
+        outgoing: &[Window],
+        incoming: &[Window],
+        offset: (i32, i32),
+        style: AnimationStyle,
+    ) -> Self {
+        let mut resting_rects = WORKSPACE_TRANSITION_RECTS.lock();
+        let mut recorded_hwnds = vec![];
+
+        // windows which are still moving in an interrupted transition rest where that transition
+        // would have left them
+        let mut resting = |windows: &[Window]| {
+            windows
+                .iter()
+                .filter_map(|window| {
+                    let rect = match resting_rects.get(&window.hwnd) {
+                        Some(rect) => *rect,
+                        None => {
+                            let rect = WindowsApi::window_rect(window.hwnd).ok()?;
+                            resting_rects.insert(window.hwnd, rect);
+                            recorded_hwnds.push(window.hwnd);
+                            rect
+                        }
+                    };
+
+                    Some((*window, rect))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let outgoing = resting(outgoing);
+        let incoming = resting(incoming);
+
+        Self {
+            prefix,
+            monitor_id,
+            outgoing_idxs,
+            outgoing,
+            incoming,
+            offset,
+            style,
+            recorded_hwnds,
+        }
+    }
+
+    fn rects_guard(&self) -> TransitionRectsGuard {
+        TransitionRectsGuard {
+            hwnds: self.recorded_hwnds.clone(),
+        }
+    }
+
+    pub fn is_outgoing(&self, workspace_idx: usize) -> bool {
+        self.outgoing_idxs.contains(&workspace_idx)
+    }
+
+    fn translate(rect: Rect, (x, y): (i32, i32)) -> Rect {
+        Rect {
+            left: rect.left + x,
+            top: rect.top + y,
+            ..rect
+        }
+    }
+
+    /// Makes the window layered so that it can be faded, remembering its extended style unless a
+    /// fade which was interrupted already did
+    fn make_layered(window: Window) -> Result<()> {
+        let ex_style = window.ex_style()?;
+        WORKSPACE_TRANSITION_EX_STYLES
+            .lock()
+            .entry(window.hwnd)
+            .or_insert(ex_style);
+
+        window.update_ex_style(&(ex_style | ExtendedWindowStyle::LAYERED))
+    }
+
+    /// Makes the window opaque and puts back the extended style it had before it was faded, so
+    /// that windows which were layered before the fade stay layered
+    fn restore_ex_style(window: Window) -> Result<()> {
+        let Some(ex_style) = WORKSPACE_TRANSITION_EX_STYLES.lock().remove(&window.hwnd) else {
+            return Ok(());
+        };
+
+        WindowsApi::set_transparent(window.hwnd, 255)?;
+        window.update_ex_style(&ex_style)
+    }
+
+    /// Puts the incoming windows where the transition starts before they are restored
+    pub fn prepare(&self) -> Result<()> {
+        match self.prefix {
+            AnimationPrefix::WorkspaceFade => {
+                for (window, _) in self.outgoing.iter().chain(&self.incoming) {
+                    Self::make_layered(*window)?;
+                }
+
+                for (window, _) in &self.incoming {
+                    WindowsApi::set_transparent(window.hwnd, 0)?;
+                }
+
+                Ok(())
+            }
+            _ => WindowsApi::move_windows(
+                &self
+                    .incoming
+                    .iter()
+                    .map(|(window, rect)| (window.hwnd, Self::translate(*rect, self.offset)))
+                    .collect::<Vec<_>>(),
+            ),
+        }
+    }
+
+    /// Undoes whatever a failed prepare managed to do, so that the workspace can be switched
+    /// without a transition
+    pub fn reset(&self) {
+        let reset = match self.prefix {
+            AnimationPrefix::WorkspaceFade => self
+                .outgoing
+                .iter()
+                .chain(&self.incoming)
+                .try_for_each(|(window, _)| Self::restore_ex_style(*window)),
+            _ => WindowsApi::move_windows(
+                &self
+                    .incoming
+                    .iter()
+                    .map(|(window, rect)| (window.hwnd, *rect))
+                    .collect::<Vec<_>>(),
+            ),
+        };
+
+        if let Err(error) = reset {
+            tracing::error!("could not reset workspace transition: {error}");
+        }
+    }
+}
+
+impl RenderDispatcher for WorkspaceTransitionRenderDispatcher {
+    fn get_animation_key(&self) -> String {
+        new_animation_key(self.prefix, self.monitor_id.to_string())
+    }
+
+    fn pre_render(&self) -> Result<()> {
+        stackbar_manager::STACKBAR_TEMPORARILY_DISABLED.store(true, Ordering::SeqCst);
+        stackbar_manager::send_notification();
+
+        Ok(())
+    }
+
+    fn render(&self, progress: f64, frame: &mut Frame) -> Result<()> {
+        match self.prefix {
+            AnimationPrefix::WorkspaceFade => {
+                let alpha = 0u8.lerp(255, progress, self.style);
+
+                for (window, _) in &self.outgoing {
+                    WindowsApi::set_transparent(window.hwnd, 255 - alpha)?;
+                }
+
+                for (window, _) in &self.incoming {
+                    WindowsApi::set_transparent(window.hwnd, alpha)?;
+                }
+            }
+            _ => {
+                let (x, y) = self.offset;
+
+                for (window, rect) in &self.outgoing {
+                    let target = Self::translate(*rect, (-x, -y));
+                    frame.move_window(window.hwnd, rect.lerp(target, progress, self.style));
+                }
+
+                for (window, rect) in &self.incoming {
+                    let start = Self::translate(*rect, self.offset);
+                    frame.move_window(window.hwnd, start.lerp(*rect, progress, self.style));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn post_render(&self) -> Result<()> {
+        // the resting rects are forgotten first, so that an error below doesn't leave them behind
+        {
+            let mut resting_rects = WORKSPACE_TRANSITION_RECTS.lock();
+            for (window, _) in self.outgoing.iter().chain(&self.incoming) {
+                resting_rects.remove(&window.hwnd);
+            }
+        }
+
+        for (window, _) in &self.outgoing {
+            window.hide();
+        }
+
+        match self.prefix {
+            AnimationPrefix::WorkspaceFade => {
+                for (window, _) in self.outgoing.iter().chain(&self.incoming) {
+                    Self::restore_ex_style(*window)?;
+                }
+            }
+            // put the hidden windows back where they are restored from
+            _ => WindowsApi::move_windows(
+                &self
+                    .outgoing
+                    .iter()
+                    .map(|(window, rect)| (window.hwnd, *rect))
+                    .collect::<Vec<_>>(),
+            )?,
+        }
+
+        stackbar_manager::STACKBAR_TEMPORARILY_DISABLED.store(false, Ordering::SeqCst);
+
+        stackbar_manager::send_notification();
+        border_manager::send_notification(None);
+        transparency_manager::send_notification();
+
+        Ok(())
+    }
+}
+
 #[derive(Serialize)]
 pub struct MonitorInformation {
     pub id: isize,
@@ -114,6 +407,8 @@ pub fn new(
         workspace_names: HashMap::default(),
         container_padding: None,
         workspace_padding: None,
+        border: None,
+        stackbar: None,
     }
 }
 
@@ -155,6 +450,8 @@ impl Monitor {
             workspace_names: Default::default(),
             container_padding: None,
             workspace_padding: None,
+            border: None,
+            stackbar: None,
         }
     }
 
@@ -166,17 +463,156 @@ impl Monitor {
 
     pub fn load_focused_workspace(&mut self, mouse_follows_focus: bool) -> Result<()> {
         let focused_idx = self.focused_workspace_idx();
+        let mut transition = self.workspace_transition(focused_idx);
+        let mut rects_guard = transition
+            .as_ref()
+            .map(|(transition, _)| transition.rects_guard());
+
+        let prepared = transition
+            .as_ref()
+            .map_or(Ok(()), |(transition, _)| transition.prepare());
+
+        if let Err(error) = prepared {
+            tracing::error!("could not prepare workspace transition, switching instantly: {error}");
+
+            if let Some((transition, _)) = transition.take() {
+                transition.reset();
+            }
+
+            // the restored windows must not wait for a transition to move them into place
+            rects_guard = None;
+        }
+
         for (i, workspace) in self.workspaces_mut().iter_mut().enumerate() {
             if i == focused_idx {
                 workspace.restore(mouse_follows_focus)?;
+            } else if transition
+                .as_ref()
+                .is_some_and(|(transition, _)| transition.is_outgoing(i))
+            {
+                // the transition hides the windows once they are out of view
             } else {
                 workspace.hide(None);
             }
         }
 
+        if let Some((transition, duration)) = transition {
+            AnimationEngine::animate(transition, duration)?;
+
+            if let Some(rects_guard) = rects_guard {
+                rects_guard.release();
+            }
+        }
+
         Ok(())
     }
 
+    /// The transition animation from the workspaces which are still visible to the focused
+    /// workspace, if a workspace transition animation is enabled
+    fn workspace_transition(
+        &self,
+        focused_idx: usize,
+    ) -> Option<(WorkspaceTransitionRenderDispatcher, Duration)> {
+        // minimized windows can't be moved into view
+        if matches!(*HIDING_BEHAVIOUR.lock(), HidingBehaviour::Minimize) {
+            return None;
+        }
+
+        let prefix = {
+            let enabled = ANIMATION_ENABLED_PER_ANIMATION.lock();
+            [
+                AnimationPrefix::WorkspaceSlideHorizontal,
+                AnimationPrefix::WorkspaceSlideVertical,
+                AnimationPrefix::WorkspaceFade,
+            ]
+            .into_iter()
+            .find(|prefix| enabled.get(prefix).is_some_and(|enabled| *enabled))?
+        };
+
+        let hidden_hwnds = HIDDEN_HWNDS.lock().clone();
+        let visible = |workspace: &Workspace| {
+            workspace
+                .visible_windows()
+                .into_iter()
+                .flatten()
+                .filter(|window| !hidden_hwnds.contains(&window.hwnd))
+                .copied()
+                .collect::<Vec<_>>()
+        };
+
+        let mut outgoing_idxs = vec![];
+        let mut outgoing = vec![];
+        for (i, workspace) in self.workspaces().iter().enumerate() {
+            if i != focused_idx {
+                let windows = visible(workspace);
+                if !windows.is_empty() {
+                    outgoing_idxs.push(i);
+                    outgoing.extend(windows);
+                }
+            }
+        }
+
+        let from_idx = *outgoing_idxs.first()?;
+        let incoming = self
+            .workspaces()
+            .get(focused_idx)?
+            .visible_windows()
+            .into_iter()
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>();
+
+        // windows move towards the lower index when switching to a higher index
+        let direction = if focused_idx > from_idx { 1 } else { -1 };
+        let offset = match prefix {
+            AnimationPrefix::WorkspaceSlideHorizontal => (direction * self.size().right, 0),
+            AnimationPrefix::WorkspaceSlideVertical => (0, direction * self.size().bottom),
+            _ => (0, 0),
+        };
+
+        let style = *ANIMATION_STYLE_PER_ANIMATION
+            .lock()
+            .get(&prefix)
+            .unwrap_or(&ANIMATION_STYLE_GLOBAL.lock());
+        let duration = style.duration(Duration::from_millis(
+            *ANIMATION_DURATION_PER_ANIMATION
+                .lock()
+                .get(&prefix)
+                .unwrap_or(&ANIMATION_DURATION_GLOBAL.load(Ordering::SeqCst)),
+        ));
+
+        Some((
+            WorkspaceTransitionRenderDispatcher::new(
+                prefix,
+                self.id(),
+                outgoing_idxs,
+                &outgoing,
+                &incoming,
+                offset,
+                style,
+            ),
+            duration,
+        ))
+    }
+
+    /// The scale factor of this monitor along with the border and stackbar settings which its
+    /// workspaces inherit, with the overrides for this monitor applied
+    fn border_and_stackbar_settings(&self) -> (f32, BorderSettings, StackbarSettings) {
+        let scale_factor = WindowsApi::dpi_for_monitor(self.id()).unwrap_or(1.0);
+
+        let mut border = BorderSettings::default();
+        if let Some(overrides) = self.border() {
+            border.apply(overrides, scale_factor);
+        }
+
+        let mut stackbar = StackbarSettings::default();
+        if let Some(overrides) = self.stackbar() {
+            stackbar.apply(overrides, scale_factor);
+        }
+
+        (scale_factor, border, stackbar)
+    }
+
     /// Updates the `globals` field of all workspaces
     pub fn update_workspaces_globals(&mut self, offset: Option<Rect>) {
         let container_padding = self
@@ -189,8 +625,12 @@ impl Monitor {
         let offset = self.work_area_offset.or(offset);
         let window_based_work_area_offset = self.window_based_work_area_offset();
         let limit = self.window_based_work_area_offset_limit();
+        let (scale_factor, border, stackbar) = self.border_and_stackbar_settings();
 
         for workspace in self.workspaces_mut() {
+            workspace.globals_mut().scale_factor = scale_factor;
+            workspace.globals_mut().border = border.clone();
+            workspace.globals_mut().stackbar = stackbar;
             workspace.globals_mut().container_padding = container_padding;
             workspace.globals_mut().workspace_padding = workspace_padding;
             workspace.globals_mut().work_area = work_area;
@@ -212,8 +652,12 @@ impl Monitor {
         let offset = self.work_area_offset.or(offset);
         let window_based_work_area_offset = self.window_based_work_area_offset();
         let limit = self.window_based_work_area_offset_limit();
+        let (scale_factor, border, stackbar) = self.border_and_stackbar_settings();
 
         if let Some(workspace) = self.workspaces_mut().get_mut(workspace_idx) {
+            workspace.globals_mut().scale_factor = scale_factor;
+            workspace.globals_mut().border = border;
+            workspace.globals_mut().stackbar = stackbar;
             workspace.globals_mut().container_padding = container_padding;
             workspace.globals_mut().workspace_padding = workspace_padding;
             workspace.globals_mut().work_area = work_area;
@@ -487,6 +487,8 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                                     workspace_names: cached.workspace_names.clone(),
                                     container_padding: cached.container_padding,
                                     workspace_padding: cached.workspace_padding,
+                                    border: cached.border.clone(),
+                                    stackbar: cached.stackbar.clone(),
                                 };
 
                                 let focused_workspace_idx = m.focused_workspace_idx();
@@ -24,7 +24,6 @@ use crate::animation::ANIMATION_STYLE_PER_ANIMATION;
 use crate::core::config_generation::IdWithIdentifier;
 use crate::core::config_generation::MatchingRule;
 use crate::core::config_generation::MatchingStrategy;
-use crate::core::ApplicationIdentifier;
 use crate::core::Axis;
 use crate::core::BorderImplementation;
 use crate::core::FocusFollowsMouseImplementation;
@@ -36,7 +35,6 @@ use crate::core::Sizing;
 use crate::core::SocketMessage;
 use crate::core::StateQuery;
 use crate::core::WindowContainerBehaviour;
-use crate::core::WindowKind;
 
 use crate::animation::ANIMATION_DURATION_GLOBAL;
 use crate::animation::ANIMATION_ENABLED_GLOBAL;
@@ -45,6 +43,8 @@ use crate::animation::ANIMATION_STYLE_GLOBAL;
 use crate::border_manager;
 use crate::border_manager::IMPLEMENTATION;
 use crate::border_manager::STYLE;
+use crate::colour::BorderColour;
+use crate::colour::Colour;
 use crate::colour::Rgb;
 use crate::config_generation::WorkspaceMatchingRule;
 use crate::current_virtual_desktop;
@@ -56,7 +56,9 @@ use crate::stackbar_manager::STACKBAR_FONT_SIZE;
 use crate::static_config::StaticConfig;
 use crate::theme_manager;
 use crate::transparency_manager;
+use crate::window::should_act_individual;
 use crate::window::RuleDebug;
+use crate::window::RuleTarget;
 use crate::window::Window;
 use crate::window_manager;
 use crate::window_manager::WindowManager;
@@ -87,6 +89,7 @@ use crate::TCP_CONNECTIONS;
 use crate::TRAY_AND_MULTI_WINDOW_IDENTIFIERS;
 use crate::WINDOWS_11;
 use crate::WORKSPACE_MATCHING_RULES;
+use crate::WORKSPACE_TARGET_RULES;
 use stackbar_manager::STACKBAR_FOCUSED_TEXT_COLOUR;
 use stackbar_manager::STACKBAR_LABEL;
 use stackbar_manager::STACKBAR_MODE;
@@ -346,6 +349,19 @@ impl WindowManager {
                 self.focus_container_window(idx)?;
                 self.focused_window()?.focus(self.mouse_follows_focus)?;
             }
+            SocketMessage::MoveStackWindow(ref container_id, from, to) => {
+                // stacks can only be reordered on the focused workspace of a monitor, where their
+                // stackbars are shown
+                for monitor in self.monitors_mut() {
+                    if let Some(container) = monitor.focused_workspace_mut().and_then(|ws| {
+                        ws.containers_mut()
+                            .iter_mut()
+                            .find(|c| c.id() == container_id)
+                    }) {
+                        container.move_window(from, to);
+                    }
+                }
+            }
             SocketMessage::ForceFocus => {
                 let focused_window = self.focused_window()?;
                 let focused_window_rect = WindowsApi::window_rect(focused_window.hwnd)?;
@@ -477,6 +493,7 @@ impl WindowManager {
             SocketMessage::ClearAllWorkspaceRules => {
                 let mut workspace_rules = WORKSPACE_MATCHING_RULES.lock();
                 workspace_rules.clear();
+                WORKSPACE_TARGET_RULES.lock().clear();
             }
             SocketMessage::EnforceWorkspaceRules => {
                 {
@@ -527,6 +544,12 @@ impl WindowManager {
 
                 let offset = self.work_area_offset;
 
+                let purge_rule = IdWithIdentifier {
+                    kind: identifier,
+                    id: id.clone(),
+                    matching_strategy: Option::from(MatchingStrategy::Equals),
+                };
+
                 let mut hwnds_to_purge = vec![];
                 for (i, monitor) in self.monitors().iter().enumerate() {
                     for container in monitor
@@ -535,27 +558,18 @@ impl WindowManager {
                         .containers()
                     {
                         for window in container.windows() {
-                            match identifier {
-                                ApplicationIdentifier::Path => {
-                                    if window.path()? == *id {
-                                        hwnds_to_purge.push((i, window.hwnd));
-                                    }
-                                }
-                                ApplicationIdentifier::Exe => {
-                                    if window.exe()? == *id {
-                                        hwnds_to_purge.push((i, window.hwnd));
-                                    }
-                                }
-                                ApplicationIdentifier::Class => {
-                                    if window.class()? == *id {
-                                        hwnds_to_purge.push((i, window.hwnd));
-                                    }
-                                }
-                                ApplicationIdentifier::Title => {
-                                    if window.title()? == *id {
-                                        hwnds_to_purge.push((i, window.hwnd));
-                                    }
-                                }
+                            // a window property which can't be read shouldn't stop the
+                            // other windows from being checked
+                            let target = RuleTarget::new(
+                                window.title().unwrap_or_default(),
+                                window.exe().unwrap_or_default(),
+                                window.class().unwrap_or_default(),
+                                window.path().unwrap_or_default(),
+                            )
+                            .with_hwnd(window.hwnd);
+
+                            if should_act_individual(&target, &purge_rule, &HashMap::new()) {
+                                hwnds_to_purge.push((i, window.hwnd));
                             }
                         }
                     }
@@ -1720,23 +1734,12 @@ impl WindowManager {
                     border_manager::send_notification(None);
                 }
             }
-            SocketMessage::BorderColour(kind, r, g, b) => match kind {
-                WindowKind::Single => {
-                    border_manager::FOCUSED.store(Rgb::new(r, g, b).into(), Ordering::SeqCst);
-                }
-                WindowKind::Stack => {
-                    border_manager::STACK.store(Rgb::new(r, g, b).into(), Ordering::SeqCst);
-                }
-                WindowKind::Monocle => {
-                    border_manager::MONOCLE.store(Rgb::new(r, g, b).into(), Ordering::SeqCst);
-                }
-                WindowKind::Unfocused => {
-                    border_manager::UNFOCUSED.store(Rgb::new(r, g, b).into(), Ordering::SeqCst);
-                }
-                WindowKind::Floating => {
-                    border_manager::FLOATING.store(Rgb::new(r, g, b).into(), Ordering::SeqCst);
-                }
-            },
+            SocketMessage::BorderColour(kind, r, g, b) => {
+                border_manager::set_window_kind_colour(
+                    kind,
+                    &BorderColour::from(Colour::from(Rgb::new(r, g, b))),
+                );
+            }
             SocketMessage::BorderStyle(style) => {
                 STYLE.store(style);
             }
@@ -1906,9 +1909,11 @@ impl WindowManager {
 
                 reply.write_all(schema.as_bytes())?;
             }
-            SocketMessage::Theme(theme) => {
-                theme_manager::send_notification(theme);
+            SocketMessage::Theme(ref theme) => {
+                theme_manager::send_notification(theme.clone());
             }
+            // subscribers are notified of the message below
+            SocketMessage::PublishNotification(_) => {}
             // Deprecated commands
             SocketMessage::AltFocusHack(_)
             | SocketMessage::IdentifyBorderOverflowApplication(_, _) => {}
@@ -1,4 +1,3 @@
-use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
@@ -14,14 +13,13 @@ use crate::core::Sizing;
 use crate::core::WindowContainerBehaviour;
 
 use crate::border_manager;
-use crate::border_manager::BORDER_OFFSET;
-use crate::border_manager::BORDER_WIDTH;
 use crate::current_virtual_desktop;
 use crate::notify_subscribers;
 use crate::stackbar_manager;
 use crate::transparency_manager;
 use crate::window::should_act;
 use crate::window::RuleDebug;
+use crate::window::RuleTarget;
 use crate::window_manager::WindowManager;
 use crate::window_manager_event::WindowManagerEvent;
 use crate::windows_api::WindowsApi;
@@ -218,19 +216,13 @@ impl WindowManager {
                         TRAY_AND_MULTI_WINDOW_IDENTIFIERS.lock();
                     let regex_identifiers = REGEX_IDENTIFIERS.lock();
 
-                    let title = &window.title()?;
-                    let exe_name = &window.exe()?;
-                    let class = &window.class()?;
-                    let path = &window.path()?;
+                    let target = RuleTarget::try_from(window)?;
 
                     // We don't want to purge windows that have been deliberately hidden by us, eg. when
                     // they are not on the top of a container stack.
                     let programmatically_hidden_hwnds = HIDDEN_HWNDS.lock();
                     let should_act = should_act(
-                        title,
-                        exe_name,
-                        class,
-                        path,
+                        &target,
                         &tray_and_multi_window_identifiers,
                         &regex_identifiers,
                     )
@@ -375,14 +367,9 @@ impl WindowManager {
                             if !floating_applications.is_empty() {
                                 let regex_identifiers = REGEX_IDENTIFIERS.lock();
 
-                                if let (Ok(title), Ok(exe_name), Ok(class), Ok(path)) =
-                                    (window.title(), window.exe(), window.class(), window.path())
-                                {
+                                if let Ok(target) = RuleTarget::try_from(window) {
                                     should_float = should_act(
-                                        &title,
-                                        &exe_name,
-                                        &class,
-                                        &path,
+                                        &target,
                                         &floating_applications,
                                         &regex_identifiers,
                                     )
@@ -397,6 +384,7 @@ impl WindowManager {
                             if behaviour.float_override {
                                 workspace.floating_windows_mut().push(window);
                                 workspace.set_layer(WorkspaceLayer::Floating);
+                                self.place_floating_window(false)?;
                                 self.update_focused_workspace(false, false)?;
                             } else {
                                 match behaviour.current_behaviour {
@@ -682,8 +670,8 @@ impl WindowManager {
                         }
 
                         // TODO: Determine if this is still needed
-                        let top_left_constant = BORDER_WIDTH.load(Ordering::SeqCst)
-                            + BORDER_OFFSET.load(Ordering::SeqCst);
+                        let border = workspace.border_settings();
+                        let top_left_constant = border.width() + border.offset();
 
                         if resize.right != 0
                             && (resize.left == top_left_constant || resize.left == 0)
@@ -0,0 +1,241 @@
+use crate::core::config_generation::MatchingRule;
+use crate::core::config_generation::MatchingStrategy;
+use crate::core::ApplicationIdentifier;
+use crate::window::matches_rule;
+use crate::window::RuleTarget;
+use regex::Regex;
+use regex::RegexSet;
+use std::cell::OnceCell;
+use std::collections::HashMap;
+use std::ops::Deref;
+
+/// An ordered list of [`MatchingRule`]s which is compiled into an indexed matcher the first time
+/// it is queried after being modified (eg. after a configuration reload)
+///
+/// Simple rules using exact, prefix or regex matching are looked up through hash maps, prefix
+/// tries and regex sets per [`ApplicationIdentifier`], and only the remaining rules (composite
+/// rules, negations, substring matching etc.) are evaluated one by one. Regardless of how a rule
+/// is indexed, the rule returned by [`RuleIndex::find`] is always the first matching rule in the
+/// order the rules were added.
+#[derive(Debug, Default, Clone)]
+pub struct RuleIndex {
+    rules: Vec<MatchingRule>,
+    compiled: OnceCell<CompiledRules>,
+}
+
+impl From<Vec<MatchingRule>> for RuleIndex {
+    fn from(rules: Vec<MatchingRule>) -> Self {
+        Self {
+            rules,
+            compiled: OnceCell::new(),
+        }
+    }
+}
+
+impl Deref for RuleIndex {
+    type Target = [MatchingRule];
+
+    fn deref(&self) -> &Self::Target {
+        &self.rules
+    }
+}
+
+impl<'a> IntoIterator for &'a RuleIndex {
+    type Item = &'a MatchingRule;
+    type IntoIter = std::slice::Iter<'a, MatchingRule>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.rules.iter()
+    }
+}
+
+impl RuleIndex {
+    pub fn push(&mut self, rule: MatchingRule) {
+        self.rules.push(rule);
+        self.compiled.take();
+    }
+
+    pub fn clear(&mut self) {
+        self.rules.clear();
+        self.compiled.take();
+    }
+
+    /// The first rule in the list which matches the target
+    pub fn find(
+        &self,
+        target: &RuleTarget,
+        regex_identifiers: &HashMap<String, Regex>,
+    ) -> Option<&MatchingRule> {
+        let compiled = self
+            .compiled
+            .get_or_init(|| CompiledRules::new(&self.rules));
+        let idx = compiled.first_match(&self.rules, target, regex_identifiers)?;
+        self.rules.get(idx)
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+struct CompiledRules {
+    exact: HashMap<ApplicationIdentifier, HashMap<String, usize>>,
+    prefixes: HashMap<ApplicationIdentifier, PrefixTrie>,
+    regexes: HashMap<ApplicationIdentifier, (RegexSet, Vec<usize>)>,
+    fallback: Vec<usize>,
+}
+
+impl CompiledRules {
+    fn new(rules: &[MatchingRule]) -> Self {
+        let mut compiled = Self::default();
+        let mut patterns: HashMap<ApplicationIdentifier, (Vec<String>, Vec<usize>)> =
+            HashMap::new();
+
+        for (idx, rule) in rules.iter().enumerate() {
+            let MatchingRule::Simple(identifier) = rule else {
+                compiled.fallback.push(idx);
+                continue;
+            };
+
+            let kind = identifier.kind;
+            let id = identifier.id.clone();
+
+            match identifier.matching_strategy {
+                Some(MatchingStrategy::Equals) => {
+                    compiled
+                        .exact
+                        .entry(kind)
+                        .or_default()
+                        .entry(id)
+                        .or_insert(idx);
+                }
+                None | Some(MatchingStrategy::Legacy)
+                    if !matches!(
+                        kind,
+                        ApplicationIdentifier::Title | ApplicationIdentifier::Class
+                    ) =>
+                {
+                    compiled
+                        .exact
+                        .entry(kind)
+                        .or_default()
+                        .entry(id)
+                        .or_insert(idx);
+                }
+                Some(MatchingStrategy::StartsWith) => {
+                    compiled.prefixes.entry(kind).or_default().insert(&id, idx);
+                }
+                // invalid patterns would prevent the whole set from compiling, so they are left
+                // to the fallback, where they will never match
+                Some(MatchingStrategy::Regex) if Regex::new(&id).is_ok() => {
+                    let (kind_patterns, indices) = patterns.entry(kind).or_default();
+                    kind_patterns.push(id);
+                    indices.push(idx);
+                }
+                _ => compiled.fallback.push(idx),
+            }
+        }
+
+        for (kind, (kind_patterns, indices)) in patterns {
+            match RegexSet::new(&kind_patterns) {
+                Ok(set) => {
+                    compiled.regexes.insert(kind, (set, indices));
+                }
+                Err(error) => {
+                    tracing::warn!("could not compile regex set for {kind} rules: {error}");
+                    compiled.fallback.extend(indices);
+                }
+            }
+        }
+
+        compiled.fallback.sort_unstable();
+        compiled
+    }
+
+    fn first_match(
+        &self,
+        rules: &[MatchingRule],
+        target: &RuleTarget,
+        regex_identifiers: &HashMap<String, Regex>,
+    ) -> Option<usize> {
+        let mut best: Option<usize> = None;
+        let mut consider = |idx: usize| {
+            if best.is_none_or(|current| idx < current) {
+                best = Some(idx);
+            }
+        };
+
+        for (kind, map) in &self.exact {
+            for value in target.values(*kind) {
+                if let Some(idx) = map.get(value) {
+                    consider(*idx);
+                }
+            }
+        }
+
+        for (kind, trie) in &self.prefixes {
+            for value in target.values(*kind) {
+                if let Some(idx) = trie.first_match(value) {
+                    consider(idx);
+                }
+            }
+        }
+
+        for (kind, (set, indices)) in &self.regexes {
+            for value in target.values(*kind) {
+                if let Some(idx) = set.matches(value).iter().map(|i| indices[i]).min() {
+                    consider(idx);
+                }
+            }
+        }
+
+        for idx in &self.fallback {
+            // the fallback is sorted, so nothing after this point can beat an indexed match
+            if best.is_some_and(|current| *idx >= current) {
+                break;
+            }
+
+            if matches_rule(target, &rules[*idx], regex_identifiers) {
+                return Some(*idx);
+            }
+        }
+
+        best
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+struct PrefixTrie {
+    children: HashMap<char, PrefixTrie>,
+    rule: Option<usize>,
+}
+
+impl PrefixTrie {
+    fn insert(&mut self, prefix: &str, idx: usize) {
+        let mut node = self;
+        for c in prefix.chars() {
+            node = node.children.entry(c).or_default();
+        }
+
+        if node.rule.is_none_or(|existing| idx < existing) {
+            node.rule = Some(idx);
+        }
+    }
+
+    /// The lowest rule index of all the prefixes which the value starts with
+    fn first_match(&self, value: &str) -> Option<usize> {
+        let mut node = self;
+        let mut best = node.rule;
+
+        for c in value.chars() {
+            match node.children.get(&c) {
+                None => break,
+                Some(child) => {
+                    node = child;
+                    if let Some(idx) = node.rule {
+                        best = Some(best.map_or(idx, |current| current.min(idx)));
+                    }
+                }
+            }
+        }
+
+        best
+    }
+}
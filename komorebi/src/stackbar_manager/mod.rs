@@ -1,11 +1,17 @@
 mod stackbar;
 
 use crate::container::Container;
+use crate::core::Rect;
 use crate::core::StackbarLabel;
 use crate::core::StackbarMode;
 use crate::stackbar_manager::stackbar::Stackbar;
+use crate::static_config::ScalableSize;
+use crate::static_config::StackbarOverrides;
+use crate::window_label::WindowLabeller;
+use crate::SocketMessage;
 use crate::WindowManager;
 use crate::WindowsApi;
+use crate::DATA_DIR;
 use crate::DEFAULT_CONTAINER_PADDING;
 use crossbeam_channel::Receiver;
 use crossbeam_channel::Sender;
@@ -15,12 +21,14 @@ use lazy_static::lazy_static;
 use parking_lot::Mutex;
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::io::Write;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicI32;
 use std::sync::atomic::AtomicU32;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::OnceLock;
+use uds_windows::UnixStream;
 
 pub static STACKBAR_FONT_SIZE: AtomicI32 = AtomicI32::new(0); // 0 will produce the system default
 pub static STACKBAR_FOCUSED_TEXT_COLOUR: AtomicU32 = AtomicU32::new(16777215); // white
@@ -31,13 +39,21 @@ pub static STACKBAR_TAB_WIDTH: AtomicI32 = AtomicI32::new(200);
 pub static STACKBAR_LABEL: AtomicCell<StackbarLabel> = AtomicCell::new(StackbarLabel::Process);
 pub static STACKBAR_MODE: AtomicCell<StackbarMode> = AtomicCell::new(StackbarMode::OnStack);
 
+pub static STACKBAR_TAB_ICONS: AtomicBool = AtomicBool::new(false);
+pub static STACKBAR_TAB_CLOSE_BUTTON: AtomicBool = AtomicBool::new(false);
+pub static STACKBAR_TAB_AUTO_WIDTH: AtomicBool = AtomicBool::new(false);
+
 pub static STACKBAR_TEMPORARILY_DISABLED: AtomicBool = AtomicBool::new(false);
 
 lazy_static! {
     pub static ref STACKBAR_STATE: Mutex<HashMap<String, Stackbar>> = Mutex::new(HashMap::new());
     pub static ref STACKBAR_FONT_FAMILY: Mutex<Option<String>> = Mutex::new(None);
+    pub static ref STACKBAR_LABELLER: Mutex<WindowLabeller> = Mutex::new(WindowLabeller::default());
     static ref STACKBARS_MONITORS: Mutex<HashMap<String, usize>> = Mutex::new(HashMap::new());
     static ref STACKBARS_CONTAINERS: Mutex<HashMap<isize, Container>> = Mutex::new(HashMap::new());
+    static ref STACKBARS_TABS: Mutex<HashMap<isize, Vec<Rect>>> = Mutex::new(HashMap::new());
+    /// The stackbar and index of the tab which is being dragged
+    static ref DRAGGED_TAB: Mutex<Option<(isize, usize)>> = Mutex::new(None);
 }
 
 pub struct Notification;
@@ -62,11 +78,93 @@ pub fn send_notification() {
     }
 }
 
-pub fn should_have_stackbar(window_count: usize) -> bool {
-    match STACKBAR_MODE.load() {
-        StackbarMode::Always => true,
-        StackbarMode::OnStack => window_count > 1,
-        StackbarMode::Never => false,
+/// Reorders a tab which has been dragged through komorebi.sock rather than in the stackbar
+/// thread, so that subscribers are notified of the new order of the windows in the container
+pub fn send_move_tab(container_id: &str, from: usize, to: usize) -> color_eyre::Result<()> {
+    let socket = DATA_DIR.join("komorebi.sock");
+    let mut stream = UnixStream::connect(socket)?;
+    let message = SocketMessage::MoveStackWindow(container_id.to_string(), from, to);
+    stream.write_all(&message.as_bytes()?)?;
+
+    Ok(())
+}
+
+/// Stackbar settings for the stackbars of a workspace, where anything which is not set falls back
+/// to the global setting
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct StackbarSettings {
+    pub mode: Option<StackbarMode>,
+    pub height: Option<i32>,
+    pub tab_width: Option<i32>,
+    pub font_size: Option<i32>,
+    pub focused_text_colour: Option<u32>,
+    pub unfocused_text_colour: Option<u32>,
+    pub background_colour: Option<u32>,
+}
+
+impl StackbarSettings {
+    /// Applies overrides on top of these settings, scaling sizes by a monitor's scale factor
+    pub fn apply(&mut self, overrides: &StackbarOverrides, scale_factor: f32) {
+        let scaled = |size: Option<ScalableSize>| size.map(|size| size.to_pixels(scale_factor));
+
+        self.mode = overrides.mode.or(self.mode);
+        self.height = scaled(overrides.height).or(self.height);
+        self.tab_width = scaled(overrides.tab_width).or(self.tab_width);
+        self.font_size = scaled(overrides.font_size).or(self.font_size);
+        self.focused_text_colour = overrides
+            .focused_text
+            .map(u32::from)
+            .or(self.focused_text_colour);
+        self.unfocused_text_colour = overrides
+            .unfocused_text
+            .map(u32::from)
+            .or(self.unfocused_text_colour);
+        self.background_colour = overrides
+            .background
+            .map(u32::from)
+            .or(self.background_colour);
+    }
+
+    pub fn mode(&self) -> StackbarMode {
+        self.mode.unwrap_or_else(|| STACKBAR_MODE.load())
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height
+            .unwrap_or_else(|| STACKBAR_TAB_HEIGHT.load_consume())
+    }
+
+    pub fn tab_width(&self) -> i32 {
+        self.tab_width
+            .unwrap_or_else(|| STACKBAR_TAB_WIDTH.load_consume())
+    }
+
+    pub fn font_size(&self) -> i32 {
+        self.font_size
+            .unwrap_or_else(|| STACKBAR_FONT_SIZE.load_consume())
+    }
+
+    pub fn focused_text_colour(&self) -> u32 {
+        self.focused_text_colour
+            .unwrap_or_else(|| STACKBAR_FOCUSED_TEXT_COLOUR.load_consume())
+    }
+
+    pub fn unfocused_text_colour(&self) -> u32 {
+        self.unfocused_text_colour
+            .unwrap_or_else(|| STACKBAR_UNFOCUSED_TEXT_COLOUR.load_consume())
+    }
+
+    pub fn background_colour(&self) -> u32 {
+        self.background_colour
+            .unwrap_or_else(|| STACKBAR_TAB_BACKGROUND_COLOUR.load_consume())
+    }
+
+    pub fn should_have_stackbar(&self, window_count: usize) -> bool {
+        match self.mode() {
+            StackbarMode::Always => true,
+            StackbarMode::OnStack => window_count > 1,
+            StackbarMode::Never => false,
+        }
     }
 }
 
@@ -95,10 +193,9 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
         // Check the wm state every time we receive a notification
         let mut state = wm.lock();
 
-        // If stackbars are disabled
-        if matches!(STACKBAR_MODE.load(), StackbarMode::Never)
-            || STACKBAR_TEMPORARILY_DISABLED.load(Ordering::SeqCst)
-        {
+        // If stackbars are temporarily disabled (stackbars disabled by their mode are handled per
+        // workspace, as monitors and workspaces can override the mode)
+        if STACKBAR_TEMPORARILY_DISABLED.load(Ordering::SeqCst) {
             for (_, stackbar) in stackbars.iter() {
                 stackbar.destroy()?;
             }
@@ -172,13 +269,12 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                 let container_padding = ws
                     .container_padding()
                     .unwrap_or_else(|| DEFAULT_CONTAINER_PADDING.load_consume());
+                let settings = ws.stackbar_settings();
+                let border_settings = ws.border_settings();
 
                 'containers: for container in ws.containers_mut() {
-                    let should_add_stackbar = match STACKBAR_MODE.load() {
-                        StackbarMode::Always => true,
-                        StackbarMode::OnStack => container.windows().len() > 1,
-                        StackbarMode::Never => false,
-                    };
+                    let should_add_stackbar =
+                        settings.should_have_stackbar(container.windows().len());
 
                     if !should_add_stackbar {
                         if let Some(stackbar) = stackbars.get(container.id()) {
@@ -208,7 +304,13 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                         container.focused_window().copied().unwrap_or_default().hwnd,
                     )?;
 
-                    stackbar.update(container_padding, container, &rect)?;
+                    stackbar.update(
+                        container_padding,
+                        container,
+                        &rect,
+                        &settings,
+                        &border_settings,
+                    )?;
                 }
             }
         }
@@ -1,19 +1,22 @@
-use crate::border_manager::BORDER_OFFSET;
-use crate::border_manager::BORDER_WIDTH;
+use crate::border_manager::BorderSettings;
 use crate::border_manager::STYLE;
 use crate::container::Container;
 use crate::core::BorderStyle;
 use crate::core::Rect;
 use crate::core::StackbarLabel;
+use crate::stackbar_manager;
+use crate::stackbar_manager::StackbarSettings;
+use crate::stackbar_manager::DRAGGED_TAB;
 use crate::stackbar_manager::STACKBARS_CONTAINERS;
-use crate::stackbar_manager::STACKBAR_FOCUSED_TEXT_COLOUR;
+use crate::stackbar_manager::STACKBARS_TABS;
 use crate::stackbar_manager::STACKBAR_FONT_FAMILY;
-use crate::stackbar_manager::STACKBAR_FONT_SIZE;
 use crate::stackbar_manager::STACKBAR_LABEL;
-use crate::stackbar_manager::STACKBAR_TAB_BACKGROUND_COLOUR;
+use crate::stackbar_manager::STACKBAR_LABELLER;
+use crate::stackbar_manager::STACKBAR_TAB_AUTO_WIDTH;
+use crate::stackbar_manager::STACKBAR_TAB_CLOSE_BUTTON;
 use crate::stackbar_manager::STACKBAR_TAB_HEIGHT;
-use crate::stackbar_manager::STACKBAR_TAB_WIDTH;
-use crate::stackbar_manager::STACKBAR_UNFOCUSED_TEXT_COLOUR;
+use crate::stackbar_manager::STACKBAR_TAB_ICONS;
+use crate::window::RuleTarget;
 use crate::windows_api;
 use crate::WindowsApi;
 use crate::DEFAULT_CONTAINER_PADDING;
@@ -54,19 +57,25 @@ use windows::Win32::Graphics::Gdi::LOGPIXELSY;
 use windows::Win32::Graphics::Gdi::PROOF_QUALITY;
 use windows::Win32::Graphics::Gdi::PS_SOLID;
 use windows::Win32::System::WindowsProgramming::MulDiv;
+use windows::Win32::UI::Input::KeyboardAndMouse::ReleaseCapture;
+use windows::Win32::UI::Input::KeyboardAndMouse::SetCapture;
 use windows::Win32::UI::WindowsAndMessaging::CreateWindowExW;
 use windows::Win32::UI::WindowsAndMessaging::DefWindowProcW;
 use windows::Win32::UI::WindowsAndMessaging::DispatchMessageW;
+use windows::Win32::UI::WindowsAndMessaging::DrawIconEx;
 use windows::Win32::UI::WindowsAndMessaging::GetMessageW;
 use windows::Win32::UI::WindowsAndMessaging::PostQuitMessage;
 use windows::Win32::UI::WindowsAndMessaging::SetLayeredWindowAttributes;
 use windows::Win32::UI::WindowsAndMessaging::TranslateMessage;
 use windows::Win32::UI::WindowsAndMessaging::CS_HREDRAW;
 use windows::Win32::UI::WindowsAndMessaging::CS_VREDRAW;
+use windows::Win32::UI::WindowsAndMessaging::DI_NORMAL;
 use windows::Win32::UI::WindowsAndMessaging::LWA_COLORKEY;
 use windows::Win32::UI::WindowsAndMessaging::MSG;
 use windows::Win32::UI::WindowsAndMessaging::WM_DESTROY;
 use windows::Win32::UI::WindowsAndMessaging::WM_LBUTTONDOWN;
+use windows::Win32::UI::WindowsAndMessaging::WM_LBUTTONUP;
+use windows::Win32::UI::WindowsAndMessaging::WM_MBUTTONUP;
 use windows::Win32::UI::WindowsAndMessaging::WNDCLASSW;
 use windows::Win32::UI::WindowsAndMessaging::WS_EX_LAYERED;
 use windows::Win32::UI::WindowsAndMessaging::WS_EX_TOOLWINDOW;
@@ -162,24 +171,34 @@ impl Stackbar {
         container_padding: i32,
         container: &mut Container,
         layout: &Rect,
+        settings: &StackbarSettings,
+        border_settings: &BorderSettings,
     ) -> color_eyre::Result<()> {
-        let width = STACKBAR_TAB_WIDTH.load_consume();
-        let height = STACKBAR_TAB_HEIGHT.load_consume();
-        let gap = DEFAULT_CONTAINER_PADDING.load_consume();
-        let background = STACKBAR_TAB_BACKGROUND_COLOUR.load_consume();
-        let focused_text_colour = STACKBAR_FOCUSED_TEXT_COLOUR.load_consume();
-        let unfocused_text_colour = STACKBAR_UNFOCUSED_TEXT_COLOUR.load_consume();
+        let height = settings.height();
+        let background = settings.background_colour();
+        let focused_text_colour = settings.focused_text_colour();
+        let unfocused_text_colour = settings.unfocused_text_colour();
+        let show_icons = STACKBAR_TAB_ICONS.load(Ordering::SeqCst);
+        let show_close_button = STACKBAR_TAB_CLOSE_BUTTON.load(Ordering::SeqCst);
 
         let mut stackbars_containers = STACKBARS_CONTAINERS.lock();
         stackbars_containers.insert(self.hwnd, container.clone());
 
         let mut layout = *layout;
         let workspace_specific_offset =
-            BORDER_WIDTH.load_consume() + BORDER_OFFSET.load_consume() + container_padding;
+            border_settings.width() + border_settings.offset() + container_padding;
 
-        layout.top -= workspace_specific_offset + STACKBAR_TAB_HEIGHT.load_consume();
+        layout.top -= workspace_specific_offset + height;
         layout.left -= workspace_specific_offset;
 
+        // automatically sized tabs span the container including its border
+        if STACKBAR_TAB_AUTO_WIDTH.load(Ordering::SeqCst) {
+            layout.right += 2 * workspace_specific_offset;
+        }
+
+        let tabs = tab_rects(container.windows().len(), settings, layout.right);
+        STACKBARS_TABS.lock().insert(self.hwnd, tabs.clone());
+
         WindowsApi::position_window(self.hwnd, &layout, false)?;
 
         unsafe {
@@ -207,7 +226,7 @@ impl Stackbar {
             }
 
             let logical_height = -MulDiv(
-                STACKBAR_FONT_SIZE.load(Ordering::SeqCst),
+                settings.font_size(),
                 72,
                 GetDeviceCaps(Option::from(hdc), LOGPIXELSY),
             );
@@ -218,20 +237,14 @@ impl Stackbar {
 
             SelectObject(hdc, hfont.into());
 
-            for (i, window) in container.windows().iter().enumerate() {
+            for (i, (window, tab)) in container.windows().iter().zip(tabs).enumerate() {
                 if window.hwnd == container.focused_window().copied().unwrap_or_default().hwnd {
                     SetTextColor(hdc, COLORREF(focused_text_colour));
                 } else {
                     SetTextColor(hdc, COLORREF(unfocused_text_colour));
                 }
 
-                let left = gap + (i as i32 * (width + gap));
-                let mut rect = Rect {
-                    top: 0,
-                    left,
-                    right: left + width,
-                    bottom: height,
-                };
+                let mut rect = tab;
 
                 match STYLE.load() {
                     BorderStyle::System => {
@@ -262,19 +275,55 @@ impl Stackbar {
                     }
                 }
 
-                let label = match STACKBAR_LABEL.load() {
-                    StackbarLabel::Process => {
-                        let exe = window.exe()?;
-                        exe.trim_end_matches(".exe").to_string()
-                    }
-                    StackbarLabel::Title => window.title()?,
+                let target = RuleTarget::try_from(*window)?;
+                let label = match STACKBAR_LABELLER.lock().label(&target, i) {
+                    Some(label) => label,
+                    None => match STACKBAR_LABEL.load() {
+                        StackbarLabel::Process => target.exe.trim_end_matches(".exe").to_string(),
+                        StackbarLabel::Title => target.title,
+                    },
                 };
 
-                let mut tab_title: Vec<u16> = label.encode_utf16().collect();
-
                 rect.left_padding(10);
                 rect.right_padding(10);
 
+                if show_close_button {
+                    let close_button = close_button_rect(&tab);
+                    let mut close_label: Vec<u16> = "\u{2715}".encode_utf16().collect();
+
+                    DrawTextW(
+                        hdc,
+                        &mut close_label,
+                        &mut close_button.into(),
+                        DT_SINGLELINE | DT_CENTER | DT_VCENTER,
+                    );
+
+                    rect.right = close_button.left - 5;
+                }
+
+                if show_icons {
+                    if let Some(icon) = WindowsApi::window_icon(window.hwnd) {
+                        let icon_size = (height / 2).min(rect.right - rect.left).max(0);
+
+                        // TODO: error handling
+                        let _ = DrawIconEx(
+                            hdc,
+                            rect.left,
+                            (height - icon_size) / 2,
+                            icon,
+                            icon_size,
+                            icon_size,
+                            0,
+                            None,
+                            DI_NORMAL,
+                        );
+
+                        rect.left_padding(icon_size + 5);
+                    }
+                }
+
+                let mut tab_title: Vec<u16> = label.encode_utf16().collect();
+
                 DrawTextW(
                     hdc,
                     &mut tab_title,
@@ -312,13 +361,33 @@ impl Stackbar {
             match msg {
                 WM_LBUTTONDOWN => {
                     let stackbars_containers = STACKBARS_CONTAINERS.lock();
-                    if let Some(container) = stackbars_containers.get(&(hwnd.0 as isize)) {
-                        let x = l_param.0 as i32 & 0xFFFF;
-                        let y = (l_param.0 as i32 >> 16) & 0xFFFF;
+                    let stackbars_tabs = STACKBARS_TABS.lock();
+                    if let (Some(container), Some(tabs)) = (
+                        stackbars_containers.get(&(hwnd.0 as isize)),
+                        stackbars_tabs.get(&(hwnd.0 as isize)),
+                    ) {
+                        let (x, y) = cursor_position(l_param);
+
+                        let clicked_idx = tabs.iter().position(|tab| tab_contains(tab, x, y));
+
+                        // Close the window if the close button of its tab was clicked
+                        if let Some(idx) = clicked_idx {
+                            if STACKBAR_TAB_CLOSE_BUTTON.load(Ordering::SeqCst)
+                                && tab_contains(&close_button_rect(&tabs[idx]), x, y)
+                            {
+                                if let Some(window) = container.windows().get(idx) {
+                                    if let Err(err) = window.close() {
+                                        tracing::error!(
+                                            "stackbar close button error: hwnd {} ({})",
+                                            *window,
+                                            err
+                                        );
+                                    }
+                                }
 
-                        let width = STACKBAR_TAB_WIDTH.load_consume();
-                        let height = STACKBAR_TAB_HEIGHT.load_consume();
-                        let gap = DEFAULT_CONTAINER_PADDING.load_consume();
+                                return LRESULT(0);
+                            }
+                        }
 
                         let focused_window_idx = container.focused_window_idx();
                         let focused_window_rect = WindowsApi::window_rect(
@@ -327,12 +396,7 @@ impl Stackbar {
                         .unwrap_or_default();
 
                         for (index, window) in container.windows().iter().enumerate() {
-                            let left = gap + (index as i32 * (width + gap));
-                            let right = left + width;
-                            let top = 0;
-                            let bottom = height;
-
-                            if x >= left && x <= right && y >= top && y <= bottom {
+                            if clicked_idx == Some(index) {
                                 // If we are focusing a window that isn't currently focused in the
                                 // stackbar, make sure we update its location so that it doesn't render
                                 // on top of other tiles before eventually ending up in the correct
@@ -364,11 +428,80 @@ impl Stackbar {
                                 window.hide_with_border(false);
                             }
                         }
+
+                        // The clicked tab can be dragged to a new position until the button is
+                        // released, even if the cursor leaves the stackbar
+                        if let Some(idx) = clicked_idx {
+                            *DRAGGED_TAB.lock() = Some((hwnd.0 as isize, idx));
+                            SetCapture(hwnd);
+                        }
+                    }
+
+                    LRESULT(0)
+                }
+                WM_LBUTTONUP => {
+                    let dragged_tab = DRAGGED_TAB.lock().take();
+                    // TODO: error handling
+                    let _ = ReleaseCapture();
+
+                    if let Some((_, from)) =
+                        dragged_tab.filter(|(stackbar_hwnd, _)| *stackbar_hwnd == hwnd.0 as isize)
+                    {
+                        let stackbars_containers = STACKBARS_CONTAINERS.lock();
+                        let stackbars_tabs = STACKBARS_TABS.lock();
+                        if let (Some(container), Some(tabs)) = (
+                            stackbars_containers.get(&(hwnd.0 as isize)),
+                            stackbars_tabs.get(&(hwnd.0 as isize)),
+                        ) {
+                            let (x, _) = cursor_position(l_param);
+
+                            // Dropping a tab beyond either end moves it to that end
+                            let to = tabs
+                                .iter()
+                                .position(|tab| x <= tab.right)
+                                .unwrap_or_else(|| tabs.len().saturating_sub(1));
+
+                            if to != from {
+                                if let Err(err) =
+                                    stackbar_manager::send_move_tab(container.id(), from, to)
+                                {
+                                    tracing::error!("stackbar tab reordering error: {}", err);
+                                }
+                            }
+                        }
+                    }
+
+                    LRESULT(0)
+                }
+                WM_MBUTTONUP => {
+                    let stackbars_containers = STACKBARS_CONTAINERS.lock();
+                    let stackbars_tabs = STACKBARS_TABS.lock();
+                    if let (Some(container), Some(tabs)) = (
+                        stackbars_containers.get(&(hwnd.0 as isize)),
+                        stackbars_tabs.get(&(hwnd.0 as isize)),
+                    ) {
+                        let (x, y) = cursor_position(l_param);
+
+                        // Close the window of the tab which was middle-clicked
+                        if let Some(window) = tabs
+                            .iter()
+                            .position(|tab| tab_contains(tab, x, y))
+                            .and_then(|idx| container.windows().get(idx))
+                        {
+                            if let Err(err) = window.close() {
+                                tracing::error!(
+                                    "stackbar WM_MBUTTONUP close error: hwnd {} ({})",
+                                    *window,
+                                    err
+                                );
+                            }
+                        }
                     }
 
                     LRESULT(0)
                 }
                 WM_DESTROY => {
+                    STACKBARS_TABS.lock().remove(&(hwnd.0 as isize));
                     PostQuitMessage(0);
                     LRESULT(0)
                 }
@@ -378,6 +511,60 @@ impl Stackbar {
     }
 }
 
+/// The tabs of a stackbar of the given width for a container with `count` windows, relative to
+/// the stackbar window
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+fn tab_rects(count: usize, settings: &StackbarSettings, stackbar_width: i32) -> Vec<Rect> {
+    let gap = DEFAULT_CONTAINER_PADDING.load_consume();
+    let height = settings.height();
+
+    let width = if STACKBAR_TAB_AUTO_WIDTH.load(Ordering::SeqCst) && count > 0 {
+        ((stackbar_width - gap * (count as i32 + 1)) / count as i32).max(0)
+    } else {
+        settings.tab_width()
+    };
+
+    (0..count as i32)
+        .map(|i| {
+            let left = gap + (i * (width + gap));
+            Rect {
+                top: 0,
+                left,
+                right: left + width,
+                bottom: height,
+            }
+        })
+        .collect()
+}
+
+/// The close button at the right of a tab
+fn close_button_rect(tab: &Rect) -> Rect {
+    let size = (tab.bottom - tab.top) / 2;
+    let right = tab.right - 10;
+
+    Rect {
+        top: tab.top + (tab.bottom - tab.top - size) / 2,
+        left: right - size,
+        right,
+        bottom: tab.top + (tab.bottom - tab.top + size) / 2,
+    }
+}
+
+/// Whether a point is inside a tab, where the right and bottom of the tab are coordinates rather
+/// than a width and height
+fn tab_contains(tab: &Rect, x: i32, y: i32) -> bool {
+    x >= tab.left && x <= tab.right && y >= tab.top && y <= tab.bottom
+}
+
+/// The cursor position of a mouse message, which can be negative while the mouse is captured
+#[allow(clippy::cast_possible_truncation)]
+fn cursor_position(l_param: LPARAM) -> (i32, i32) {
+    let x = (l_param.0 & 0xFFFF) as i16;
+    let y = ((l_param.0 >> 16) & 0xFFFF) as i16;
+
+    (i32::from(x), i32::from(y))
+}
+
 fn wide_string(s: &str) -> Vec<u16> {
     std::ffi::OsStr::new(s)
         .encode_wide()
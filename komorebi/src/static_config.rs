@@ -13,8 +13,10 @@ use crate::border_manager;
 use crate::border_manager::ZOrder;
 use crate::border_manager::IMPLEMENTATION;
 use crate::border_manager::STYLE;
+use crate::colour::BorderColour;
 use crate::colour::Colour;
 use crate::config_generation::WorkspaceMatchingRule;
+use crate::config_generation::WorkspaceTargetRule;
 use crate::core::config_generation::ApplicationConfiguration;
 use crate::core::config_generation::ApplicationConfigurationGenerator;
 use crate::core::config_generation::ApplicationOptions;
@@ -35,24 +37,36 @@ use crate::core::SocketMessage;
 use crate::core::StackbarLabel;
 use crate::core::StackbarMode;
 use crate::core::WindowContainerBehaviour;
+use crate::core::WindowKind;
 use crate::core::WindowManagementBehaviour;
 use crate::current_virtual_desktop;
 use crate::monitor;
 use crate::monitor::Monitor;
 use crate::monitor_reconciliator;
 use crate::ring::Ring;
+use crate::rule_index::RuleIndex;
 use crate::stackbar_manager::STACKBAR_FOCUSED_TEXT_COLOUR;
 use crate::stackbar_manager::STACKBAR_FONT_FAMILY;
 use crate::stackbar_manager::STACKBAR_FONT_SIZE;
 use crate::stackbar_manager::STACKBAR_LABEL;
+use crate::stackbar_manager::STACKBAR_LABELLER;
 use crate::stackbar_manager::STACKBAR_MODE;
+use crate::stackbar_manager::STACKBAR_TAB_AUTO_WIDTH;
 use crate::stackbar_manager::STACKBAR_TAB_BACKGROUND_COLOUR;
+use crate::stackbar_manager::STACKBAR_TAB_CLOSE_BUTTON;
 use crate::stackbar_manager::STACKBAR_TAB_HEIGHT;
+use crate::stackbar_manager::STACKBAR_TAB_ICONS;
 use crate::stackbar_manager::STACKBAR_TAB_WIDTH;
 use crate::stackbar_manager::STACKBAR_UNFOCUSED_TEXT_COLOUR;
 use crate::theme_manager;
 use crate::transparency_manager;
+use crate::transparency_manager::TransparencyProfile;
+use crate::transparency_manager::TransparencyRule;
+use crate::transparency_manager::TransparencyRules;
 use crate::window;
+use crate::window_label::LabelOverride;
+use crate::window_label::LabelTemplate;
+use crate::window_label::WindowLabeller;
 use crate::window_manager::WindowManager;
 use crate::window_manager_event::WindowManagerEvent;
 use crate::windows_api::WindowsApi;
@@ -60,12 +74,14 @@ use crate::workspace::Workspace;
 use crate::AspectRatio;
 use crate::Axis;
 use crate::CrossBoundaryBehaviour;
+use crate::FloatingPlacementRule;
 use crate::PredefinedAspectRatio;
 use crate::DATA_DIR;
 use crate::DEFAULT_CONTAINER_PADDING;
 use crate::DEFAULT_WORKSPACE_PADDING;
 use crate::DISPLAY_INDEX_PREFERENCES;
 use crate::FLOATING_APPLICATIONS;
+use crate::FLOATING_WINDOW_PLACEMENT_RULES;
 use crate::FLOATING_WINDOW_TOGGLE_ASPECT_RATIO;
 use crate::HIDING_BEHAVIOUR;
 use crate::IGNORE_IDENTIFIERS;
@@ -82,6 +98,10 @@ use crate::TRANSPARENCY_BLACKLIST;
 use crate::TRAY_AND_MULTI_WINDOW_IDENTIFIERS;
 use crate::WINDOWS_11;
 use crate::WORKSPACE_MATCHING_RULES;
+use crate::WORKSPACE_TARGET_RULES;
+use chrono::Local;
+use chrono::NaiveTime;
+use color_eyre::eyre::bail;
 use color_eyre::Result;
 use crossbeam_channel::Receiver;
 use hotwatch::EventKind;
@@ -100,24 +120,105 @@ use std::sync::Arc;
 use uds_windows::UnixListener;
 use uds_windows::UnixStream;
 
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct BorderColours {
-    /// Border colour when the container contains a single window
+    /// Border colour or gradient when the container contains a single window
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub single: Option<Colour>,
-    /// Border colour when the container contains multiple windows
+    pub single: Option<BorderColour>,
+    /// Border colour or gradient when the container contains multiple windows
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub stack: Option<Colour>,
-    /// Border colour when the container is in monocle mode
+    pub stack: Option<BorderColour>,
+    /// Border colour or gradient when the container is in monocle mode
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub monocle: Option<Colour>,
-    /// Border colour when the container is in floating mode
+    pub monocle: Option<BorderColour>,
+    /// Border colour or gradient when the container is in floating mode
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub floating: Option<Colour>,
-    /// Border colour when the container is unfocused
+    pub floating: Option<BorderColour>,
+    /// Border colour or gradient when the container is unfocused
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub unfocused: Option<Colour>,
+    pub unfocused: Option<BorderColour>,
+}
+
+impl BorderColours {
+    /// Ensures that every border gradient has at least one colour to draw
+    pub fn validate(&self) -> Result<()> {
+        [
+            &self.single,
+            &self.stack,
+            &self.monocle,
+            &self.floating,
+            &self.unfocused,
+        ]
+        .into_iter()
+        .flatten()
+        .try_for_each(BorderColour::validate)
+    }
+}
+
+/// A size in pixels, or in DPI-independent units which are scaled by the monitor's scale factor
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum ScalableSize {
+    /// Size in pixels
+    Pixels(i32),
+    /// Size in DPI-independent units, where 1 unit is 1 pixel at 100% scaling
+    Scaled {
+        /// Size at 100% scaling
+        scaled: f32,
+    },
+}
+
+impl ScalableSize {
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn to_pixels(self, scale_factor: f32) -> i32 {
+        match self {
+            ScalableSize::Pixels(pixels) => pixels,
+            // monitors can't be scaled below 100%
+            ScalableSize::Scaled { scaled } => (scaled * scale_factor.max(1.0)).round() as i32,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct BorderOverrides {
+    /// Width of the window border (default: global)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<ScalableSize>,
+    /// Offset of the window border (default: global)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<ScalableSize>,
+    /// Window border colours for different kinds of windows (default: global)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub colours: Option<BorderColours>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct StackbarOverrides {
+    /// Stackbar mode (default: global)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<StackbarMode>,
+    /// Stackbar height (default: global)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<ScalableSize>,
+    /// Width of a stackbar tab (default: global)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tab_width: Option<ScalableSize>,
+    /// Font size (default: global)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub font_size: Option<ScalableSize>,
+    /// Focused tab text colour (default: global)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focused_text: Option<Colour>,
+    /// Unfocused tab text colour (default: global)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unfocused_text: Option<Colour>,
+    /// Tab background colour (default: global)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background: Option<Colour>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -164,6 +265,15 @@ pub struct WorkspaceConfig {
     /// Specify an axis on which to flip the selected layout (default: None)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub layout_flip: Option<Axis>,
+    /// Border overrides for this workspace (default: monitor)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub border: Option<BorderOverrides>,
+    /// Stackbar overrides for this workspace (default: monitor)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stackbar: Option<StackbarOverrides>,
+    /// Transparency profile for this workspace (default: global)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transparency: Option<TransparencyProfile>,
 }
 
 impl From<&Workspace> for WorkspaceConfig {
@@ -239,6 +349,18 @@ impl From<&Workspace> for WorkspaceConfig {
             window_container_behaviour_rules: Option::from(window_container_behaviour_rules),
             float_override: *value.float_override(),
             layout_flip: value.layout_flip(),
+            border: value
+                .workspace_config()
+                .as_ref()
+                .and_then(|config| config.border.clone()),
+            stackbar: value
+                .workspace_config()
+                .as_ref()
+                .and_then(|config| config.stackbar.clone()),
+            transparency: value
+                .workspace_config()
+                .as_ref()
+                .and_then(|config| config.transparency),
         }
     }
 }
@@ -263,6 +385,12 @@ pub struct MonitorConfig {
     /// Workspace padding (default: global)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub workspace_padding: Option<i32>,
+    /// Border overrides for this monitor (default: global)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub border: Option<BorderOverrides>,
+    /// Stackbar overrides for this monitor (default: global)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stackbar: Option<StackbarOverrides>,
 }
 
 impl From<&Monitor> for MonitorConfig {
@@ -298,6 +426,8 @@ impl From<&Monitor> for MonitorConfig {
             window_based_work_area_offset_limit: Some(value.window_based_work_area_offset_limit()),
             container_padding,
             workspace_padding,
+            border: value.border().clone(),
+            stackbar: value.stackbar().clone(),
         }
     }
 }
@@ -378,6 +508,15 @@ pub struct StaticConfig {
     /// Individual window transparency ignore rules
     #[serde(skip_serializing_if = "Option::is_none")]
     pub transparency_ignore_rules: Option<Vec<MatchingRule>>,
+    /// Individual window transparency profiles, where the first matching rule is used
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transparency_rules: Option<Vec<TransparencyRule>>,
+    /// Make every window on unfocused monitors transparent (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transparency_dim_inactive_monitors: Option<bool>,
+    /// Alpha value for windows on unfocused monitors when they are dimmed [[0-255]] (default: transparency_alpha)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transparency_inactive_monitor_alpha: Option<u8>,
     /// Global default workspace padding (default: 10)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default_workspace_padding: Option<i32>,
@@ -387,6 +526,9 @@ pub struct StaticConfig {
     /// Monitor and workspace configurations
     #[serde(skip_serializing_if = "Option::is_none")]
     pub monitors: Option<Vec<MonitorConfig>>,
+    /// Workspace rules which pick their target workspace by name, monitor id or policy when they are enforced
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_target_rules: Option<Vec<WorkspaceTargetRule>>,
     /// Which Windows signal to use when hiding windows (default: Cloak)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub window_hiding_behaviour: Option<HidingBehaviour>,
@@ -403,6 +545,9 @@ pub struct StaticConfig {
     /// Identify applications which should be managed as floating windows
     #[serde(skip_serializing_if = "Option::is_none")]
     pub floating_applications: Option<Vec<MatchingRule>>,
+    /// Size and placement rules for floating windows, applied when they are first managed or toggled floating
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub floating_window_placement_rules: Option<Vec<FloatingPlacementRule>>,
     /// Identify border overflow applications
     #[serde(skip_serializing_if = "Option::is_none")]
     pub border_overflow_applications: Option<Vec<MatchingRule>>,
@@ -433,6 +578,10 @@ pub struct StaticConfig {
     /// Theme configuration options
     #[serde(skip_serializing_if = "Option::is_none")]
     pub theme: Option<KomorebiTheme>,
+    /// Automatically switch themes by time of day or to follow the system light/dark mode, taking
+    /// precedence over theme
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theme_schedule: Option<ThemeSchedule>,
     /// Identify applications which are slow to send initial event notifications
     #[serde(skip_serializing_if = "Option::is_none")]
     pub slow_application_identifiers: Option<Vec<MatchingRule>>,
@@ -467,7 +616,7 @@ pub struct AnimationsConfig {
     pub fps: Option<u64>,
 }
 
-#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "palette")]
 pub enum KomorebiTheme {
@@ -535,9 +684,104 @@ pub enum KomorebiTheme {
         #[serde(skip_serializing_if = "Option::is_none")]
         bar_accent: Option<komorebi_themes::Base16Value>,
     },
+    /// A user-defined palette loaded from a file, which is reapplied whenever the file changes
+    Custom {
+        /// Path to a base16 YAML scheme, or to a TOML or JSON file of palettes keyed by name
+        path: PathBuf,
+        /// Name of the palette to use from the file (required for files with multiple palettes)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "follow")]
+pub enum ThemeSchedule {
+    /// Switch between themes at times of day
+    TimeOfDay {
+        /// Themes to apply from a time of day until the next scheduled theme
+        themes: Vec<ScheduledTheme>,
+    },
+    /// Follow the system light/dark mode preference
+    System {
+        /// Theme to apply when the system is in light mode
+        light: KomorebiTheme,
+        /// Theme to apply when the system is in dark mode
+        dark: KomorebiTheme,
+    },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ScheduledTheme {
+    /// Time of day from which to apply the theme (24-hour HH:MM, e.g. "07:00")
+    pub from: String,
+    /// Theme to apply
+    pub theme: KomorebiTheme,
+}
+
+impl ThemeSchedule {
+    /// Ensures that every scheduled time of day is a valid 24-hour HH:MM time
+    pub fn validate(&self) -> Result<()> {
+        if let ThemeSchedule::TimeOfDay { themes } = self {
+            for scheduled in themes {
+                if NaiveTime::parse_from_str(&scheduled.from, "%H:%M").is_err() {
+                    bail!(
+                        "theme scheduled from invalid time \"{}\", expected 24-hour HH:MM",
+                        scheduled.from
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The theme which should be applied at the given time of day according to the schedule
+    pub fn current(&self, now: NaiveTime) -> Option<KomorebiTheme> {
+        match self {
+            ThemeSchedule::TimeOfDay { themes } => {
+                // times are validated when the configuration is loaded
+                let mut themes = themes
+                    .iter()
+                    .filter_map(|scheduled| {
+                        NaiveTime::parse_from_str(&scheduled.from, "%H:%M")
+                            .ok()
+                            .map(|from| (from, &scheduled.theme))
+                    })
+                    .collect::<Vec<_>>();
+
+                themes.sort_by_key(|(from, _)| *from);
+
+                // before the first scheduled time, the last theme of the previous day still applies
+                themes
+                    .iter()
+                    .rev()
+                    .find(|(from, _)| *from <= now)
+                    .or(themes.last())
+                    .map(|(_, theme)| (*theme).clone())
+            }
+            ThemeSchedule::System { light, dark } => {
+                if theme_manager::system_uses_light_theme() {
+                    Some(light.clone())
+                } else {
+                    Some(dark.clone())
+                }
+            }
+        }
+    }
 }
 
 impl StaticConfig {
+    /// The theme which should currently be applied, taking the theme schedule into account
+    pub fn current_theme(&self) -> Option<KomorebiTheme> {
+        match &self.theme_schedule {
+            Some(schedule) => schedule.current(Local::now().time()),
+            None => self.theme.clone(),
+        }
+    }
+
     pub fn end_of_life(raw: &str) {
         let features = vec![
             "focus_follows_mouse",
@@ -639,6 +883,16 @@ pub struct TabsConfig {
     /// Font size
     #[serde(skip_serializing_if = "Option::is_none")]
     pub font_size: Option<i32>,
+    /// Show the application icon on each tab (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icons: Option<bool>,
+    /// Show a close button on each tab (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub close_button: Option<bool>,
+    /// Share the width of the container between the tabs instead of giving each tab a fixed
+    /// width (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_width: Option<bool>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -650,6 +904,13 @@ pub struct StackbarConfig {
     /// Stackbar label
     #[serde(skip_serializing_if = "Option::is_none")]
     pub label: Option<StackbarLabel>,
+    /// Stackbar label template, taking precedence over label
+    /// (e.g. "{index}: {process} — {title:30}")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label_template: Option<LabelTemplate>,
+    /// Stackbar label templates for specific applications, where the first matching rule is used
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label_overrides: Option<Vec<LabelOverride>>,
     /// Stackbar mode
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mode: Option<StackbarMode>,
@@ -670,14 +931,18 @@ impl From<&WindowManager> for StaticConfig {
             None
         } else {
             Option::from(BorderColours {
-                single: Option::from(Colour::from(border_manager::FOCUSED.load(Ordering::SeqCst))),
-                stack: Option::from(Colour::from(border_manager::STACK.load(Ordering::SeqCst))),
-                monocle: Option::from(Colour::from(border_manager::MONOCLE.load(Ordering::SeqCst))),
-                floating: Option::from(Colour::from(
-                    border_manager::FLOATING.load(Ordering::SeqCst),
+                single: Option::from(border_manager::window_kind_border_colour(
+                    WindowKind::Single,
+                )),
+                stack: Option::from(border_manager::window_kind_border_colour(WindowKind::Stack)),
+                monocle: Option::from(border_manager::window_kind_border_colour(
+                    WindowKind::Monocle,
                 )),
-                unfocused: Option::from(Colour::from(
-                    border_manager::UNFOCUSED.load(Ordering::SeqCst),
+                floating: Option::from(border_manager::window_kind_border_colour(
+                    WindowKind::Floating,
+                )),
+                unfocused: Option::from(border_manager::window_kind_border_colour(
+                    WindowKind::Unfocused,
                 )),
             })
         };
@@ -710,6 +975,18 @@ impl From<&WindowManager> for StaticConfig {
                 transparency_manager::TRANSPARENCY_ALPHA.load(Ordering::SeqCst),
             ),
             transparency_ignore_rules: None,
+            transparency_rules: Option::from(
+                transparency_manager::TRANSPARENCY_RULES
+                    .lock()
+                    .rules()
+                    .to_vec(),
+            ),
+            transparency_dim_inactive_monitors: Option::from(
+                transparency_manager::TRANSPARENCY_DIM_INACTIVE_MONITORS.load(Ordering::SeqCst),
+            ),
+            transparency_inactive_monitor_alpha: Option::from(
+                transparency_manager::TRANSPARENCY_INACTIVE_MONITOR_ALPHA.load(Ordering::SeqCst),
+            ),
             border_style: Option::from(STYLE.load()),
             border_z_order: None,
             border_implementation: Option::from(IMPLEMENTATION.load()),
@@ -720,16 +997,20 @@ impl From<&WindowManager> for StaticConfig {
                 DEFAULT_CONTAINER_PADDING.load(Ordering::SeqCst),
             ),
             monitors: Option::from(monitors),
+            workspace_target_rules: Option::from(WORKSPACE_TARGET_RULES.lock().clone()),
             window_hiding_behaviour: Option::from(*HIDING_BEHAVIOUR.lock()),
             global_work_area_offset: value.work_area_offset,
             ignore_rules: None,
             floating_applications: None,
+            floating_window_placement_rules: Option::from(
+                FLOATING_WINDOW_PLACEMENT_RULES.lock().clone(),
+            ),
             manage_rules: None,
             border_overflow_applications: None,
             tray_and_multi_window_applications: None,
             layered_applications: None,
             object_name_change_applications: Option::from(
-                OBJECT_NAME_CHANGE_ON_LAUNCH.lock().clone(),
+                OBJECT_NAME_CHANGE_ON_LAUNCH.lock().to_vec(),
             ),
             object_name_change_title_ignore_list: Option::from(
                 OBJECT_NAME_CHANGE_TITLE_IGNORE_LIST
@@ -744,12 +1025,15 @@ impl From<&WindowManager> for StaticConfig {
             stackbar: None,
             animation: None,
             theme: None,
+            theme_schedule: None,
             slow_application_compensation_time: Option::from(
                 SLOW_APPLICATION_COMPENSATION_TIME.load(Ordering::SeqCst),
             ),
-            slow_application_identifiers: Option::from(SLOW_APPLICATION_IDENTIFIERS.lock().clone()),
+            slow_application_identifiers: Option::from(
+                SLOW_APPLICATION_IDENTIFIERS.lock().to_vec(),
+            ),
             bar_configurations: None,
-            remove_titlebar_applications: Option::from(NO_TITLEBAR.lock().clone()),
+            remove_titlebar_applications: Option::from(NO_TITLEBAR.lock().to_vec()),
             floating_window_aspect_ratio: Option::from(*FLOATING_WINDOW_TOGGLE_ASPECT_RATIO.lock()),
         }
     }
@@ -841,24 +1125,24 @@ impl StaticConfig {
         }
 
         if let Some(colours) = &self.border_colours {
-            if let Some(single) = colours.single {
-                border_manager::FOCUSED.store(u32::from(single), Ordering::SeqCst);
+            if let Some(single) = &colours.single {
+                border_manager::set_window_kind_colour(WindowKind::Single, single);
             }
 
-            if let Some(stack) = colours.stack {
-                border_manager::STACK.store(u32::from(stack), Ordering::SeqCst);
+            if let Some(stack) = &colours.stack {
+                border_manager::set_window_kind_colour(WindowKind::Stack, stack);
             }
 
-            if let Some(monocle) = colours.monocle {
-                border_manager::MONOCLE.store(u32::from(monocle), Ordering::SeqCst);
+            if let Some(monocle) = &colours.monocle {
+                border_manager::set_window_kind_colour(WindowKind::Monocle, monocle);
             }
 
-            if let Some(floating) = colours.floating {
-                border_manager::FLOATING.store(u32::from(floating), Ordering::SeqCst);
+            if let Some(floating) = &colours.floating {
+                border_manager::set_window_kind_colour(WindowKind::Floating, floating);
             }
 
-            if let Some(unfocused) = colours.unfocused {
-                border_manager::UNFOCUSED.store(u32::from(unfocused), Ordering::SeqCst);
+            if let Some(unfocused) = &colours.unfocused {
+                border_manager::set_window_kind_colour(WindowKind::Unfocused, unfocused);
             }
         }
 
@@ -891,6 +1175,18 @@ impl StaticConfig {
             .store(self.transparency.unwrap_or(false), Ordering::SeqCst);
         transparency_manager::TRANSPARENCY_ALPHA
             .store(self.transparency_alpha.unwrap_or(200), Ordering::SeqCst);
+        transparency_manager::TRANSPARENCY_DIM_INACTIVE_MONITORS.store(
+            self.transparency_dim_inactive_monitors.unwrap_or(false),
+            Ordering::SeqCst,
+        );
+        transparency_manager::TRANSPARENCY_INACTIVE_MONITOR_ALPHA.store(
+            self.transparency_inactive_monitor_alpha
+                .or(self.transparency_alpha)
+                .unwrap_or(200),
+            Ordering::SeqCst,
+        );
+        *transparency_manager::TRANSPARENCY_RULES.lock() =
+            TransparencyRules::new(self.transparency_rules.clone().unwrap_or_default());
 
         let mut ignore_identifiers = IGNORE_IDENTIFIERS.lock();
         let mut regex_identifiers = REGEX_IDENTIFIERS.lock();
@@ -912,6 +1208,21 @@ impl StaticConfig {
             populate_rules(rules, &mut floating_applications, &mut regex_identifiers)?;
         }
 
+        for placement_rule in self.floating_window_placement_rules.iter_mut().flatten() {
+            populate_rule_identifiers(&mut placement_rule.matching_rule, &mut regex_identifiers)?;
+        }
+
+        *FLOATING_WINDOW_PLACEMENT_RULES.lock() = self
+            .floating_window_placement_rules
+            .clone()
+            .unwrap_or_default();
+
+        for target_rule in self.workspace_target_rules.iter_mut().flatten() {
+            populate_rule_identifiers(&mut target_rule.matching_rule, &mut regex_identifiers)?;
+        }
+
+        *WORKSPACE_TARGET_RULES.lock() = self.workspace_target_rules.clone().unwrap_or_default();
+
         if let Some(rules) = &mut self.manage_rules {
             populate_rules(rules, &mut manage_identifiers, &mut regex_identifiers)?;
         }
@@ -972,6 +1283,11 @@ impl StaticConfig {
                 STACKBAR_LABEL.store(*label);
             }
 
+            *STACKBAR_LABELLER.lock() = WindowLabeller::new(
+                stackbar.label_template.clone(),
+                stackbar.label_overrides.clone().unwrap_or_default(),
+            );
+
             if let Some(mode) = &stackbar.mode {
                 STACKBAR_MODE.store(*mode);
             }
@@ -996,13 +1312,19 @@ impl StaticConfig {
 
                 STACKBAR_FONT_SIZE.store(tabs.font_size.unwrap_or(0), Ordering::SeqCst);
                 *STACKBAR_FONT_FAMILY.lock() = tabs.font_family.clone();
+                STACKBAR_TAB_ICONS.store(tabs.icons.unwrap_or(false), Ordering::SeqCst);
+                STACKBAR_TAB_CLOSE_BUTTON
+                    .store(tabs.close_button.unwrap_or(false), Ordering::SeqCst);
+                STACKBAR_TAB_AUTO_WIDTH.store(tabs.auto_width.unwrap_or(false), Ordering::SeqCst);
             }
         }
 
-        if let Some(theme) = &self.theme {
-            theme_manager::send_notification(*theme);
+        if let Some(theme) = self.current_theme() {
+            theme_manager::send_notification(theme);
         }
 
+        theme_manager::set_schedule(self.theme_schedule.clone());
+
         if let Some(path) = &self.app_specific_configuration_path {
             match path.extension() {
                 None => {}
@@ -1152,6 +1474,23 @@ impl StaticConfig {
         let content = std::fs::read_to_string(path)?;
         let mut value: Self = serde_json::from_str(&content)?;
 
+        if let Some(schedule) = &value.theme_schedule {
+            schedule.validate()?;
+        }
+
+        if let Some(colours) = &value.border_colours {
+            colours.validate()?;
+        }
+
+        for monitor in value.monitors.iter().flatten() {
+            let workspaces = monitor.workspaces.iter().filter_map(|w| w.border.as_ref());
+            for border in monitor.border.iter().chain(workspaces) {
+                if let Some(colours) = &border.colours {
+                    colours.validate()?;
+                }
+            }
+        }
+
         if let Some(path) = &mut value.app_specific_configuration_path {
             *path = resolve_home_path(&*path)?;
         }
@@ -1331,6 +1670,8 @@ impl StaticConfig {
                 );
                 monitor.set_container_padding(monitor_config.container_padding);
                 monitor.set_workspace_padding(monitor_config.workspace_padding);
+                monitor.set_border(monitor_config.border.clone());
+                monitor.set_stackbar(monitor_config.stackbar.clone());
 
                 monitor.update_workspaces_globals(offset);
                 for (j, ws) in monitor.workspaces_mut().iter_mut().enumerate() {
@@ -1416,6 +1757,8 @@ impl StaticConfig {
                     );
                     m.set_container_padding(monitor_config.container_padding);
                     m.set_workspace_padding(monitor_config.workspace_padding);
+                    m.set_border(monitor_config.border.clone());
+                    m.set_stackbar(monitor_config.stackbar.clone());
 
                     m.update_workspaces_globals(offset);
 
@@ -1502,6 +1845,8 @@ impl StaticConfig {
                 );
                 monitor.set_container_padding(monitor_config.container_padding);
                 monitor.set_workspace_padding(monitor_config.workspace_padding);
+                monitor.set_border(monitor_config.border.clone());
+                monitor.set_stackbar(monitor_config.stackbar.clone());
 
                 monitor.update_workspaces_globals(offset);
 
@@ -1588,6 +1933,8 @@ impl StaticConfig {
                     );
                     m.set_container_padding(monitor_config.container_padding);
                     m.set_workspace_padding(monitor_config.workspace_padding);
+                    m.set_border(monitor_config.border.clone());
+                    m.set_stackbar(monitor_config.stackbar.clone());
 
                     m.update_workspaces_globals(offset);
 
@@ -1660,7 +2007,7 @@ impl StaticConfig {
 
 fn populate_option(
     entry: &mut ApplicationConfiguration,
-    identifiers: &mut Vec<MatchingRule>,
+    identifiers: &mut RuleIndex,
     regex_identifiers: &mut HashMap<String, Regex>,
 ) -> Result<()> {
     if entry.identifier.matching_strategy.is_none() {
@@ -1686,36 +2033,31 @@ fn populate_option(
 
 fn populate_rules(
     matching_rules: &mut Vec<MatchingRule>,
-    identifiers: &mut Vec<MatchingRule>,
+    identifiers: &mut RuleIndex,
     regex_identifiers: &mut HashMap<String, Regex>,
 ) -> Result<()> {
     for matching_rule in matching_rules {
         if !identifiers.contains(matching_rule) {
-            match matching_rule {
-                MatchingRule::Simple(simple) => {
-                    if simple.matching_strategy.is_none() {
-                        simple.matching_strategy = Option::from(MatchingStrategy::Legacy);
-                    }
+            populate_rule_identifiers(matching_rule, regex_identifiers)?;
+            identifiers.push(matching_rule.clone());
+        }
+    }
 
-                    if matches!(simple.matching_strategy, Some(MatchingStrategy::Regex)) {
-                        let re = Regex::new(&simple.id)?;
-                        regex_identifiers.insert(simple.id.clone(), re);
-                    }
-                }
-                MatchingRule::Composite(composite) => {
-                    for rule in composite {
-                        if rule.matching_strategy.is_none() {
-                            rule.matching_strategy = Option::from(MatchingStrategy::Legacy);
-                        }
+    Ok(())
+}
 
-                        if matches!(rule.matching_strategy, Some(MatchingStrategy::Regex)) {
-                            let re = Regex::new(&rule.id)?;
-                            regex_identifiers.insert(rule.id.clone(), re);
-                        }
-                    }
-                }
-            }
-            identifiers.push(matching_rule.clone());
+fn populate_rule_identifiers(
+    matching_rule: &mut MatchingRule,
+    regex_identifiers: &mut HashMap<String, Regex>,
+) -> Result<()> {
+    for identifier in matching_rule.identifiers_mut() {
+        if identifier.matching_strategy.is_none() {
+            identifier.matching_strategy = Option::from(MatchingStrategy::Legacy);
+        }
+
+        if matches!(identifier.matching_strategy, Some(MatchingStrategy::Regex)) {
+            let re = Regex::new(&identifier.id)?;
+            regex_identifiers.insert(identifier.id.clone(), re);
         }
     }
 
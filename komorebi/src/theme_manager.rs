@@ -7,12 +7,26 @@ use crate::stackbar_manager::STACKBAR_TAB_BACKGROUND_COLOUR;
 use crate::stackbar_manager::STACKBAR_UNFOCUSED_TEXT_COLOUR;
 use crate::Colour;
 use crate::KomorebiTheme;
+use crate::SocketMessage;
+use crate::ThemeSchedule;
+use crate::DATA_DIR;
+use chrono::Local;
 use crossbeam_channel::Receiver;
 use crossbeam_channel::Sender;
 use crossbeam_utils::atomic::AtomicCell;
+use hotwatch::EventKind;
+use hotwatch::Hotwatch;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use std::io::Write;
 use std::ops::Deref;
+use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::sync::OnceLock;
+use std::time::Duration;
+use uds_windows::UnixStream;
+use winreg::enums::HKEY_CURRENT_USER;
+use winreg::RegKey;
 
 pub struct Notification(KomorebiTheme);
 
@@ -28,6 +42,15 @@ impl Deref for Notification {
 
 static CHANNEL: OnceLock<(Sender<Notification>, Receiver<Notification>)> = OnceLock::new();
 
+/// Set when the theme schedule changes so that the scheduled theme is reapplied
+static SCHEDULE_UPDATED: AtomicBool = AtomicBool::new(false);
+
+lazy_static! {
+    /// The custom theme whose palette file is being watched for changes
+    static ref PALETTE_WATCHER: Mutex<Option<(KomorebiTheme, Hotwatch)>> = Mutex::new(None);
+    static ref THEME_SCHEDULE: Mutex<Option<ThemeSchedule>> = Mutex::new(None);
+}
+
 pub fn channel() -> &'static (Sender<Notification>, Receiver<Notification>) {
     CHANNEL.get_or_init(|| crossbeam_channel::bounded(20))
 }
@@ -62,6 +85,113 @@ pub fn listen_for_notifications() {
     });
 }
 
+pub fn set_schedule(schedule: Option<ThemeSchedule>) {
+    *THEME_SCHEDULE.lock() = schedule;
+    SCHEDULE_UPDATED.store(true, Ordering::SeqCst);
+}
+
+pub fn listen_for_schedule() {
+    std::thread::spawn(move || loop {
+        match handle_schedule() {
+            Ok(()) => {
+                tracing::warn!("restarting finished thread");
+            }
+            Err(error) => {
+                tracing::warn!("restarting failed thread: {}", error);
+            }
+        }
+    });
+}
+
+/// Whether the system light/dark mode preference for apps is set to light
+pub fn system_uses_light_theme() -> bool {
+    RegKey::predef(HKEY_CURRENT_USER)
+        .open_subkey(r"SOFTWARE\Microsoft\Windows\CurrentVersion\Themes\Personalize")
+        .and_then(|personalize| personalize.get_value::<u32, _>("AppsUseLightTheme"))
+        .is_ok_and(|light| light == 1)
+}
+
+/// Applies a theme through komorebi.sock rather than sending a notification directly, so that
+/// subscribers such as komorebi-bar are notified of the theme change too
+fn send_theme_message(theme: &KomorebiTheme) -> color_eyre::Result<()> {
+    let socket = DATA_DIR.join("komorebi.sock");
+    let mut stream = UnixStream::connect(socket)?;
+    stream.write_all(&SocketMessage::Theme(theme.clone()).as_bytes()?)?;
+
+    Ok(())
+}
+
+/// Applies the scheduled theme whenever it changes, either because a scheduled time of day has
+/// been reached or because the system light/dark mode preference has changed
+fn handle_schedule() -> color_eyre::Result<()> {
+    tracing::info!("evaluating theme schedule");
+
+    let mut applied: Option<KomorebiTheme> = None;
+
+    loop {
+        std::thread::sleep(Duration::from_secs(1));
+
+        if SCHEDULE_UPDATED.swap(false, Ordering::SeqCst) {
+            // the updated configuration has already applied the scheduled theme
+            applied = THEME_SCHEDULE
+                .lock()
+                .as_ref()
+                .and_then(|schedule| schedule.current(Local::now().time()));
+            continue;
+        }
+
+        let scheduled = THEME_SCHEDULE
+            .lock()
+            .as_ref()
+            .and_then(|schedule| schedule.current(Local::now().time()));
+
+        if let Some(theme) = scheduled {
+            if applied.as_ref() != Some(&theme) {
+                send_theme_message(&theme)?;
+                tracing::info!("applied scheduled theme");
+                applied = Some(theme);
+            }
+        }
+    }
+}
+
+/// Watches the palette file of a custom theme, reapplying the theme through komorebi.sock whenever
+/// the file changes so that subscribers are notified of the updated theme too
+fn watch_palette(theme: &KomorebiTheme) -> color_eyre::Result<()> {
+    let mut watcher = PALETTE_WATCHER.lock();
+
+    let KomorebiTheme::Custom { path, .. } = theme else {
+        *watcher = None;
+        return Ok(());
+    };
+
+    if watcher
+        .as_ref()
+        .is_some_and(|(watched, _)| watched == theme)
+    {
+        return Ok(());
+    }
+
+    let mut hotwatch = Hotwatch::new()?;
+    let watched = theme.clone();
+
+    hotwatch.watch(path, move |event| match event.kind {
+        // Editing in Notepad sends a NoticeWrite while editing in (Neo)Vim sends
+        // a NoticeRemove, presumably because of the use of swap files?
+        EventKind::Modify(_) | EventKind::Remove(_) => {
+            if let Err(error) = send_theme_message(&watched) {
+                tracing::error!("could not reapply updated palette: {error}");
+            }
+        }
+        _ => {}
+    })?;
+
+    tracing::info!("watching palette for changes: {}", path.display());
+    *watcher = Some((theme.clone(), hotwatch));
+
+    Ok(())
+}
+
 pub fn handle_notifications() -> color_eyre::Result<()> {
     tracing::info!("listening");
 
@@ -70,6 +200,10 @@ pub fn handle_notifications() -> color_eyre::Result<()> {
     for notification in receiver {
         let theme = &notification.0;
 
+        if let Err(error) = watch_palette(theme) {
+            tracing::error!("could not watch palette for changes: {error}");
+        }
+
         let (
             single_border,
             stack_border,
@@ -190,8 +324,30 @@ pub fn handle_notifications() -> color_eyre::Result<()> {
                     stackbar_background,
                 )
             }
+            KomorebiTheme::Custom { path, name } => {
+                let colours = match komorebi_themes::Palette::load(path, name.as_deref()) {
+                    Ok(palette) => palette.colours(),
+                    Err(error) => {
+                        tracing::error!("could not load palette from {}: {error}", path.display());
+                        continue;
+                    }
+                };
+
+                (
+                    colours.single_border,
+                    colours.stack_border,
+                    colours.monocle_border,
+                    colours.floating_border,
+                    colours.unfocused_border,
+                    colours.stackbar_focused_text,
+                    colours.stackbar_unfocused_text,
+                    colours.stackbar_background,
+                )
+            }
         };
 
+        // themes only have solid border colours
+        border_manager::GRADIENTS.lock().clear();
         border_manager::FOCUSED.store(u32::from(Colour::from(single_border)), Ordering::SeqCst);
         border_manager::MONOCLE.store(u32::from(Colour::from(monocle_border)), Ordering::SeqCst);
         border_manager::STACK.store(u32::from(Colour::from(stack_border)), Ordering::SeqCst);
@@ -3,13 +3,23 @@
 use crossbeam_channel::Receiver;
 use crossbeam_channel::Sender;
 use crossbeam_utils::atomic::AtomicConsume;
+use lazy_static::lazy_static;
 use parking_lot::Mutex;
+use regex::Regex;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::HashMap;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicU8;
 use std::sync::Arc;
 use std::sync::OnceLock;
 
+use crate::core::config_generation::MatchingRule;
+use crate::core::config_generation::MatchingStrategy;
+use crate::rule_index::RuleIndex;
 use crate::should_act;
+use crate::window::matches_rule;
+use crate::RuleTarget;
 use crate::Window;
 use crate::WindowManager;
 use crate::WindowsApi;
@@ -18,9 +28,214 @@ use crate::TRANSPARENCY_BLACKLIST;
 
 pub static TRANSPARENCY_ENABLED: AtomicBool = AtomicBool::new(false);
 pub static TRANSPARENCY_ALPHA: AtomicU8 = AtomicU8::new(200);
+pub static TRANSPARENCY_DIM_INACTIVE_MONITORS: AtomicBool = AtomicBool::new(false);
+pub static TRANSPARENCY_INACTIVE_MONITOR_ALPHA: AtomicU8 = AtomicU8::new(200);
+
+lazy_static! {
+    pub static ref TRANSPARENCY_RULES: Mutex<TransparencyRules> =
+        Mutex::new(TransparencyRules::default());
+}
 
 static KNOWN_HWNDS: OnceLock<Mutex<Vec<isize>>> = OnceLock::new();
 
+/// Alpha values [[0-255]] for windows depending on their focus state. Unset values fall back to
+/// the workspace profile, and then to the global transparency settings
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TransparencyProfile {
+    /// Alpha value for the focused window (default: 255, or for floating windows, left unchanged
+    /// unless floating is set)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focused: Option<u8>,
+    /// Alpha value for unfocused tiled windows (default: global transparency_alpha)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unfocused: Option<u8>,
+    /// Alpha value for unfocused floating windows (default: 255 if focused is set, otherwise left
+    /// unchanged)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub floating: Option<u8>,
+    /// Alpha value for the monocle window on the focused monitor (default: 255)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub monocle: Option<u8>,
+}
+
+impl TransparencyProfile {
+    fn alpha(&self, kind: TransparencyKind) -> Option<u8> {
+        match kind {
+            TransparencyKind::Focused => self.focused,
+            TransparencyKind::Unfocused => self.unfocused,
+            TransparencyKind::Floating => self.floating,
+            TransparencyKind::Monocle => self.monocle,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TransparencyKind {
+    Focused,
+    Unfocused,
+    Floating,
+    Monocle,
+}
+
+impl TransparencyKind {
+    fn default_alpha(self) -> u8 {
+        match self {
+            Self::Unfocused => TRANSPARENCY_ALPHA.load_consume(),
+            Self::Focused | Self::Floating | Self::Monocle => u8::MAX,
+        }
+    }
+}
+
+/// Resolves the alpha value of a window, where the alpha value of a matching rule takes
+/// precedence over the workspace profile, which takes precedence over the default, and a dimmed
+/// monitor limits the alpha value to at most `dim`. Returns `None` if none of them set a value.
+pub fn resolve_alpha(
+    rule: Option<u8>,
+    workspace: Option<u8>,
+    default: Option<u8>,
+    dim: Option<u8>,
+) -> Option<u8> {
+    let alpha = rule.or(workspace).or(default)?;
+
+    match dim {
+        Some(dim) => Some(alpha.min(dim)),
+        None => Some(alpha),
+    }
+}
+
+/// The alpha value of a floating window when neither a rule nor the workspace profile sets one for
+/// its focus state. Once either of the floating values is set for a window, the other focus state
+/// is opaque. Otherwise the window is left as it is, unless its monitor is dimmed.
+pub fn floating_default_alpha(
+    rule: Option<&TransparencyProfile>,
+    workspace: &TransparencyProfile,
+    dim: Option<u8>,
+) -> Option<u8> {
+    let sets_floating =
+        |profile: &TransparencyProfile| profile.focused.is_some() || profile.floating.is_some();
+
+    if rule.is_some_and(sets_floating) || sets_floating(workspace) {
+        Some(u8::MAX)
+    } else {
+        dim
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TransparencyRule {
+    /// Applications which use this profile
+    pub matching_rule: MatchingRule,
+    /// Transparency profile for these applications
+    pub profile: TransparencyProfile,
+}
+
+/// Per-application transparency profiles, where the first rule which matches a window is used
+#[derive(Clone, Debug, Default)]
+pub struct TransparencyRules {
+    rules: Vec<TransparencyRule>,
+    regex_identifiers: HashMap<String, Regex>,
+}
+
+impl TransparencyRules {
+    pub fn new(rules: Vec<TransparencyRule>) -> Self {
+        let mut regex_identifiers = HashMap::new();
+
+        for rule in &rules {
+            for identifier in rule.matching_rule.identifiers() {
+                if matches!(identifier.matching_strategy, Some(MatchingStrategy::Regex)) {
+                    match Regex::new(&identifier.id) {
+                        Ok(re) => {
+                            regex_identifiers.insert(identifier.id.clone(), re);
+                        }
+                        Err(error) => {
+                            tracing::error!("invalid regex in transparency rule: {error}")
+                        }
+                    }
+                }
+            }
+        }
+
+        Self {
+            rules,
+            regex_identifiers,
+        }
+    }
+
+    pub fn rules(&self) -> &[TransparencyRule] {
+        &self.rules
+    }
+
+    fn profile(&self, target: &RuleTarget) -> Option<&TransparencyProfile> {
+        self.rules
+            .iter()
+            .find(|rule| matches_rule(target, &rule.matching_rule, &self.regex_identifiers))
+            .map(|rule| &rule.profile)
+    }
+}
+
+/// Everything needed to work out the alpha value of the windows on one workspace
+struct AlphaResolver<'a> {
+    rules: &'a TransparencyRules,
+    workspace: TransparencyProfile,
+    blacklist: &'a RuleIndex,
+    regex_identifiers: &'a HashMap<String, Regex>,
+    /// The alpha value which every window is limited to when its monitor is dimmed
+    dim: Option<u8>,
+}
+
+impl AlphaResolver<'_> {
+    /// The alpha value for this kind of window, falling back to `default` when neither a rule nor
+    /// the workspace profile sets one
+    fn alpha(&self, window: Window, kind: TransparencyKind, default: Option<u8>) -> Option<u8> {
+        let target = RuleTarget::try_from(window).ok();
+
+        if let Some(target) = &target {
+            if !self.blacklist.is_empty()
+                && should_act(target, self.blacklist, self.regex_identifiers).is_some()
+            {
+                return Some(u8::MAX);
+            }
+        }
+
+        let rule = target
+            .as_ref()
+            .and_then(|target| self.rules.profile(target))
+            .and_then(|profile| profile.alpha(kind));
+
+        resolve_alpha(rule, self.workspace.alpha(kind), default, self.dim)
+    }
+
+    /// The default alpha value of a floating window, see [`floating_default_alpha`]
+    fn floating_default(&self, window: Window) -> Option<u8> {
+        let target = RuleTarget::try_from(window).ok();
+        let rule = target
+            .as_ref()
+            .and_then(|target| self.rules.profile(target));
+
+        floating_default_alpha(rule, &self.workspace, self.dim)
+    }
+
+    /// Applies the alpha value for this kind of window, and returns whether the window was left
+    /// transparent. Windows without an alpha value are left as they are.
+    fn apply(&self, window: Window, kind: TransparencyKind, default: Option<u8>) -> bool {
+        let Some(alpha) = self.alpha(window, kind, default) else {
+            return false;
+        };
+
+        let hwnd = window.hwnd;
+
+        match window.set_alpha(alpha) {
+            Ok(()) => alpha < u8::MAX,
+            Err(error) => {
+                tracing::error!("failed to set the alpha of window {hwnd} to {alpha}: {error}");
+                false
+            }
+        }
+    }
+}
+
 pub struct Notification;
 
 static CHANNEL: OnceLock<(Sender<Notification>, Receiver<Notification>)> = OnceLock::new();
@@ -85,9 +300,15 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
         let state = wm.lock();
 
         let focused_monitor_idx = state.focused_monitor_idx();
+        let dim_inactive_monitors = TRANSPARENCY_DIM_INACTIVE_MONITORS.load_consume();
+
+        let transparency_rules = TRANSPARENCY_RULES.lock();
+        let transparency_blacklist = TRANSPARENCY_BLACKLIST.lock();
+        let regex_identifiers = REGEX_IDENTIFIERS.lock();
 
         'monitors: for (monitor_idx, m) in state.monitors.elements().iter().enumerate() {
             let focused_workspace_idx = m.focused_workspace_idx();
+            let is_focused_monitor = monitor_idx == focused_monitor_idx;
 
             'workspaces: for (workspace_idx, ws) in m.workspaces().iter().enumerate() {
                 // Only operate on the focused workspace of each monitor
@@ -103,21 +324,41 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                     continue 'workspaces;
                 }
 
-                // Monocle container is never transparent
+                let resolver = AlphaResolver {
+                    rules: &transparency_rules,
+                    workspace: ws.transparency_profile(),
+                    blacklist: &transparency_blacklist,
+                    regex_identifiers: &regex_identifiers,
+                    dim: (dim_inactive_monitors && !is_focused_monitor)
+                        .then(|| TRANSPARENCY_INACTIVE_MONITOR_ALPHA.load_consume()),
+                };
+
+                // Floating windows use their own alpha value unless they are focused, and are
+                // only changed when a rule or the workspace profile sets an alpha value for them
+                // or their monitor is dimmed
+                for window in ws.floating_windows() {
+                    let kind = if window.is_focused() {
+                        TransparencyKind::Focused
+                    } else {
+                        TransparencyKind::Floating
+                    };
+
+                    if resolver.apply(*window, kind, resolver.floating_default(*window)) {
+                        known_hwnds.lock().push(window.hwnd);
+                    }
+                }
+
+                // The monocle window is only treated as unfocused on unfocused monitors
                 if let Some(monocle) = ws.monocle_container() {
                     if let Some(window) = monocle.focused_window() {
-                        if monitor_idx == focused_monitor_idx {
-                            if let Err(error) = window.opaque() {
-                                let hwnd = window.hwnd;
-                                tracing::error!(
-                                    "failed to make monocle window {hwnd} opaque: {error}"
-                                )
-                            }
-                        } else if let Err(error) = window.transparent() {
-                            let hwnd = window.hwnd;
-                            tracing::error!(
-                                "failed to make monocle window {hwnd} transparent: {error}"
-                            )
+                        let kind = if is_focused_monitor {
+                            TransparencyKind::Monocle
+                        } else {
+                            TransparencyKind::Unfocused
+                        };
+
+                        if resolver.apply(*window, kind, Some(kind.default_alpha())) {
+                            known_hwnds.lock().push(window.hwnd);
                         }
                     }
 
@@ -136,76 +377,29 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                     continue 'monitors;
                 }
 
-                let transparency_blacklist = TRANSPARENCY_BLACKLIST.lock();
-                let regex_identifiers = REGEX_IDENTIFIERS.lock();
-
                 for (idx, c) in ws.containers().iter().enumerate() {
                     // Update the transparency for all containers on this workspace
 
-                    // If the window is not focused on the current workspace, or isn't on the focused monitor
-                    // make it transparent
-                    #[allow(clippy::collapsible_else_if)]
-                    if idx != ws.focused_container_idx() || monitor_idx != focused_monitor_idx {
-                        let focused_window_idx = c.focused_window_idx();
-                        for (window_idx, window) in c.windows().iter().enumerate() {
-                            if window_idx == focused_window_idx {
-                                let mut should_make_transparent = true;
-                                if !transparency_blacklist.is_empty() {
-                                    if let (Ok(title), Ok(exe_name), Ok(class), Ok(path)) = (
-                                        window.title(),
-                                        window.exe(),
-                                        window.class(),
-                                        window.path(),
-                                    ) {
-                                        let is_blacklisted = should_act(
-                                            &title,
-                                            &exe_name,
-                                            &class,
-                                            &path,
-                                            &transparency_blacklist,
-                                            &regex_identifiers,
-                                        )
-                                        .is_some();
-
-                                        should_make_transparent = !is_blacklisted;
-                                    }
-                                }
-
-                                if should_make_transparent {
-                                    match window.transparent() {
-                                        Err(error) => {
-                                            let hwnd = foreground_hwnd;
-                                            tracing::error!("failed to make unfocused window {hwnd} transparent: {error}" )
-                                        }
-                                        Ok(..) => {
-                                            known_hwnds.lock().push(window.hwnd);
-                                        }
-                                    }
-                                }
-                            } else {
-                                // just in case, this is useful when people are clicking around
-                                // on unfocused stackbar tabs
-                                known_hwnds.lock().push(window.hwnd);
-                            }
-                        }
-                    // Otherwise, make it opaque
+                    // If the window is not focused on the current workspace, or isn't on the
+                    // focused monitor, it is unfocused
+                    let kind = if idx != ws.focused_container_idx() || !is_focused_monitor {
+                        TransparencyKind::Unfocused
                     } else {
-                        let focused_window_idx = c.focused_window_idx();
-                        for (window_idx, window) in c.windows().iter().enumerate() {
-                            if window_idx != focused_window_idx {
+                        TransparencyKind::Focused
+                    };
+
+                    let focused_window_idx = c.focused_window_idx();
+                    for (window_idx, window) in c.windows().iter().enumerate() {
+                        if window_idx == focused_window_idx {
+                            if resolver.apply(*window, kind, Some(kind.default_alpha())) {
                                 known_hwnds.lock().push(window.hwnd);
-                            } else {
-                                if let Err(error) =
-                                    c.focused_window().copied().unwrap_or_default().opaque()
-                                {
-                                    let hwnd = foreground_hwnd;
-                                    tracing::error!(
-                                        "failed to make focused window {hwnd} opaque: {error}"
-                                    )
-                                }
                             }
+                        } else {
+                            // just in case, this is useful when people are clicking around
+                            // on unfocused stackbar tabs
+                            known_hwnds.lock().push(window.hwnd);
                         }
-                    };
+                    }
                 }
             }
         }
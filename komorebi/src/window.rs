@@ -1,6 +1,8 @@
 use crate::animation::lerp::Lerp;
+use crate::animation::lerp::SpringMotion;
 use crate::animation::prefix::new_animation_key;
 use crate::animation::prefix::AnimationPrefix;
+use crate::animation::render_dispatcher::Frame;
 use crate::animation::AnimationEngine;
 use crate::animation::RenderDispatcher;
 use crate::animation::ANIMATION_DURATION_GLOBAL;
@@ -10,6 +12,8 @@ use crate::animation::ANIMATION_ENABLED_PER_ANIMATION;
 use crate::animation::ANIMATION_MANAGER;
 use crate::animation::ANIMATION_STYLE_GLOBAL;
 use crate::animation::ANIMATION_STYLE_PER_ANIMATION;
+use crate::animation::MOVEMENT_VELOCITY;
+use crate::animation::WORKSPACE_TRANSITION_RECTS;
 use crate::border_manager;
 use crate::com::SetCloak;
 use crate::core::config_generation::IdWithIdentifier;
@@ -19,6 +23,7 @@ use crate::core::ApplicationIdentifier;
 use crate::core::HidingBehaviour;
 use crate::core::Rect;
 use crate::focus_manager;
+use crate::rule_index::RuleIndex;
 use crate::stackbar_manager;
 use crate::styles::ExtendedWindowStyle;
 use crate::styles::WindowStyle;
@@ -28,6 +33,7 @@ use crate::windows_api;
 use crate::windows_api::WindowsApi;
 use crate::AnimationStyle;
 use crate::FLOATING_APPLICATIONS;
+use crate::FLOATING_WINDOW_PLACEMENT_RULES;
 use crate::FLOATING_WINDOW_TOGGLE_ASPECT_RATIO;
 use crate::HIDDEN_HWNDS;
 use crate::HIDING_BEHAVIOUR;
@@ -40,6 +46,7 @@ use crate::REGEX_IDENTIFIERS;
 use crate::SLOW_APPLICATION_COMPENSATION_TIME;
 use crate::SLOW_APPLICATION_IDENTIFIERS;
 use crate::WSL2_UI_PROCESSES;
+use bitflags::Flags;
 use color_eyre::eyre;
 use color_eyre::Result;
 use crossbeam_utils::atomic::AtomicConsume;
@@ -48,6 +55,7 @@ use serde::ser::SerializeStruct;
 use serde::Deserialize;
 use serde::Serialize;
 use serde::Serializer;
+use std::cell::OnceCell;
 use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::fmt::Display;
@@ -59,6 +67,12 @@ use std::thread;
 use std::time::Duration;
 use strum::Display;
 use strum::EnumString;
+use sysinfo::Pid;
+use sysinfo::Process;
+use sysinfo::ProcessRefreshKind;
+use sysinfo::ProcessesToUpdate;
+use sysinfo::System;
+use sysinfo::UpdateKind;
 use windows::Win32::Foundation::HWND;
 
 pub static MINIMUM_WIDTH: AtomicI32 = AtomicI32::new(0);
@@ -166,6 +180,8 @@ struct MovementRenderDispatcher {
     target_rect: Rect,
     top: bool,
     style: AnimationStyle,
+    /// Velocity of the window when the animation starts, in pixels per second
+    velocity: Rect,
 }
 
 impl MovementRenderDispatcher {
@@ -184,6 +200,8 @@ impl MovementRenderDispatcher {
             target_rect,
             top,
             style,
+            // only spring animations keep their velocity when they are interrupted
+            velocity: MOVEMENT_VELOCITY.lock().remove(&hwnd).unwrap_or_default(),
         }
     }
 }
@@ -200,18 +218,30 @@ impl RenderDispatcher for MovementRenderDispatcher {
         Ok(())
     }
 
-    fn render(&self, progress: f64) -> Result<()> {
-        let new_rect = self.start_rect.lerp(self.target_rect, progress, self.style);
+    fn render(&self, progress: f64, frame: &mut Frame) -> Result<()> {
+        let new_rect = match self.style {
+            AnimationStyle::Spring(spring) => {
+                let (new_rect, velocity) = self.start_rect.spring(
+                    self.target_rect,
+                    self.velocity,
+                    progress * spring.duration().as_secs_f64(),
+                    spring,
+                );
+
+                MOVEMENT_VELOCITY.lock().insert(self.hwnd, velocity);
+                new_rect
+            }
+            _ => self.start_rect.lerp(self.target_rect, progress, self.style),
+        };
 
-        // using MoveWindow because it runs faster than SetWindowPos
-        // so animation have more fps and feel smoother
-        WindowsApi::move_window(self.hwnd, &new_rect, false)?;
-        WindowsApi::invalidate_rect(self.hwnd, None, false);
+        // the moves of every window animated on this frame are applied together
+        frame.move_window(self.hwnd, new_rect);
 
         Ok(())
     }
 
     fn post_render(&self) -> Result<()> {
+        MOVEMENT_VELOCITY.lock().remove(&self.hwnd);
         WindowsApi::position_window(self.hwnd, &self.target_rect, self.top)?;
         if ANIMATION_MANAGER
             .lock()
@@ -277,7 +307,7 @@ impl RenderDispatcher for TransparencyRenderDispatcher {
         Ok(())
     }
 
-    fn render(&self, progress: f64) -> Result<()> {
+    fn render(&self, progress: f64, _frame: &mut Frame) -> Result<()> {
         WindowsApi::set_transparent(
             self.hwnd,
             self.start_opacity
@@ -339,6 +369,101 @@ impl AspectRatio {
     }
 }
 
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct FloatingWindowSize {
+    /// Width in pixels
+    pub width: i32,
+    /// Height in pixels
+    pub height: i32,
+}
+
+#[derive(Copy, Clone, Debug, Default, Display, EnumString, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum FloatingWindowAnchor {
+    /// Centered in the work area
+    #[default]
+    Center,
+    /// Top left corner of the work area
+    TopLeft,
+    /// Top right corner of the work area
+    TopRight,
+    /// Bottom left corner of the work area
+    BottomLeft,
+    /// Bottom right corner of the work area
+    BottomRight,
+    /// Centered on the cursor, kept within the work area
+    Cursor,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct FloatingPlacementRule {
+    /// Rule identifying the windows which should use this placement
+    pub matching_rule: MatchingRule,
+    /// Explicit size of the window (takes precedence over aspect_ratio)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<FloatingWindowSize>,
+    /// Aspect ratio of the window, with a height of half the work area (default: floating_window_aspect_ratio)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aspect_ratio: Option<AspectRatio>,
+    /// Where the window should be placed within the work area (default: Center)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<FloatingWindowAnchor>,
+    /// Index of the monitor the window should be placed on (default: focused monitor)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub monitor: Option<usize>,
+}
+
+impl FloatingPlacementRule {
+    /// The rect a window using this placement should occupy within the given work area
+    pub fn rect(&self, work_area: &Rect) -> Rect {
+        let (width, height) = match self.size {
+            Some(size) => (
+                size.width.min(work_area.right),
+                size.height.min(work_area.bottom),
+            ),
+            None => {
+                let (aspect_ratio_width, aspect_ratio_height) = self
+                    .aspect_ratio
+                    .unwrap_or_else(|| *FLOATING_WINDOW_TOGGLE_ASPECT_RATIO.lock())
+                    .width_and_height();
+                let height = work_area.bottom / 2;
+                let width = (height * aspect_ratio_width) / aspect_ratio_height;
+                (width.min(work_area.right), height)
+            }
+        };
+
+        let right_edge = work_area.left + work_area.right - width;
+        let bottom_edge = work_area.top + work_area.bottom - height;
+
+        let (left, top) = match self.anchor.unwrap_or_default() {
+            FloatingWindowAnchor::Center => (
+                work_area.left + ((work_area.right - width) / 2),
+                work_area.top + ((work_area.bottom - height) / 2),
+            ),
+            FloatingWindowAnchor::TopLeft => (work_area.left, work_area.top),
+            FloatingWindowAnchor::TopRight => (right_edge, work_area.top),
+            FloatingWindowAnchor::BottomLeft => (work_area.left, bottom_edge),
+            FloatingWindowAnchor::BottomRight => (right_edge, bottom_edge),
+            FloatingWindowAnchor::Cursor => {
+                let cursor = WindowsApi::cursor_pos().unwrap_or_default();
+                (
+                    (cursor.x - width / 2).min(right_edge).max(work_area.left),
+                    (cursor.y - height / 2).min(bottom_edge).max(work_area.top),
+                )
+            }
+        };
+
+        Rect {
+            left,
+            top,
+            right: width,
+            bottom: height,
+        }
+    }
+}
+
 impl Window {
     pub const fn hwnd(self) -> HWND {
         HWND(windows_api::as_ptr!(self.hwnd))
@@ -432,7 +557,32 @@ impl Window {
         )
     }
 
+    pub fn place(&mut self, work_area: &Rect, placement: &FloatingPlacementRule) -> Result<()> {
+        self.set_position(&placement.rect(work_area), true)
+    }
+
+    /// The first floating placement rule which matches this window
+    pub fn floating_placement_rule(self) -> Option<FloatingPlacementRule> {
+        let placement_rules = FLOATING_WINDOW_PLACEMENT_RULES.lock();
+        if placement_rules.is_empty() {
+            return None;
+        }
+
+        let target = RuleTarget::try_from(self).ok()?;
+        let regex_identifiers = REGEX_IDENTIFIERS.lock();
+
+        placement_rules
+            .iter()
+            .find(|rule| matches_rule(&target, &rule.matching_rule, &regex_identifiers))
+            .cloned()
+    }
+
     pub fn set_position(&self, layout: &Rect, top: bool) -> Result<()> {
+        // windows sliding into view with a workspace transition are already on their way here
+        if WORKSPACE_TRANSITION_RECTS.lock().get(&self.hwnd) == Some(layout) {
+            return Ok(());
+        }
+
         let window_rect = WindowsApi::window_rect(self.hwnd)?;
 
         if window_rect.eq(layout) {
@@ -445,16 +595,16 @@ impl Window {
         if move_enabled.is_some_and(|enabled| *enabled)
             || ANIMATION_ENABLED_GLOBAL.load(Ordering::SeqCst)
         {
-            let duration = Duration::from_millis(
-                *ANIMATION_DURATION_PER_ANIMATION
-                    .lock()
-                    .get(&MovementRenderDispatcher::PREFIX)
-                    .unwrap_or(&ANIMATION_DURATION_GLOBAL.load(Ordering::SeqCst)),
-            );
             let style = *ANIMATION_STYLE_PER_ANIMATION
                 .lock()
                 .get(&MovementRenderDispatcher::PREFIX)
                 .unwrap_or(&ANIMATION_STYLE_GLOBAL.lock());
+            let duration = style.duration(Duration::from_millis(
+                *ANIMATION_DURATION_PER_ANIMATION
+                    .lock()
+                    .get(&MovementRenderDispatcher::PREFIX)
+                    .unwrap_or(&ANIMATION_DURATION_GLOBAL.load(Ordering::SeqCst)),
+            ));
 
             let render_dispatcher =
                 MovementRenderDispatcher::new(self.hwnd, window_rect, *layout, top, style);
@@ -586,28 +736,37 @@ impl Window {
     }
 
     pub fn transparent(self) -> Result<()> {
+        self.set_alpha(transparency_manager::TRANSPARENCY_ALPHA.load_consume())
+    }
+
+    /// Makes the window transparent with the given alpha, or opaque if the alpha is 255
+    pub fn set_alpha(self, alpha: u8) -> Result<()> {
+        if alpha == u8::MAX {
+            return self.opaque();
+        }
+
         let animation_enabled = ANIMATION_ENABLED_PER_ANIMATION.lock();
         let transparent_enabled = animation_enabled.get(&TransparencyRenderDispatcher::PREFIX);
 
         if transparent_enabled.is_some_and(|enabled| *enabled)
             || ANIMATION_ENABLED_GLOBAL.load(Ordering::SeqCst)
         {
-            let duration = Duration::from_millis(
-                *ANIMATION_DURATION_PER_ANIMATION
-                    .lock()
-                    .get(&TransparencyRenderDispatcher::PREFIX)
-                    .unwrap_or(&ANIMATION_DURATION_GLOBAL.load(Ordering::SeqCst)),
-            );
             let style = *ANIMATION_STYLE_PER_ANIMATION
                 .lock()
                 .get(&TransparencyRenderDispatcher::PREFIX)
                 .unwrap_or(&ANIMATION_STYLE_GLOBAL.lock());
+            let duration = style.duration(Duration::from_millis(
+                *ANIMATION_DURATION_PER_ANIMATION
+                    .lock()
+                    .get(&TransparencyRenderDispatcher::PREFIX)
+                    .unwrap_or(&ANIMATION_DURATION_GLOBAL.load(Ordering::SeqCst)),
+            ));
 
             let render_dispatcher = TransparencyRenderDispatcher::new(
                 self.hwnd,
                 false,
                 WindowsApi::get_transparent(self.hwnd).unwrap_or(255),
-                transparency_manager::TRANSPARENCY_ALPHA.load_consume(),
+                alpha,
                 style,
             );
 
@@ -616,10 +775,7 @@ impl Window {
             let mut ex_style = self.ex_style()?;
             ex_style.insert(ExtendedWindowStyle::LAYERED);
             self.update_ex_style(&ex_style)?;
-            WindowsApi::set_transparent(
-                self.hwnd,
-                transparency_manager::TRANSPARENCY_ALPHA.load_consume(),
-            )
+            WindowsApi::set_transparent(self.hwnd, alpha)
         }
     }
 
@@ -630,16 +786,16 @@ impl Window {
         if transparent_enabled.is_some_and(|enabled| *enabled)
             || ANIMATION_ENABLED_GLOBAL.load(Ordering::SeqCst)
         {
-            let duration = Duration::from_millis(
-                *ANIMATION_DURATION_PER_ANIMATION
-                    .lock()
-                    .get(&TransparencyRenderDispatcher::PREFIX)
-                    .unwrap_or(&ANIMATION_DURATION_GLOBAL.load(Ordering::SeqCst)),
-            );
             let style = *ANIMATION_STYLE_PER_ANIMATION
                 .lock()
                 .get(&TransparencyRenderDispatcher::PREFIX)
                 .unwrap_or(&ANIMATION_STYLE_GLOBAL.lock());
+            let duration = style.duration(Duration::from_millis(
+                *ANIMATION_DURATION_PER_ANIMATION
+                    .lock()
+                    .get(&TransparencyRenderDispatcher::PREFIX)
+                    .unwrap_or(&ANIMATION_DURATION_GLOBAL.load(Ordering::SeqCst)),
+            ));
 
             let render_dispatcher = TransparencyRenderDispatcher::new(
                 self.hwnd,
@@ -716,6 +872,54 @@ impl Window {
         exe
     }
 
+    pub fn command_line(self) -> Result<String> {
+        let process_id = Pid::from_u32(self.process_id());
+        let mut system = System::new();
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[process_id]),
+            true,
+            ProcessRefreshKind::nothing().with_cmd(UpdateKind::OnlyIfNotSet),
+        );
+
+        let process = system
+            .process(process_id)
+            .ok_or_else(|| eyre::anyhow!("there is no process with id {process_id}"))?;
+
+        Ok(process
+            .cmd()
+            .iter()
+            .map(|arg| arg.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join(" "))
+    }
+
+    pub fn parent_process(self) -> Result<String> {
+        let process_id = Pid::from_u32(self.process_id());
+        let mut system = System::new();
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[process_id]),
+            true,
+            ProcessRefreshKind::nothing(),
+        );
+
+        let parent_id = system
+            .process(process_id)
+            .and_then(Process::parent)
+            .ok_or_else(|| eyre::anyhow!("there is no parent process for id {process_id}"))?;
+
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[parent_id]),
+            true,
+            ProcessRefreshKind::nothing(),
+        );
+
+        let parent = system
+            .process(parent_id)
+            .ok_or_else(|| eyre::anyhow!("there is no process with id {parent_id}"))?;
+
+        Ok(parent.name().to_string_lossy().to_string())
+    }
+
     pub fn process_id(self) -> u32 {
         let (process_id, _) = WindowsApi::window_thread_process_id(self.hwnd);
         process_id
@@ -894,47 +1098,38 @@ fn window_is_eligible(
         }
     }
 
+    let target = RuleTarget::new(
+        title.clone(),
+        exe_name.clone(),
+        class.clone(),
+        path.to_string(),
+    )
+    .with_hwnd(hwnd)
+    .with_window_style(*style)
+    .with_extended_window_style(*ex_style);
+
     let regex_identifiers = REGEX_IDENTIFIERS.lock();
 
     let ignore_identifiers = IGNORE_IDENTIFIERS.lock();
-    let should_ignore = if let Some(rule) = should_act(
-        title,
-        exe_name,
-        class,
-        path,
-        &ignore_identifiers,
-        &regex_identifiers,
-    ) {
-        debug.matches_ignore_identifier = Some(rule);
-        true
-    } else {
-        false
-    };
+    let should_ignore =
+        if let Some(rule) = should_act(&target, &ignore_identifiers, &regex_identifiers) {
+            debug.matches_ignore_identifier = Some(rule);
+            true
+        } else {
+            false
+        };
 
     let manage_identifiers = MANAGE_IDENTIFIERS.lock();
-    let managed_override = if let Some(rule) = should_act(
-        title,
-        exe_name,
-        class,
-        path,
-        &manage_identifiers,
-        &regex_identifiers,
-    ) {
-        debug.matches_managed_override = Some(rule);
-        true
-    } else {
-        false
-    };
+    let managed_override =
+        if let Some(rule) = should_act(&target, &manage_identifiers, &regex_identifiers) {
+            debug.matches_managed_override = Some(rule);
+            true
+        } else {
+            false
+        };
 
     let floating_identifiers = FLOATING_APPLICATIONS.lock();
-    if let Some(rule) = should_act(
-        title,
-        exe_name,
-        class,
-        path,
-        &floating_identifiers,
-        &regex_identifiers,
-    ) {
+    if let Some(rule) = should_act(&target, &floating_identifiers, &regex_identifiers) {
         debug.matches_floating_applications = Some(rule);
     }
 
@@ -943,19 +1138,13 @@ fn window_is_eligible(
     }
 
     let layered_whitelist = LAYERED_WHITELIST.lock();
-    let mut allow_layered = if let Some(rule) = should_act(
-        title,
-        exe_name,
-        class,
-        path,
-        &layered_whitelist,
-        &regex_identifiers,
-    ) {
-        debug.matches_layered_whitelist = Some(rule);
-        true
-    } else {
-        false
-    };
+    let mut allow_layered =
+        if let Some(rule) = should_act(&target, &layered_whitelist, &regex_identifiers) {
+            debug.matches_layered_whitelist = Some(rule);
+            true
+        } else {
+            false
+        };
 
     let known_layered_hwnds = transparency_manager::known_hwnds();
 
@@ -981,31 +1170,18 @@ fn window_is_eligible(
     };
 
     let titlebars_removed = NO_TITLEBAR.lock();
-    let allow_titlebar_removed = if let Some(rule) = should_act(
-        title,
-        exe_name,
-        class,
-        path,
-        &titlebars_removed,
-        &regex_identifiers,
-    ) {
-        debug.matches_no_titlebar = Some(rule);
-        true
-    } else {
-        false
-    };
+    let allow_titlebar_removed =
+        if let Some(rule) = should_act(&target, &titlebars_removed, &regex_identifiers) {
+            debug.matches_no_titlebar = Some(rule);
+            true
+        } else {
+            false
+        };
 
     {
         let slow_application_identifiers = SLOW_APPLICATION_IDENTIFIERS.lock();
-        let should_sleep = should_act(
-            title,
-            exe_name,
-            class,
-            path,
-            &slow_application_identifiers,
-            &regex_identifiers,
-        )
-        .is_some();
+        let should_sleep =
+            should_act(&target, &slow_application_identifiers, &regex_identifiers).is_some();
 
         if should_sleep {
             std::thread::sleep(Duration::from_millis(
@@ -1036,293 +1212,277 @@ fn window_is_eligible(
     false
 }
 
-#[allow(clippy::cognitive_complexity, clippy::too_many_lines)]
-pub fn should_act(
-    title: &str,
-    exe_name: &str,
-    class: &str,
-    path: &str,
-    identifiers: &[MatchingRule],
-    regex_identifiers: &HashMap<String, Regex>,
-) -> Option<MatchingRule> {
-    let mut matching_rule = None;
-    for rule in identifiers {
-        match rule {
-            MatchingRule::Simple(identifier) => {
-                if should_act_individual(
-                    title,
-                    exe_name,
-                    class,
-                    path,
-                    identifier,
-                    regex_identifiers,
-                ) {
-                    matching_rule = Some(rule.clone());
-                };
-            }
-            MatchingRule::Composite(identifiers) => {
-                let mut composite_results = vec![];
-                for identifier in identifiers {
-                    composite_results.push(should_act_individual(
-                        title,
-                        exe_name,
-                        class,
-                        path,
-                        identifier,
-                        regex_identifiers,
-                    ));
-                }
+/// A window, or a synthetic description of one, which [`MatchingRule`]s can be evaluated against
+///
+/// The title, exe, class and path are always known up front. The other properties are looked up
+/// lazily (and only once) from the window handle when a rule which uses them is evaluated, so
+/// rule lists which only use the basic identifiers don't pay for the more expensive lookups.
+#[derive(Debug, Default, Clone)]
+pub struct RuleTarget {
+    pub hwnd: Option<isize>,
+    pub title: String,
+    pub exe: String,
+    pub class: String,
+    pub path: String,
+    command_line: OnceCell<Option<String>>,
+    parent_process: OnceCell<Option<String>>,
+    monitor: OnceCell<Vec<String>>,
+    window_style: OnceCell<Option<WindowStyle>>,
+    extended_window_style: OnceCell<Option<ExtendedWindowStyle>>,
+    size: OnceCell<Option<(String, String)>>,
+}
+
+impl TryFrom<Window> for RuleTarget {
+    type Error = eyre::ErrReport;
+
+    fn try_from(value: Window) -> std::result::Result<Self, Self::Error> {
+        Ok(
+            Self::new(value.title()?, value.exe()?, value.class()?, value.path()?)
+                .with_hwnd(value.hwnd),
+        )
+    }
+}
+
+impl RuleTarget {
+    pub fn new(title: String, exe: String, class: String, path: String) -> Self {
+        Self {
+            title,
+            exe,
+            class,
+            path,
+            ..Default::default()
+        }
+    }
+
+    #[must_use]
+    pub fn with_hwnd(mut self, hwnd: isize) -> Self {
+        self.hwnd = Option::from(hwnd);
+        self
+    }
+
+    #[must_use]
+    pub fn with_command_line(self, command_line: String) -> Self {
+        let _ = self.command_line.set(Option::from(command_line));
+        self
+    }
+
+    #[must_use]
+    pub fn with_parent_process(self, parent_process: String) -> Self {
+        let _ = self.parent_process.set(Option::from(parent_process));
+        self
+    }
+
+    #[must_use]
+    pub fn with_monitor(self, monitor: Vec<String>) -> Self {
+        let _ = self.monitor.set(monitor);
+        self
+    }
 
-                if composite_results.iter().all(|&x| x) {
-                    matching_rule = Some(rule.clone());
+    #[must_use]
+    pub fn with_window_style(self, style: WindowStyle) -> Self {
+        let _ = self.window_style.set(Option::from(style));
+        self
+    }
+
+    #[must_use]
+    pub fn with_extended_window_style(self, style: ExtendedWindowStyle) -> Self {
+        let _ = self.extended_window_style.set(Option::from(style));
+        self
+    }
+
+    #[must_use]
+    pub fn with_size(self, width: i32, height: i32) -> Self {
+        let _ = self
+            .size
+            .set(Option::from((width.to_string(), height.to_string())));
+        self
+    }
+
+    fn window(&self) -> Option<Window> {
+        self.hwnd.map(Window::from)
+    }
+
+    /// The value(s) of the given identifier for this target, an empty vec is returned if the
+    /// value could not be determined
+    pub fn values(&self, kind: ApplicationIdentifier) -> Vec<&str> {
+        match kind {
+            ApplicationIdentifier::Title => vec![self.title.as_str()],
+            ApplicationIdentifier::Exe => vec![self.exe.as_str()],
+            ApplicationIdentifier::Class => vec![self.class.as_str()],
+            ApplicationIdentifier::Path => vec![self.path.as_str()],
+            ApplicationIdentifier::CommandLine => self
+                .command_line
+                .get_or_init(|| self.window().and_then(|w| w.command_line().ok()))
+                .as_deref()
+                .into_iter()
+                .collect(),
+            ApplicationIdentifier::ParentProcess => self
+                .parent_process
+                .get_or_init(|| self.window().and_then(|w| w.parent_process().ok()))
+                .as_deref()
+                .into_iter()
+                .collect(),
+            ApplicationIdentifier::Monitor => self
+                .monitor
+                .get_or_init(|| {
+                    self.window()
+                        .and_then(|w| {
+                            WindowsApi::monitor(WindowsApi::monitor_from_window(w.hwnd)).ok()
+                        })
+                        .map(|monitor| {
+                            let mut values = vec![
+                                monitor.name().clone(),
+                                monitor.device().clone(),
+                                monitor.device_id().clone(),
+                            ];
+
+                            if let Some(serial_number_id) = monitor.serial_number_id() {
+                                values.push(serial_number_id.clone());
+                            }
+
+                            values
+                        })
+                        .unwrap_or_default()
+                })
+                .iter()
+                .map(String::as_str)
+                .collect(),
+            ApplicationIdentifier::WindowStyle => self
+                .window_style
+                .get_or_init(|| self.window().and_then(|w| w.style().ok()))
+                .as_ref()
+                .map(flag_names)
+                .unwrap_or_default(),
+            ApplicationIdentifier::ExtendedWindowStyle => self
+                .extended_window_style
+                .get_or_init(|| self.window().and_then(|w| w.ex_style().ok()))
+                .as_ref()
+                .map(flag_names)
+                .unwrap_or_default(),
+            ApplicationIdentifier::Width | ApplicationIdentifier::Height => {
+                let size = self.size.get_or_init(|| {
+                    self.window()
+                        .and_then(|w| WindowsApi::window_rect(w.hwnd).ok())
+                        .map(|rect| (rect.right.to_string(), rect.bottom.to_string()))
+                });
+
+                match (kind, size) {
+                    (ApplicationIdentifier::Width, Some((width, _))) => vec![width.as_str()],
+                    (ApplicationIdentifier::Height, Some((_, height))) => vec![height.as_str()],
+                    _ => vec![],
                 }
             }
         }
     }
+}
 
-    matching_rule
+/// The names of all of the flags which are set, including aliases (eg. both SIZEBOX and THICKFRAME)
+/// and combinations (eg. CAPTION) of other flags
+fn flag_names<F: Flags + Copy>(flags: &F) -> Vec<&'static str> {
+    F::FLAGS
+        .iter()
+        .filter(|flag| !flag.value().is_empty() && flags.contains(*flag.value()))
+        .map(|flag| flag.name())
+        .collect()
+}
+
+/// The first rule in the given list which matches the target
+pub fn should_act(
+    target: &RuleTarget,
+    identifiers: &RuleIndex,
+    regex_identifiers: &HashMap<String, Regex>,
+) -> Option<MatchingRule> {
+    identifiers.find(target, regex_identifiers).cloned()
+}
+
+pub fn matches_rule(
+    target: &RuleTarget,
+    rule: &MatchingRule,
+    regex_identifiers: &HashMap<String, Regex>,
+) -> bool {
+    match rule {
+        MatchingRule::Simple(identifier) => {
+            should_act_individual(target, identifier, regex_identifiers)
+        }
+        MatchingRule::Composite(identifiers) => identifiers
+            .iter()
+            .all(|identifier| should_act_individual(target, identifier, regex_identifiers)),
+        MatchingRule::Any { any } => any
+            .iter()
+            .any(|rule| matches_rule(target, rule, regex_identifiers)),
+        MatchingRule::All { all } => all
+            .iter()
+            .all(|rule| matches_rule(target, rule, regex_identifiers)),
+        MatchingRule::Not { not } => !matches_rule(target, not, regex_identifiers),
+    }
 }
 
 pub fn should_act_individual(
-    title: &str,
-    exe_name: &str,
-    class: &str,
-    path: &str,
+    target: &RuleTarget,
     identifier: &IdWithIdentifier,
     regex_identifiers: &HashMap<String, Regex>,
 ) -> bool {
-    let mut should_act = false;
+    let strategy = identifier
+        .matching_strategy
+        .as_ref()
+        .unwrap_or(&MatchingStrategy::Legacy);
+
+    // A property which could not be read (eg. the command line of an elevated process) never
+    // matches, otherwise every negated strategy would be vacuously true for it
+    let values = target.values(identifier.kind);
+    if values.is_empty() {
+        return false;
+    }
 
-    match identifier.matching_strategy {
-        None | Some(MatchingStrategy::Legacy) => match identifier.kind {
-            ApplicationIdentifier::Title => {
-                if title.starts_with(&identifier.id) || title.ends_with(&identifier.id) {
-                    should_act = true;
-                }
-            }
-            ApplicationIdentifier::Class => {
-                if class.starts_with(&identifier.id) || class.ends_with(&identifier.id) {
-                    should_act = true;
-                }
-            }
-            ApplicationIdentifier::Exe => {
-                if exe_name.eq(&identifier.id) {
-                    should_act = true;
-                }
-            }
-            ApplicationIdentifier::Path => {
-                if path.eq(&identifier.id) {
-                    should_act = true;
-                }
-            }
-        },
-        Some(MatchingStrategy::Equals) => match identifier.kind {
-            ApplicationIdentifier::Title => {
-                if title.eq(&identifier.id) {
-                    should_act = true;
-                }
-            }
-            ApplicationIdentifier::Class => {
-                if class.eq(&identifier.id) {
-                    should_act = true;
-                }
-            }
-            ApplicationIdentifier::Exe => {
-                if exe_name.eq(&identifier.id) {
-                    should_act = true;
-                }
-            }
-            ApplicationIdentifier::Path => {
-                if path.eq(&identifier.id) {
-                    should_act = true;
-                }
-            }
-        },
-        Some(MatchingStrategy::DoesNotEqual) => match identifier.kind {
-            ApplicationIdentifier::Title => {
-                if !title.eq(&identifier.id) {
-                    should_act = true;
-                }
-            }
-            ApplicationIdentifier::Class => {
-                if !class.eq(&identifier.id) {
-                    should_act = true;
-                }
-            }
-            ApplicationIdentifier::Exe => {
-                if !exe_name.eq(&identifier.id) {
-                    should_act = true;
-                }
-            }
-            ApplicationIdentifier::Path => {
-                if !path.eq(&identifier.id) {
-                    should_act = true;
-                }
-            }
-        },
-        Some(MatchingStrategy::StartsWith) => match identifier.kind {
-            ApplicationIdentifier::Title => {
-                if title.starts_with(&identifier.id) {
-                    should_act = true;
-                }
-            }
-            ApplicationIdentifier::Class => {
-                if class.starts_with(&identifier.id) {
-                    should_act = true;
-                }
-            }
-            ApplicationIdentifier::Exe => {
-                if exe_name.starts_with(&identifier.id) {
-                    should_act = true;
-                }
-            }
-            ApplicationIdentifier::Path => {
-                if path.starts_with(&identifier.id) {
-                    should_act = true;
-                }
-            }
-        },
-        Some(MatchingStrategy::DoesNotStartWith) => match identifier.kind {
-            ApplicationIdentifier::Title => {
-                if !title.starts_with(&identifier.id) {
-                    should_act = true;
-                }
-            }
-            ApplicationIdentifier::Class => {
-                if !class.starts_with(&identifier.id) {
-                    should_act = true;
-                }
-            }
-            ApplicationIdentifier::Exe => {
-                if !exe_name.starts_with(&identifier.id) {
-                    should_act = true;
-                }
-            }
-            ApplicationIdentifier::Path => {
-                if !path.starts_with(&identifier.id) {
-                    should_act = true;
-                }
-            }
-        },
-        Some(MatchingStrategy::EndsWith) => match identifier.kind {
-            ApplicationIdentifier::Title => {
-                if title.ends_with(&identifier.id) {
-                    should_act = true;
-                }
-            }
-            ApplicationIdentifier::Class => {
-                if class.ends_with(&identifier.id) {
-                    should_act = true;
-                }
-            }
-            ApplicationIdentifier::Exe => {
-                if exe_name.ends_with(&identifier.id) {
-                    should_act = true;
-                }
-            }
-            ApplicationIdentifier::Path => {
-                if path.ends_with(&identifier.id) {
-                    should_act = true;
-                }
-            }
-        },
-        Some(MatchingStrategy::DoesNotEndWith) => match identifier.kind {
-            ApplicationIdentifier::Title => {
-                if !title.ends_with(&identifier.id) {
-                    should_act = true;
-                }
-            }
-            ApplicationIdentifier::Class => {
-                if !class.ends_with(&identifier.id) {
-                    should_act = true;
-                }
-            }
-            ApplicationIdentifier::Exe => {
-                if !exe_name.ends_with(&identifier.id) {
-                    should_act = true;
-                }
-            }
-            ApplicationIdentifier::Path => {
-                if !path.ends_with(&identifier.id) {
-                    should_act = true;
-                }
-            }
-        },
-        Some(MatchingStrategy::Contains) => match identifier.kind {
-            ApplicationIdentifier::Title => {
-                if title.contains(&identifier.id) {
-                    should_act = true;
-                }
-            }
-            ApplicationIdentifier::Class => {
-                if class.contains(&identifier.id) {
-                    should_act = true;
-                }
-            }
-            ApplicationIdentifier::Exe => {
-                if exe_name.contains(&identifier.id) {
-                    should_act = true;
-                }
-            }
-            ApplicationIdentifier::Path => {
-                if path.contains(&identifier.id) {
-                    should_act = true;
-                }
+    let matches = |value: &&str| {
+        matches_strategy(
+            value,
+            identifier.kind,
+            strategy,
+            &identifier.id,
+            regex_identifiers,
+        )
+    };
+
+    if strategy.is_negated() {
+        values.iter().all(matches)
+    } else {
+        values.iter().any(matches)
+    }
+}
+
+fn matches_strategy(
+    value: &str,
+    kind: ApplicationIdentifier,
+    strategy: &MatchingStrategy,
+    id: &str,
+    regex_identifiers: &HashMap<String, Regex>,
+) -> bool {
+    match strategy {
+        MatchingStrategy::Legacy => match kind {
+            ApplicationIdentifier::Title | ApplicationIdentifier::Class => {
+                value.starts_with(id) || value.ends_with(id)
             }
+            _ => value.eq(id),
         },
-        Some(MatchingStrategy::DoesNotContain) => match identifier.kind {
-            ApplicationIdentifier::Title => {
-                if !title.contains(&identifier.id) {
-                    should_act = true;
-                }
-            }
-            ApplicationIdentifier::Class => {
-                if !class.contains(&identifier.id) {
-                    should_act = true;
-                }
-            }
-            ApplicationIdentifier::Exe => {
-                if !exe_name.contains(&identifier.id) {
-                    should_act = true;
-                }
-            }
-            ApplicationIdentifier::Path => {
-                if !path.contains(&identifier.id) {
-                    should_act = true;
-                }
-            }
+        MatchingStrategy::Equals => value.eq(id),
+        MatchingStrategy::DoesNotEqual => !value.eq(id),
+        MatchingStrategy::StartsWith => value.starts_with(id),
+        MatchingStrategy::DoesNotStartWith => !value.starts_with(id),
+        MatchingStrategy::EndsWith => value.ends_with(id),
+        MatchingStrategy::DoesNotEndWith => !value.ends_with(id),
+        MatchingStrategy::Contains => value.contains(id),
+        MatchingStrategy::DoesNotContain => !value.contains(id),
+        MatchingStrategy::Regex => regex_identifiers
+            .get(id)
+            .is_some_and(|re| re.is_match(value)),
+        MatchingStrategy::GreaterThan => match (value.parse::<i64>(), id.parse::<i64>()) {
+            (Ok(value), Ok(id)) => value > id,
+            _ => false,
         },
-        Some(MatchingStrategy::Regex) => match identifier.kind {
-            ApplicationIdentifier::Title => {
-                if let Some(re) = regex_identifiers.get(&identifier.id) {
-                    if re.is_match(title) {
-                        should_act = true;
-                    }
-                }
-            }
-            ApplicationIdentifier::Class => {
-                if let Some(re) = regex_identifiers.get(&identifier.id) {
-                    if re.is_match(class) {
-                        should_act = true;
-                    }
-                }
-            }
-            ApplicationIdentifier::Exe => {
-                if let Some(re) = regex_identifiers.get(&identifier.id) {
-                    if re.is_match(exe_name) {
-                        should_act = true;
-                    }
-                }
-            }
-            ApplicationIdentifier::Path => {
-                if let Some(re) = regex_identifiers.get(&identifier.id) {
-                    if re.is_match(path) {
-                        should_act = true;
-                    }
-                }
-            }
+        MatchingStrategy::LessThan => match (value.parse::<i64>(), id.parse::<i64>()) {
+            (Ok(value), Ok(id)) => value < id,
+            _ => false,
         },
     }
-
-    should_act
 }
@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+use std::iter;
+
+use regex::Regex;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::core::config_generation::MatchingRule;
+use crate::core::config_generation::MatchingStrategy;
+use crate::window::matches_rule;
+use crate::window::RuleTarget;
+
+/// A window label made from a template such as "{index}: {process} — {title:30}"
+///
+/// The available placeholders are {process} (the executable without ".exe"), {exe}, {title},
+/// {class} and {index} (the position of the window in its stack, starting from 1). A placeholder
+/// can be limited to a number of characters, where {title:30} keeps the start of the title and
+/// {title:-30} keeps the end, with an ellipsis in place of the rest. Use {{ and }} for literal
+/// braces.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct LabelTemplate(pub String);
+
+impl LabelTemplate {
+    pub fn render(&self, target: &RuleTarget, index: usize) -> String {
+        let mut label = String::new();
+        let mut chars = self.0.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    label.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    label.push('}');
+                }
+                '{' => {
+                    let mut placeholder = String::new();
+                    let mut closed = false;
+
+                    for c in chars.by_ref() {
+                        if c == '}' {
+                            closed = true;
+                            break;
+                        }
+
+                        placeholder.push(c);
+                    }
+
+                    // an unclosed brace is kept as it is rather than swallowing the rest of the
+                    // template
+                    if closed {
+                        label.push_str(&Self::placeholder(&placeholder, target, index));
+                    } else {
+                        label.push('{');
+                        label.push_str(&placeholder);
+                    }
+                }
+                c => label.push(c),
+            }
+        }
+
+        label
+    }
+
+    fn placeholder(placeholder: &str, target: &RuleTarget, index: usize) -> String {
+        // unknown placeholders and invalid limits are kept so that mistakes in the template are
+        // visible
+        let unknown = || format!("{{{placeholder}}}");
+
+        let (name, limit) = match placeholder.split_once(':') {
+            Some((name, limit)) => match limit.trim().parse::<isize>() {
+                Ok(limit) => (name.trim(), Some(limit)),
+                Err(_) => return unknown(),
+            },
+            None => (placeholder.trim(), None),
+        };
+
+        let value = match name {
+            "process" => target.exe.trim_end_matches(".exe").to_string(),
+            "exe" => target.exe.clone(),
+            "title" => target.title.clone(),
+            "class" => target.class.clone(),
+            "index" => (index + 1).to_string(),
+            _ => return unknown(),
+        };
+
+        match limit {
+            Some(limit) => truncate(&value, limit),
+            None => value,
+        }
+    }
+}
+
+/// Truncates a value to at most `limit` characters including an ellipsis, keeping the start of
+/// the value, or the end of the value if the limit is negative
+fn truncate(value: &str, limit: isize) -> String {
+    let length = value.chars().count();
+    let max = limit.unsigned_abs();
+
+    if length <= max {
+        return value.to_string();
+    }
+
+    if max == 0 {
+        return String::new();
+    }
+
+    if limit > 0 {
+        value.chars().take(max - 1).chain(iter::once('…')).collect()
+    } else {
+        iter::once('…')
+            .chain(value.chars().skip(length - (max - 1)))
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct LabelOverride {
+    /// Applications which use this label
+    pub matching_rule: MatchingRule,
+    /// Label template for these applications
+    pub label: LabelTemplate,
+}
+
+/// Labels windows with a template, or with the template of the first override which matches
+#[derive(Clone, Debug, Default)]
+pub struct WindowLabeller {
+    template: Option<LabelTemplate>,
+    overrides: Vec<LabelOverride>,
+    regex_identifiers: HashMap<String, Regex>,
+}
+
+impl WindowLabeller {
+    pub fn new(template: Option<LabelTemplate>, overrides: Vec<LabelOverride>) -> Self {
+        let mut regex_identifiers = HashMap::new();
+
+        for label_override in &overrides {
+            for identifier in label_override.matching_rule.identifiers() {
+                if matches!(identifier.matching_strategy, Some(MatchingStrategy::Regex)) {
+                    match Regex::new(&identifier.id) {
+                        Ok(re) => {
+                            regex_identifiers.insert(identifier.id.clone(), re);
+                        }
+                        Err(error) => {
+                            tracing::error!("invalid regex in label override: {error}")
+                        }
+                    }
+                }
+            }
+        }
+
+        Self {
+            template,
+            overrides,
+            regex_identifiers,
+        }
+    }
+
+    /// The label for the window at `index` in its stack, or `None` if there is no template which
+    /// applies to it
+    pub fn label(&self, target: &RuleTarget, index: usize) -> Option<String> {
+        self.overrides
+            .iter()
+            .find(|label_override| {
+                matches_rule(
+                    target,
+                    &label_override.matching_rule,
+                    &self.regex_identifiers,
+                )
+            })
+            .map(|label_override| &label_override.label)
+            .or(self.template.as_ref())
+            .map(|template| template.render(target, index))
+    }
+}
@@ -45,21 +45,21 @@ use crate::core::Rect;
 use crate::core::Sizing;
 use crate::core::StackbarLabel;
 use crate::core::WindowContainerBehaviour;
+use crate::core::WindowKind;
 use crate::core::WindowManagementBehaviour;
 
 use crate::border_manager;
-use crate::border_manager::BORDER_OFFSET;
-use crate::border_manager::BORDER_WIDTH;
 use crate::border_manager::STYLE;
 use crate::config_generation::WorkspaceMatchingRule;
+use crate::config_generation::WorkspaceTarget;
 use crate::container::Container;
 use crate::core::StackbarMode;
 use crate::current_virtual_desktop;
 use crate::load_configuration;
+use crate::matches_rule;
 use crate::monitor::Monitor;
 use crate::ring::Ring;
 use crate::should_act;
-use crate::should_act_individual;
 use crate::stackbar_manager::STACKBAR_FOCUSED_TEXT_COLOUR;
 use crate::stackbar_manager::STACKBAR_LABEL;
 use crate::stackbar_manager::STACKBAR_MODE;
@@ -69,8 +69,13 @@ use crate::stackbar_manager::STACKBAR_TAB_WIDTH;
 use crate::stackbar_manager::STACKBAR_UNFOCUSED_TEXT_COLOUR;
 use crate::static_config::StaticConfig;
 use crate::transparency_manager;
+use crate::transparency_manager::TransparencyRule;
 use crate::transparency_manager::TRANSPARENCY_ALPHA;
+use crate::transparency_manager::TRANSPARENCY_DIM_INACTIVE_MONITORS;
 use crate::transparency_manager::TRANSPARENCY_ENABLED;
+use crate::transparency_manager::TRANSPARENCY_INACTIVE_MONITOR_ALPHA;
+use crate::transparency_manager::TRANSPARENCY_RULES;
+use crate::window::RuleTarget;
 use crate::window::Window;
 use crate::window_manager_event::WindowManagerEvent;
 use crate::windows_api::WindowsApi;
@@ -98,6 +103,7 @@ use crate::SUBSCRIPTION_SOCKETS;
 use crate::TRANSPARENCY_BLACKLIST;
 use crate::TRAY_AND_MULTI_WINDOW_IDENTIFIERS;
 use crate::WORKSPACE_MATCHING_RULES;
+use crate::WORKSPACE_TARGET_RULES;
 
 #[derive(Debug)]
 pub struct WindowManager {
@@ -209,6 +215,9 @@ pub struct GlobalState {
     pub transparency_enabled: bool,
     pub transparency_alpha: u8,
     pub transparency_blacklist: Vec<MatchingRule>,
+    pub transparency_rules: Vec<TransparencyRule>,
+    pub transparency_dim_inactive_monitors: bool,
+    pub transparency_inactive_monitor_alpha: u8,
     pub remove_titlebars: bool,
     #[serde(alias = "float_identifiers")]
     pub ignore_identifiers: Vec<MatchingRule>,
@@ -230,21 +239,19 @@ impl Default for GlobalState {
         Self {
             border_enabled: border_manager::BORDER_ENABLED.load(Ordering::SeqCst),
             border_colours: BorderColours {
-                single: Option::from(Colour::Rgb(Rgb::from(
-                    border_manager::FOCUSED.load(Ordering::SeqCst),
-                ))),
-                stack: Option::from(Colour::Rgb(Rgb::from(
-                    border_manager::STACK.load(Ordering::SeqCst),
-                ))),
-                monocle: Option::from(Colour::Rgb(Rgb::from(
-                    border_manager::MONOCLE.load(Ordering::SeqCst),
-                ))),
-                floating: Option::from(Colour::Rgb(Rgb::from(
-                    border_manager::FLOATING.load(Ordering::SeqCst),
-                ))),
-                unfocused: Option::from(Colour::Rgb(Rgb::from(
-                    border_manager::UNFOCUSED.load(Ordering::SeqCst),
-                ))),
+                single: Option::from(border_manager::window_kind_border_colour(
+                    WindowKind::Single,
+                )),
+                stack: Option::from(border_manager::window_kind_border_colour(WindowKind::Stack)),
+                monocle: Option::from(border_manager::window_kind_border_colour(
+                    WindowKind::Monocle,
+                )),
+                floating: Option::from(border_manager::window_kind_border_colour(
+                    WindowKind::Floating,
+                )),
+                unfocused: Option::from(border_manager::window_kind_border_colour(
+                    WindowKind::Unfocused,
+                )),
             },
             border_style: STYLE.load(),
             border_offset: border_manager::BORDER_OFFSET.load(Ordering::SeqCst),
@@ -264,13 +271,18 @@ impl Default for GlobalState {
             stackbar_height: STACKBAR_TAB_HEIGHT.load(Ordering::SeqCst),
             transparency_enabled: TRANSPARENCY_ENABLED.load(Ordering::SeqCst),
             transparency_alpha: TRANSPARENCY_ALPHA.load(Ordering::SeqCst),
-            transparency_blacklist: TRANSPARENCY_BLACKLIST.lock().clone(),
+            transparency_blacklist: TRANSPARENCY_BLACKLIST.lock().to_vec(),
+            transparency_rules: TRANSPARENCY_RULES.lock().rules().to_vec(),
+            transparency_dim_inactive_monitors: TRANSPARENCY_DIM_INACTIVE_MONITORS
+                .load(Ordering::SeqCst),
+            transparency_inactive_monitor_alpha: TRANSPARENCY_INACTIVE_MONITOR_ALPHA
+                .load(Ordering::SeqCst),
             remove_titlebars: REMOVE_TITLEBARS.load(Ordering::SeqCst),
-            ignore_identifiers: IGNORE_IDENTIFIERS.lock().clone(),
-            manage_identifiers: MANAGE_IDENTIFIERS.lock().clone(),
-            layered_whitelist: LAYERED_WHITELIST.lock().clone(),
-            tray_and_multi_window_identifiers: TRAY_AND_MULTI_WINDOW_IDENTIFIERS.lock().clone(),
-            name_change_on_launch_identifiers: OBJECT_NAME_CHANGE_ON_LAUNCH.lock().clone(),
+            ignore_identifiers: IGNORE_IDENTIFIERS.lock().to_vec(),
+            manage_identifiers: MANAGE_IDENTIFIERS.lock().to_vec(),
+            layered_whitelist: LAYERED_WHITELIST.lock().to_vec(),
+            tray_and_multi_window_identifiers: TRAY_AND_MULTI_WINDOW_IDENTIFIERS.lock().to_vec(),
+            name_change_on_launch_identifiers: OBJECT_NAME_CHANGE_ON_LAUNCH.lock().to_vec(),
             monitor_index_preferences: MONITOR_INDEX_PREFERENCES.lock().clone(),
             display_index_preferences: DISPLAY_INDEX_PREFERENCES.lock().clone(),
             workspace_rules: WORKSPACE_MATCHING_RULES.lock().clone(),
@@ -338,7 +350,7 @@ impl From<&WindowManager> for State {
                                 .clone(),
                             float_override: workspace.float_override,
                             layer: workspace.layer,
-                            globals: workspace.globals,
+                            globals: workspace.globals.clone(),
                             workspace_config: None,
                         })
                         .collect::<VecDeque<_>>();
@@ -349,6 +361,8 @@ impl From<&WindowManager> for State {
                 workspace_names: monitor.workspace_names.clone(),
                 container_padding: monitor.container_padding,
                 workspace_padding: monitor.workspace_padding,
+                border: monitor.border.clone(),
+                stackbar: monitor.stackbar.clone(),
             })
             .collect::<VecDeque<_>>();
         stripped_monitors.focus(wm.monitors.focused_idx());
@@ -810,7 +824,11 @@ impl WindowManager {
         // at the end of this function
         {
             let workspace_matching_rules = WORKSPACE_MATCHING_RULES.lock();
+            let workspace_target_rules = WORKSPACE_TARGET_RULES.lock();
             let regex_identifiers = REGEX_IDENTIFIERS.lock();
+            // Workspaces picked by policy-based rules during this pass, so that several windows
+            // matching a "first empty" rule don't all get sent to the same workspace
+            let mut pending_targets: Vec<(usize, usize)> = vec![];
             // Go through all the monitors and workspaces
             for (i, monitor) in self.monitors().iter().enumerate() {
                 for (j, workspace) in monitor.workspaces().iter().enumerate() {
@@ -819,37 +837,15 @@ impl WindowManager {
                         let mut already_moved_window_handles =
                             self.already_moved_window_handles.lock();
 
-                        if let (Ok(exe_name), Ok(title), Ok(class), Ok(path)) =
-                            (window.exe(), window.title(), window.class(), window.path())
-                        {
-                            for rule in &*workspace_matching_rules {
-                                let matched = match &rule.matching_rule {
-                                    MatchingRule::Simple(r) => should_act_individual(
-                                        &title,
-                                        &exe_name,
-                                        &class,
-                                        &path,
-                                        r,
-                                        &regex_identifiers,
-                                    ),
-                                    MatchingRule::Composite(r) => {
-                                        let mut composite_results = vec![];
-                                        for identifier in r {
-                                            composite_results.push(should_act_individual(
-                                                &title,
-                                                &exe_name,
-                                                &class,
-                                                &path,
-                                                identifier,
-                                                &regex_identifiers,
-                                            ));
-                                        }
+                        if let Ok(target) = RuleTarget::try_from(*window) {
+                            let mut matched_legacy_rule = false;
 
-                                        composite_results.iter().all(|&x| x)
-                                    }
-                                };
+                            for rule in &*workspace_matching_rules {
+                                let matched =
+                                    matches_rule(&target, &rule.matching_rule, &regex_identifiers);
 
                                 if matched {
+                                    matched_legacy_rule = true;
                                     let floating = workspace.floating_windows().contains(window);
 
                                     if rule.initial_only {
@@ -857,7 +853,7 @@ impl WindowManager {
                                             already_moved_window_handles.insert(window.hwnd);
 
                                             self.add_window_handle_to_move_based_on_workspace_rule(
-                                                &window.title()?,
+                                                &target.title,
                                                 window.hwnd,
                                                 i,
                                                 j,
@@ -869,7 +865,7 @@ impl WindowManager {
                                         }
                                     } else {
                                         self.add_window_handle_to_move_based_on_workspace_rule(
-                                            &window.title()?,
+                                            &target.title,
                                             window.hwnd,
                                             i,
                                             j,
@@ -881,6 +877,55 @@ impl WindowManager {
                                     }
                                 }
                             }
+
+                            // A window which is already being moved by a workspace rule would
+                            // otherwise be removed from its workspace twice
+                            if matched_legacy_rule {
+                                continue;
+                            }
+
+                            let target_rule = workspace_target_rules.iter().find(|rule| {
+                                matches_rule(&target, &rule.matching_rule, &regex_identifiers)
+                            });
+
+                            if let Some(rule) = target_rule {
+                                if rule.initial_only
+                                    && !already_moved_window_handles.insert(window.hwnd)
+                                {
+                                    continue;
+                                }
+
+                                let resolved = match self.resolve_workspace_target(
+                                    &rule.target,
+                                    window.hwnd,
+                                    (i, j),
+                                    &pending_targets,
+                                ) {
+                                    Ok(resolved) => resolved,
+                                    Err(error) => {
+                                        tracing::error!("ignoring workspace target rule: {error}");
+                                        continue;
+                                    }
+                                };
+
+                                if let Some((target_monitor_idx, target_workspace_idx)) = resolved {
+                                    if (target_monitor_idx, target_workspace_idx) != (i, j) {
+                                        pending_targets
+                                            .push((target_monitor_idx, target_workspace_idx));
+                                    }
+
+                                    self.add_window_handle_to_move_based_on_workspace_rule(
+                                        &target.title,
+                                        window.hwnd,
+                                        i,
+                                        j,
+                                        target_monitor_idx,
+                                        target_workspace_idx,
+                                        workspace.floating_windows().contains(window),
+                                        &mut to_move,
+                                    );
+                                }
+                            }
                         }
                     }
                 }
@@ -978,6 +1023,95 @@ impl WindowManager {
         Ok(())
     }
 
+    /// Resolves a [`WorkspaceTarget`] to a monitor and workspace index pair for the given window,
+    /// which is currently on the workspace at `current`. Workspaces in `pending` are about to
+    /// receive a window and are considered occupied by it for the purpose of policy targets.
+    ///
+    /// Returns an error if the target names a workspace index which doesn't exist on its monitor.
+    pub fn resolve_workspace_target(
+        &self,
+        target: &WorkspaceTarget,
+        hwnd: isize,
+        current: (usize, usize),
+        pending: &[(usize, usize)],
+    ) -> Result<Option<(usize, usize)>> {
+        let monitor_matches = |monitor: &Monitor, id: &str| {
+            monitor
+                .serial_number_id()
+                .as_ref()
+                .is_some_and(|sn| sn == id)
+                || monitor.device_id() == id
+        };
+
+        let (monitor_filter, pick_fewest) = match target {
+            WorkspaceTarget::Named(name) => {
+                return Ok(self.monitors().iter().enumerate().find_map(|(i, monitor)| {
+                    monitor
+                        .workspaces()
+                        .iter()
+                        .position(|workspace| workspace.name().as_deref() == Some(name.as_str()))
+                        .map(|j| (i, j))
+                }));
+            }
+            WorkspaceTarget::Monitor { id, workspace } => {
+                let Some((i, monitor)) = self
+                    .monitors()
+                    .iter()
+                    .enumerate()
+                    .find(|(_, monitor)| monitor_matches(monitor, id))
+                else {
+                    return Ok(None);
+                };
+
+                if *workspace >= monitor.workspaces().len() {
+                    bail!("there is no workspace with index {workspace} on monitor {id}");
+                }
+
+                return Ok(Option::from((i, *workspace)));
+            }
+            WorkspaceTarget::FirstEmpty { monitor } => (monitor, false),
+            WorkspaceTarget::FewestContainers { monitor } => (monitor, true),
+        };
+
+        let mut candidates = vec![];
+        for (i, monitor) in self.monitors().iter().enumerate() {
+            if monitor_filter
+                .as_ref()
+                .is_some_and(|id| !monitor_matches(monitor, id))
+            {
+                continue;
+            }
+
+            for (j, workspace) in monitor.workspaces().iter().enumerate() {
+                let occupancy = workspace_occupancy(workspace, hwnd)
+                    + pending.iter().filter(|p| **p == (i, j)).count();
+                candidates.push(((i, j), occupancy));
+            }
+        }
+
+        // the window's current workspace wins ties so that enforcing a rule repeatedly doesn't
+        // bounce the window between equally suitable workspaces
+        let qualifies = |occupancy: usize| pick_fewest || occupancy == 0;
+        if let Some((_, occupancy)) = candidates.iter().find(|(idx, _)| *idx == current) {
+            let lowest = candidates.iter().map(|(_, o)| *o).min().unwrap_or_default();
+            if qualifies(*occupancy) && (!pick_fewest || *occupancy == lowest) {
+                return Ok(Option::from(current));
+            }
+        }
+
+        if pick_fewest {
+            Ok(candidates
+                .iter()
+                .min_by_key(|(_, occupancy)| *occupancy)
+                .map(|(idx, _)| *idx))
+        } else {
+            Ok(candidates
+                .iter()
+                .find(|(_, occupancy)| *occupancy == 0)
+                .map(|(idx, _)| *idx))
+        }
+    }
+
     #[tracing::instrument(skip(self))]
     pub fn retile_all(&mut self, preserve_resize_dimensions: bool) -> Result<()> {
         let offset = self.work_area_offset;
@@ -1406,8 +1540,9 @@ impl WindowManager {
                 let workspace = self.focused_workspace()?;
                 let focused_hwnd = WindowsApi::foreground_window()?;
 
-                let border_offset = BORDER_OFFSET.load(Ordering::SeqCst);
-                let border_width = BORDER_WIDTH.load(Ordering::SeqCst);
+                let border_settings = workspace.border_settings();
+                let border_offset = border_settings.offset();
+                let border_width = border_settings.width();
                 focused_monitor_work_area.left += border_offset;
                 focused_monitor_work_area.left += border_width;
                 focused_monitor_work_area.top += border_offset;
@@ -1618,10 +1753,13 @@ impl WindowManager {
                 for containers in workspace.containers_mut() {
                     for window in containers.windows_mut() {
                         let should_remove_titlebar_for_window = should_act(
-                            &window.title().unwrap_or_default(),
-                            &window.exe().unwrap_or_default(),
-                            &window.class().unwrap_or_default(),
-                            &window.path().unwrap_or_default(),
+                            &RuleTarget::new(
+                                window.title().unwrap_or_default(),
+                                window.exe().unwrap_or_default(),
+                                window.class().unwrap_or_default(),
+                                window.path().unwrap_or_default(),
+                            )
+                            .with_hwnd(window.hwnd),
                             &no_titlebar,
                             &regex_identifiers,
                         )
@@ -2163,8 +2301,9 @@ impl WindowManager {
         let mouse_follows_focus = self.mouse_follows_focus;
 
         let mut focused_monitor_work_area = self.focused_monitor_work_area()?;
-        let border_offset = BORDER_OFFSET.load(Ordering::SeqCst);
-        let border_width = BORDER_WIDTH.load(Ordering::SeqCst);
+        let border_settings = self.focused_workspace()?.border_settings();
+        let border_offset = border_settings.offset();
+        let border_width = border_settings.width();
         focused_monitor_work_area.left += border_offset;
         focused_monitor_work_area.left += border_width;
         focused_monitor_work_area.top += border_offset;
@@ -2823,22 +2962,66 @@ impl WindowManager {
     pub fn float_window(&mut self) -> Result<()> {
         tracing::info!("floating window");
 
-        let work_area = self.focused_monitor_work_area()?;
-
         let workspace = self.focused_workspace_mut()?;
         workspace.new_floating_window()?;
 
-        let window = workspace
-            .floating_windows_mut()
-            .last_mut()
-            .ok_or_else(|| anyhow!("there is no floating window"))?;
-
-        window.center(&work_area)?;
+        let window = self.place_floating_window(true)?;
         window.focus(self.mouse_follows_focus)?;
 
         Ok(())
     }
 
+    /// Sizes and positions the most recently added floating window on the focused workspace
+    /// according to the first matching floating placement rule, moving it to the focused workspace
+    /// of another monitor if the rule requests it. If no rule matches, the window is centered when
+    /// `center_by_default` is true and left untouched otherwise.
+    pub fn place_floating_window(&mut self, center_by_default: bool) -> Result<Window> {
+        let focused_monitor_idx = self.focused_monitor_idx();
+        let window = *self
+            .focused_workspace()?
+            .floating_windows()
+            .last()
+            .ok_or_else(|| anyhow!("there is no floating window"))?;
+
+        let Some(placement_rule) = window.floating_placement_rule() else {
+            if center_by_default {
+                let work_area = self.focused_monitor_work_area()?;
+                let mut window = window;
+                window.center(&work_area)?;
+            }
+
+            return Ok(window);
+        };
+
+        let target_monitor_idx = placement_rule
+            .monitor
+            .filter(|idx| self.monitors().get(*idx).is_some())
+            .unwrap_or(focused_monitor_idx);
+
+        if target_monitor_idx != focused_monitor_idx {
+            self.focused_workspace_mut()?.floating_windows_mut().pop();
+
+            self.monitors_mut()
+                .get_mut(target_monitor_idx)
+                .ok_or_else(|| anyhow!("there is no monitor at this idx"))?
+                .focused_workspace_mut()
+                .ok_or_else(|| anyhow!("there is no workspace"))?
+                .floating_windows_mut()
+                .push(window);
+        }
+
+        let work_area = *self
+            .monitors()
+            .get(target_monitor_idx)
+            .ok_or_else(|| anyhow!("there is no monitor at this idx"))?
+            .work_area_size();
+
+        let mut window = window;
+        window.place(&work_area, &placement_rule)?;
+
+        Ok(window)
+    }
+
     #[tracing::instrument(skip(self))]
     pub fn unfloat_window(&mut self) -> Result<()> {
         tracing::info!("unfloating window");
@@ -3657,3 +3840,22 @@ impl WindowManager {
         }
     }
 }
+
+/// The number of containers and floating windows on a workspace, not counting the given window
+fn workspace_occupancy(workspace: &Workspace, hwnd: isize) -> usize {
+    let containers = workspace
+        .containers()
+        .iter()
+        .chain(workspace.monocle_container())
+        .filter(|container| !(container.windows().len() == 1 && container.contains_window(hwnd)))
+        .count();
+
+    let floating_windows = workspace
+        .floating_windows()
+        .iter()
+        .chain(workspace.maximized_window())
+        .filter(|window| window.hwnd != hwnd)
+        .count();
+
+    containers + floating_windows
+}
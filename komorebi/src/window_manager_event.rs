@@ -5,6 +5,7 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::window::should_act;
+use crate::window::RuleTarget;
 use crate::window::Window;
 use crate::winevent::WinEvent;
 use crate::OBJECT_NAME_CHANGE_ON_LAUNCH;
@@ -181,20 +182,12 @@ impl WindowManagerEvent {
                     OBJECT_NAME_CHANGE_TITLE_IGNORE_LIST.lock();
                 let regex_identifiers = REGEX_IDENTIFIERS.lock();
 
-                let title = &window.title().ok()?;
-                let exe_name = &window.exe().ok()?;
-                let class = &window.class().ok()?;
-                let path = &window.path().ok()?;
-
-                let mut should_trigger_show = should_act(
-                    title,
-                    exe_name,
-                    class,
-                    path,
-                    &object_name_change_on_launch,
-                    &regex_identifiers,
-                )
-                .is_some();
+                let target = RuleTarget::try_from(window).ok()?;
+                let title = &target.title;
+
+                let mut should_trigger_show =
+                    should_act(&target, &object_name_change_on_launch, &regex_identifiers)
+                        .is_some();
 
                 if should_trigger_show {
                     for r in &*object_name_change_title_ignore_list {
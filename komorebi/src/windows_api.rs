@@ -73,9 +73,13 @@ use windows::Win32::UI::Input::KeyboardAndMouse::MOUSEINPUT;
 use windows::Win32::UI::Input::KeyboardAndMouse::VK_LBUTTON;
 use windows::Win32::UI::Input::KeyboardAndMouse::VK_MENU;
 use windows::Win32::UI::WindowsAndMessaging::AllowSetForegroundWindow;
+use windows::Win32::UI::WindowsAndMessaging::BeginDeferWindowPos;
 use windows::Win32::UI::WindowsAndMessaging::BringWindowToTop;
 use windows::Win32::UI::WindowsAndMessaging::CreateWindowExW;
+use windows::Win32::UI::WindowsAndMessaging::DeferWindowPos;
+use windows::Win32::UI::WindowsAndMessaging::EndDeferWindowPos;
 use windows::Win32::UI::WindowsAndMessaging::EnumWindows;
+use windows::Win32::UI::WindowsAndMessaging::GetClassLongPtrW;
 use windows::Win32::UI::WindowsAndMessaging::GetCursorPos;
 use windows::Win32::UI::WindowsAndMessaging::GetDesktopWindow;
 use windows::Win32::UI::WindowsAndMessaging::GetForegroundWindow;
@@ -95,6 +99,7 @@ use windows::Win32::UI::WindowsAndMessaging::PostMessageW;
 use windows::Win32::UI::WindowsAndMessaging::RealGetWindowClassW;
 use windows::Win32::UI::WindowsAndMessaging::RegisterClassW;
 use windows::Win32::UI::WindowsAndMessaging::RegisterDeviceNotificationW;
+use windows::Win32::UI::WindowsAndMessaging::SendMessageTimeoutW;
 use windows::Win32::UI::WindowsAndMessaging::SetCursorPos;
 use windows::Win32::UI::WindowsAndMessaging::SetForegroundWindow;
 use windows::Win32::UI::WindowsAndMessaging::SetLayeredWindowAttributes;
@@ -105,16 +110,21 @@ use windows::Win32::UI::WindowsAndMessaging::SystemParametersInfoW;
 use windows::Win32::UI::WindowsAndMessaging::WindowFromPoint;
 use windows::Win32::UI::WindowsAndMessaging::CW_USEDEFAULT;
 use windows::Win32::UI::WindowsAndMessaging::DEV_BROADCAST_DEVICEINTERFACE_W;
+use windows::Win32::UI::WindowsAndMessaging::GCLP_HICON;
+use windows::Win32::UI::WindowsAndMessaging::GCLP_HICONSM;
 use windows::Win32::UI::WindowsAndMessaging::GWL_EXSTYLE;
 use windows::Win32::UI::WindowsAndMessaging::GWL_STYLE;
 use windows::Win32::UI::WindowsAndMessaging::GW_HWNDNEXT;
 use windows::Win32::UI::WindowsAndMessaging::HDEVNOTIFY;
+use windows::Win32::UI::WindowsAndMessaging::HICON;
 use windows::Win32::UI::WindowsAndMessaging::HWND_BOTTOM;
 use windows::Win32::UI::WindowsAndMessaging::HWND_TOP;
+use windows::Win32::UI::WindowsAndMessaging::ICON_SMALL2;
 use windows::Win32::UI::WindowsAndMessaging::LWA_ALPHA;
 use windows::Win32::UI::WindowsAndMessaging::REGISTER_NOTIFICATION_FLAGS;
 use windows::Win32::UI::WindowsAndMessaging::SET_WINDOW_POS_FLAGS;
 use windows::Win32::UI::WindowsAndMessaging::SHOW_WINDOW_CMD;
+use windows::Win32::UI::WindowsAndMessaging::SMTO_ABORTIFHUNG;
 use windows::Win32::UI::WindowsAndMessaging::SPIF_SENDCHANGE;
 use windows::Win32::UI::WindowsAndMessaging::SPI_GETACTIVEWINDOWTRACKING;
 use windows::Win32::UI::WindowsAndMessaging::SPI_GETFOREGROUNDLOCKTIMEOUT;
@@ -132,6 +142,7 @@ use windows::Win32::UI::WindowsAndMessaging::SYSTEM_PARAMETERS_INFO_ACTION;
 use windows::Win32::UI::WindowsAndMessaging::SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS;
 use windows::Win32::UI::WindowsAndMessaging::WINDOW_LONG_PTR_INDEX;
 use windows::Win32::UI::WindowsAndMessaging::WM_CLOSE;
+use windows::Win32::UI::WindowsAndMessaging::WM_GETICON;
 use windows::Win32::UI::WindowsAndMessaging::WNDCLASSW;
 use windows::Win32::UI::WindowsAndMessaging::WNDENUMPROC;
 use windows::Win32::UI::WindowsAndMessaging::WS_DISABLED;
@@ -544,6 +555,76 @@ impl WindowsApi {
         .process()
     }
 
+    /// Moves several windows at once, so that they are repositioned in a single screen update
+    pub fn move_windows(moves: &[(isize, Rect)]) -> Result<()> {
+        let flags = SetWindowPosition::NO_ACTIVATE
+            | SetWindowPosition::NO_Z_ORDER
+            | SetWindowPosition::NO_OWNER_Z_ORDER
+            | SetWindowPosition::NO_REDRAW;
+
+        // a window which has been destroyed since the moves were calculated would fail the batch
+        let moves = moves
+            .iter()
+            .filter(|(hwnd, _)| Self::is_window(*hwnd))
+            .map(|(hwnd, layout)| {
+                let hwnd = HWND(as_ptr!(*hwnd));
+
+                let shadow_rect = Self::shadow_rect(hwnd).unwrap_or_default();
+                let rect = Rect {
+                    left: layout.left + shadow_rect.left,
+                    top: layout.top + shadow_rect.top,
+                    right: layout.right + shadow_rect.right,
+                    bottom: layout.bottom + shadow_rect.bottom,
+                };
+
+                (hwnd, rect)
+            })
+            .collect::<Vec<_>>();
+
+        if moves.is_empty() {
+            return Ok(());
+        }
+
+        if let Err(error) = Self::defer_window_positions(&moves, flags.bits()) {
+            tracing::warn!("could not move windows together, moving them individually: {error}");
+
+            for (hwnd, rect) in &moves {
+                if let Err(error) = Self::set_window_pos(*hwnd, rect, HWND::default(), flags.bits())
+                {
+                    tracing::error!("could not move window {}: {error}", hwnd.0 as isize);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Applies window positions in a single deferred batch. If one of the windows can't be added
+    /// to the batch, the system has already destroyed it and none of the positions are applied.
+    fn defer_window_positions(moves: &[(HWND, Rect)], flags: u32) -> Result<()> {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+        let mut positions = unsafe { BeginDeferWindowPos(moves.len() as i32) }.process()?;
+
+        for (hwnd, rect) in moves {
+            // the batch must not be ended when this fails, as its handle is no longer valid
+            positions = unsafe {
+                DeferWindowPos(
+                    positions,
+                    *hwnd,
+                    None,
+                    rect.left,
+                    rect.top,
+                    rect.right,
+                    rect.bottom,
+                    SET_WINDOW_POS_FLAGS(flags),
+                )
+            }
+            .process()?;
+        }
+
+        unsafe { EndDeferWindowPos(positions) }.process()
+    }
+
     pub fn move_window(hwnd: isize, layout: &Rect, repaint: bool) -> Result<()> {
         let hwnd = HWND(as_ptr!(hwnd));
 
@@ -581,6 +662,35 @@ impl WindowsApi {
         }
     }
 
+    /// The small icon of a window, falling back to the icons of its window class
+    pub fn window_icon(hwnd: isize) -> Option<HICON> {
+        let hwnd = HWND(as_ptr!(hwnd));
+        let mut icon: usize = 0;
+
+        unsafe {
+            // don't wait on windows which are not responding
+            let _ = SendMessageTimeoutW(
+                hwnd,
+                WM_GETICON,
+                WPARAM(ICON_SMALL2 as usize),
+                LPARAM(0),
+                SMTO_ABORTIFHUNG,
+                100,
+                Some(&raw mut icon),
+            );
+
+            if icon == 0 {
+                icon = GetClassLongPtrW(hwnd, GCLP_HICONSM);
+            }
+
+            if icon == 0 {
+                icon = GetClassLongPtrW(hwnd, GCLP_HICON);
+            }
+        }
+
+        (icon != 0).then_some(HICON(as_ptr!(icon)))
+    }
+
     pub fn hide_window(hwnd: isize) {
         Self::show_window(hwnd, SW_HIDE);
     }
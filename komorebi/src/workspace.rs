@@ -22,14 +22,14 @@ use crate::core::Layout;
 use crate::core::OperationDirection;
 use crate::core::Rect;
 
-use crate::border_manager::BORDER_OFFSET;
-use crate::border_manager::BORDER_WIDTH;
+use crate::border_manager::BorderSettings;
 use crate::container::Container;
 use crate::ring::Ring;
 use crate::should_act;
-use crate::stackbar_manager;
-use crate::stackbar_manager::STACKBAR_TAB_HEIGHT;
+use crate::stackbar_manager::StackbarSettings;
 use crate::static_config::WorkspaceConfig;
+use crate::transparency_manager::TransparencyProfile;
+use crate::window::RuleTarget;
 use crate::window::Window;
 use crate::window::WindowDetails;
 use crate::windows_api::WindowsApi;
@@ -152,17 +152,7 @@ pub enum WorkspaceWindowLocation {
 }
 
 #[derive(
-    Debug,
-    Default,
-    Copy,
-    Clone,
-    Serialize,
-    Deserialize,
-    Getters,
-    CopyGetters,
-    MutGetters,
-    Setters,
-    PartialEq,
+    Debug, Clone, Serialize, Deserialize, Getters, CopyGetters, MutGetters, Setters, PartialEq,
 )]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 /// Settings setup either by the parent monitor or by the `WindowManager`
@@ -173,6 +163,36 @@ pub struct WorkspaceGlobals {
     pub work_area_offset: Option<Rect>,
     pub window_based_work_area_offset: Option<Rect>,
     pub window_based_work_area_offset_limit: isize,
+    /// Scale factor of the parent monitor, used for overrides which are given in scaled sizes
+    #[serde(skip, default = "unscaled")]
+    pub scale_factor: f32,
+    /// Border settings of the parent monitor
+    #[serde(skip)]
+    pub border: BorderSettings,
+    /// Stackbar settings of the parent monitor
+    #[serde(skip)]
+    pub stackbar: StackbarSettings,
+}
+
+impl Default for WorkspaceGlobals {
+    fn default() -> Self {
+        Self {
+            container_padding: None,
+            workspace_padding: None,
+            work_area: Rect::default(),
+            work_area_offset: None,
+            window_based_work_area_offset: None,
+            window_based_work_area_offset_limit: 0,
+            scale_factor: unscaled(),
+            border: BorderSettings::default(),
+            stackbar: StackbarSettings::default(),
+        }
+    }
+}
+
+/// The scale factor of a workspace until its monitor has set it, which is 100% scaling
+const fn unscaled() -> f32 {
+    1.0
 }
 
 impl Workspace {
@@ -327,6 +347,47 @@ impl Workspace {
         Ok(())
     }
 
+    /// Border settings for this workspace, with its own overrides applied over the settings of
+    /// its monitor
+    pub fn border_settings(&self) -> BorderSettings {
+        let mut settings = self.globals().border.clone();
+
+        if let Some(overrides) = self
+            .workspace_config()
+            .as_ref()
+            .and_then(|config| config.border.as_ref())
+        {
+            settings.apply(overrides, self.globals().scale_factor);
+        }
+
+        settings
+    }
+
+    /// Stackbar settings for this workspace, with its own overrides applied over the settings of
+    /// its monitor
+    pub fn stackbar_settings(&self) -> StackbarSettings {
+        let mut settings = self.globals().stackbar;
+
+        if let Some(overrides) = self
+            .workspace_config()
+            .as_ref()
+            .and_then(|config| config.stackbar.as_ref())
+        {
+            settings.apply(overrides, self.globals().scale_factor);
+        }
+
+        settings
+    }
+
+    /// Transparency profile for this workspace, which is used for windows without an
+    /// application-specific transparency rule
+    pub fn transparency_profile(&self) -> TransparencyProfile {
+        self.workspace_config()
+            .as_ref()
+            .and_then(|config| config.transparency)
+            .unwrap_or_default()
+    }
+
     pub fn update(&mut self) -> Result<()> {
         if !INITIAL_CONFIGURATION_LOADED.load(Ordering::SeqCst) {
             return Ok(());
@@ -345,6 +406,8 @@ impl Workspace {
         let window_based_work_area_offset = self.globals().window_based_work_area_offset;
         let window_based_work_area_offset_limit =
             self.globals().window_based_work_area_offset_limit;
+        let border_settings = self.border_settings();
+        let stackbar_settings = self.stackbar_settings();
 
         let mut adjusted_work_area = work_area_offset.map_or_else(
             || work_area,
@@ -413,9 +476,9 @@ impl Workspace {
                 if let Some(window) = container.focused_window_mut() {
                     adjusted_work_area.add_padding(container_padding);
                     {
-                        let border_offset = BORDER_OFFSET.load(Ordering::SeqCst);
+                        let border_offset = border_settings.offset();
                         adjusted_work_area.add_padding(border_offset);
-                        let width = BORDER_WIDTH.load(Ordering::SeqCst);
+                        let width = border_settings.width();
                         adjusted_work_area.add_padding(width);
                     }
                     window.set_position(&adjusted_work_area, true)?;
@@ -446,15 +509,15 @@ impl Workspace {
 
                     if let Some(layout) = layouts.get_mut(i) {
                         {
-                            let border_offset = BORDER_OFFSET.load(Ordering::SeqCst);
+                            let border_offset = border_settings.offset();
                             layout.add_padding(border_offset);
 
-                            let width = BORDER_WIDTH.load(Ordering::SeqCst);
+                            let width = border_settings.width();
                             layout.add_padding(width);
                         }
 
-                        if stackbar_manager::should_have_stackbar(window_count) {
-                            let tab_height = STACKBAR_TAB_HEIGHT.load(Ordering::SeqCst);
+                        if stackbar_settings.should_have_stackbar(window_count) {
+                            let tab_height = stackbar_settings.height();
                             let total_height = tab_height + container_padding;
 
                             layout.top += total_height;
@@ -467,10 +530,13 @@ impl Workspace {
                                 .is_some_and(|w| w.hwnd == window.hwnd)
                             {
                                 let should_remove_titlebar_for_window = should_act(
-                                    &window.title().unwrap_or_default(),
-                                    &window.exe().unwrap_or_default(),
-                                    &window.class().unwrap_or_default(),
-                                    &window.path().unwrap_or_default(),
+                                    &RuleTarget::new(
+                                        window.title().unwrap_or_default(),
+                                        window.exe().unwrap_or_default(),
+                                        window.class().unwrap_or_default(),
+                                        window.path().unwrap_or_default(),
+                                    )
+                                    .with_hwnd(window.hwnd),
                                     &no_titlebar,
                                     &regex_identifiers,
                                 )
@@ -0,0 +1,214 @@
+use komorebi::animation::style::apply_ease_func;
+use komorebi::animation::style::CubicBezier;
+use komorebi::core::AnimationStyle;
+use komorebi::core::Spring;
+use std::str::FromStr;
+use std::time::Duration;
+
+fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+    assert!(
+        (actual - expected).abs() < tolerance,
+        "expected {expected}, got {actual}"
+    );
+}
+
+#[test]
+fn linear_cubic_bezier_is_linear() {
+    let curve = CubicBezier::new(0.0, 0.0, 1.0, 1.0);
+
+    for t in [0.1, 0.25, 0.5, 0.75, 0.9] {
+        assert_close(curve.evaluate(t), t, 1e-6);
+    }
+}
+
+#[test]
+fn cubic_bezier_matches_the_css_ease_curve() {
+    // reference values for cubic-bezier(0.25, 0.1, 0.25, 1.0) as computed by browsers
+    let ease = CubicBezier::new(0.25, 0.1, 0.25, 1.0);
+
+    assert_close(ease.evaluate(0.25), 0.4094, 1e-3);
+    assert_close(ease.evaluate(0.5), 0.8024, 1e-3);
+    assert_close(ease.evaluate(0.75), 0.9604, 1e-3);
+}
+
+#[test]
+fn cubic_bezier_is_pinned_at_the_ends() {
+    let curve = CubicBezier::new(0.68, -0.6, 0.32, 1.6);
+
+    assert_eq!(curve.evaluate(0.0), 0.0);
+    assert_eq!(curve.evaluate(1.0), 1.0);
+    assert_eq!(curve.evaluate(-0.5), 0.0);
+    assert_eq!(curve.evaluate(1.5), 1.0);
+    // y control points outside of 0.0..=1.0 overshoot
+    assert!(curve.evaluate(0.1) < 0.0);
+    assert!(curve.evaluate(0.9) > 1.0);
+}
+
+#[test]
+fn cubic_bezier_solves_flat_curves() {
+    // x barely moves at the start of this curve, where newton's method can't make progress
+    let curve = CubicBezier::new(1.0, 0.0, 1.0, 0.0);
+
+    let mut previous = 0.0;
+    for step in 1..100 {
+        let progress = curve.evaluate(f64::from(step) / 100.0);
+        assert!(progress.is_finite());
+        assert!(progress >= previous - 1e-6);
+        previous = progress;
+    }
+}
+
+#[test]
+fn spring_starts_at_rest_and_settles() {
+    let spring = Spring::default();
+    let duration = spring.duration().as_secs_f64();
+
+    assert_eq!(spring.state(1.0, 0.0, 0.0), (1.0, 0.0));
+
+    let (displacement, velocity) = spring.state(1.0, 0.0, duration);
+    assert!(displacement.abs() < 0.01, "{displacement}");
+    assert!(velocity.abs() < 0.1, "{velocity}");
+
+    assert_close(
+        apply_ease_func(0.0, AnimationStyle::Spring(spring)),
+        0.0,
+        1e-9,
+    );
+    assert_close(
+        apply_ease_func(1.0, AnimationStyle::Spring(spring)),
+        1.0,
+        0.01,
+    );
+}
+
+#[test]
+fn spring_state_covers_every_damping_regime() {
+    for damping in [5.0, 26.0, 2.0 * 170.0f64.sqrt(), 80.0] {
+        let spring = Spring {
+            damping,
+            ..Default::default()
+        };
+
+        // the velocity is the rate of change of the displacement
+        let (t, dt) = (0.05, 1e-6);
+        let (x, v) = spring.state(1.0, 2.0, t);
+        let (next, _) = spring.state(1.0, 2.0, t + dt);
+        assert_close((next - x) / dt, v, 1e-3);
+
+        assert_eq!(spring.state(1.0, 2.0, 0.0).0, 1.0);
+        assert_close(spring.state(1.0, 2.0, 0.0).1, 2.0, 1e-9);
+    }
+}
+
+#[test]
+fn underdamped_springs_overshoot() {
+    let bouncy = Spring {
+        damping: 5.0,
+        ..Default::default()
+    };
+    let stiff = Spring {
+        damping: 80.0,
+        ..Default::default()
+    };
+
+    let overshoot = |spring: &Spring| {
+        (1..100)
+            .map(|step| spring.state(1.0, 0.0, f64::from(step) / 100.0).0)
+            .fold(f64::MAX, f64::min)
+    };
+
+    assert!(overshoot(&bouncy) < 0.0);
+    assert!(overshoot(&stiff) >= 0.0);
+}
+
+#[test]
+fn spring_duration_depends_on_damping() {
+    let bouncy = Spring {
+        damping: 5.0,
+        ..Default::default()
+    };
+
+    assert!(bouncy.duration() > Spring::default().duration());
+    assert!(Spring::default().duration() < Duration::from_secs(1));
+
+    let undamped = Spring {
+        damping: 0.0,
+        ..Default::default()
+    };
+    assert_eq!(undamped.duration(), Duration::from_secs(10));
+
+    let configured = Duration::from_millis(250);
+    assert_eq!(
+        AnimationStyle::Spring(bouncy).duration(configured),
+        bouncy.duration()
+    );
+    assert_eq!(AnimationStyle::Linear.duration(configured), configured);
+}
+
+#[test]
+fn partial_springs_use_the_default_values() {
+    let style: AnimationStyle = serde_json::from_str(r#"{"Spring":{"stiffness":300}}"#).unwrap();
+
+    assert_eq!(
+        style,
+        AnimationStyle::Spring(Spring {
+            stiffness: 300.0,
+            damping: 26.0,
+            mass: 1.0,
+        })
+    );
+}
+
+#[test]
+fn named_styles_are_parsed_in_any_case() {
+    for input in [
+        "EaseOutCubic",
+        "ease-out-cubic",
+        "ease_out_cubic",
+        "easeoutcubic",
+    ] {
+        assert_eq!(
+            AnimationStyle::from_str(input),
+            Ok(AnimationStyle::EaseOutCubic)
+        );
+    }
+
+    assert_eq!(
+        AnimationStyle::from_str("linear"),
+        Ok(AnimationStyle::Linear)
+    );
+    assert!(AnimationStyle::from_str("ease-out-wobbly").is_err());
+}
+
+#[test]
+fn parameterised_styles_are_parsed() {
+    assert_eq!(
+        AnimationStyle::from_str("cubic-bezier(0.25, 0.1, 0.25, 1)"),
+        Ok(AnimationStyle::CubicBezier(0.25, 0.1, 0.25, 1.0))
+    );
+    assert_eq!(
+        AnimationStyle::from_str("spring"),
+        Ok(AnimationStyle::Spring(Spring::default()))
+    );
+    assert_eq!(
+        AnimationStyle::from_str("spring(300, 20, 2)"),
+        Ok(AnimationStyle::Spring(Spring {
+            stiffness: 300.0,
+            damping: 20.0,
+            mass: 2.0,
+        }))
+    );
+}
+
+#[test]
+fn malformed_styles_are_rejected() {
+    for input in [
+        "cubic-bezier(0.25, 0.1, 0.25)",
+        "cubic-bezier(0.25, 0.1, 0.25, 1",
+        "cubic-bezier(a, b, c, d)",
+        "spring(300)",
+        "linear(1)",
+    ] {
+        assert!(AnimationStyle::from_str(input).is_err(), "{input}");
+    }
+}
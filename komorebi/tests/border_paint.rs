@@ -0,0 +1,84 @@
+use komorebi::animation::lerp::Lerp;
+use komorebi::border_manager::BorderPaint;
+use komorebi::AnimationStyle;
+use komorebi::Rgb;
+
+const RED: Rgb = Rgb::new(255, 0, 0);
+const GREEN: Rgb = Rgb::new(0, 255, 0);
+const BLUE: Rgb = Rgb::new(0, 0, 255);
+
+fn paint(colours: &[Rgb], angle: f64) -> BorderPaint {
+    BorderPaint {
+        colours: colours.to_vec(),
+        angle,
+        width: 8,
+        offset: -1,
+    }
+}
+
+fn lerp(start: &BorderPaint, end: &BorderPaint, time: f64) -> BorderPaint {
+    start
+        .clone()
+        .lerp(end.clone(), time, AnimationStyle::Linear)
+}
+
+#[test]
+fn gradients_are_resampled_to_the_larger_number_of_stops() {
+    let two = paint(&[RED, BLUE], 0.0);
+    let three = paint(&[RED, GREEN, BLUE], 0.0);
+
+    // the two colour gradient is sampled halfway along for the middle stop
+    let start = lerp(&two, &three, 0.0);
+    assert_eq!(
+        start.colours,
+        [RED, RED.lerp(BLUE, 0.5, AnimationStyle::Linear), BLUE]
+    );
+
+    assert_eq!(lerp(&two, &three, 1.0).colours, [RED, GREEN, BLUE]);
+    assert_eq!(lerp(&three, &two, 1.0).colours.len(), 3);
+}
+
+#[test]
+fn solid_colours_are_spread_across_every_stop() {
+    let solid = paint(&[GREEN], 0.0);
+    let gradient = paint(&[RED, BLUE], 45.0);
+
+    assert_eq!(lerp(&solid, &gradient, 0.0).colours, [GREEN, GREEN]);
+    assert_eq!(lerp(&gradient, &solid, 1.0).colours, [GREEN, GREEN]);
+    assert_eq!(lerp(&solid, &solid, 0.5).colours, [GREEN]);
+}
+
+#[test]
+fn gradients_turn_the_shortest_way_around() {
+    let start = paint(&[RED, BLUE], 350.0);
+    let end = paint(&[RED, BLUE], 10.0);
+
+    assert!((lerp(&start, &end, 0.5).angle - 360.0).abs() < 1e-9);
+    assert!((lerp(&start, &end, 1.0).angle - 370.0).abs() < 1e-9);
+    assert!((lerp(&end, &start, 0.5).angle - 0.0).abs() < 1e-9);
+
+    let quarter = paint(&[RED, BLUE], 90.0);
+    assert!((lerp(&end, &quarter, 0.5).angle - 50.0).abs() < 1e-9);
+}
+
+#[test]
+fn solid_colours_take_the_angle_of_the_gradient() {
+    let solid = paint(&[GREEN], 0.0);
+    let gradient = paint(&[RED, BLUE], 135.0);
+
+    assert!((lerp(&solid, &gradient, 0.5).angle - 135.0).abs() < 1e-9);
+    assert!((lerp(&gradient, &solid, 0.5).angle - 135.0).abs() < 1e-9);
+}
+
+#[test]
+fn width_and_offset_are_interpolated() {
+    let thin = BorderPaint {
+        width: 2,
+        offset: 0,
+        ..paint(&[RED], 0.0)
+    };
+    let thick = paint(&[RED], 0.0);
+
+    let halfway = lerp(&thin, &thick, 0.5);
+    assert_eq!((halfway.width, halfway.offset), (5, -1));
+}
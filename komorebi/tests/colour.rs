@@ -0,0 +1,87 @@
+use komorebi::static_config::BorderColours;
+use komorebi::BorderColour;
+use komorebi::Colour;
+use komorebi::Gradient;
+use komorebi::Oklab;
+use komorebi::Rgb;
+
+#[test]
+fn oklab_round_trips_every_grey() {
+    for channel in 0..=255 {
+        let grey = Rgb::new(channel, channel, channel);
+        assert_eq!(Rgb::from(Oklab::from(grey)), grey);
+    }
+}
+
+#[test]
+fn oklab_round_trips_colours() {
+    for r in (0..=255).step_by(15) {
+        for g in (0..=255).step_by(15) {
+            for b in (0..=255).step_by(15) {
+                let rgb = Rgb::new(r, g, b);
+                assert_eq!(Rgb::from(Oklab::from(rgb)), rgb);
+            }
+        }
+    }
+}
+
+#[test]
+fn oklab_matches_reference_values() {
+    let assert_oklab = |rgb: Rgb, (l, a, b): (f64, f64, f64)| {
+        let oklab = Oklab::from(rgb);
+        assert!((oklab.l - l).abs() < 1e-3, "{rgb:?}: {oklab:?}");
+        assert!((oklab.a - a).abs() < 1e-3, "{rgb:?}: {oklab:?}");
+        assert!((oklab.b - b).abs() < 1e-3, "{rgb:?}: {oklab:?}");
+    };
+
+    assert_oklab(Rgb::new(255, 255, 255), (1.0, 0.0, 0.0));
+    assert_oklab(Rgb::new(0, 0, 0), (0.0, 0.0, 0.0));
+    assert_oklab(Rgb::new(255, 0, 0), (0.628, 0.225, 0.126));
+    assert_oklab(Rgb::new(0, 0, 255), (0.452, -0.032, -0.312));
+}
+
+#[test]
+fn out_of_gamut_oklab_colours_are_clamped() {
+    let rgb = Rgb::from(Oklab {
+        l: 1.5,
+        a: 0.4,
+        b: -0.4,
+    });
+
+    assert!(rgb.r <= 255 && rgb.g <= 255 && rgb.b <= 255);
+}
+
+#[test]
+fn empty_gradients_are_rejected() {
+    let empty = BorderColour::Gradient(Gradient {
+        colours: vec![],
+        angle: None,
+    });
+    let gradient = BorderColour::Gradient(Gradient {
+        colours: vec![Colour::from(Rgb::new(255, 0, 0))],
+        angle: Some(90.0),
+    });
+
+    assert!(empty.validate().is_err());
+    assert!(gradient.validate().is_ok());
+    assert!(BorderColour::from(Colour::from(Rgb::new(0, 0, 0)))
+        .validate()
+        .is_ok());
+}
+
+#[test]
+fn border_colours_with_an_empty_gradient_are_rejected() {
+    let colours: BorderColours = serde_json::from_str(
+        r##"{ "single": "#ff0000", "stack": { "colours": ["#00ff00", "#0000ff"] }, "unfocused": { "colours": [] } }"##,
+    )
+    .unwrap();
+
+    assert!(colours.validate().is_err());
+
+    let colours = BorderColours {
+        unfocused: None,
+        ..colours
+    };
+
+    assert!(colours.validate().is_ok());
+}
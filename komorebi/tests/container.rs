@@ -0,0 +1,70 @@
+use komorebi::container::Container;
+use komorebi::window::Window;
+
+fn stack(hwnds: &[isize], focused_idx: usize) -> Container {
+    let mut container = Container::default();
+    for hwnd in hwnds {
+        container.windows_mut().push_back(Window::from(*hwnd));
+    }
+
+    container.focus_window(focused_idx);
+    container
+}
+
+fn hwnds(container: &Container) -> Vec<isize> {
+    container.windows().iter().map(|window| window.hwnd).collect()
+}
+
+fn focused_hwnd(container: &Container) -> Option<isize> {
+    container.focused_window().map(|window| window.hwnd)
+}
+
+#[test]
+fn moving_a_window_shifts_the_windows_in_between() {
+    let mut container = stack(&[1, 2, 3, 4], 0);
+
+    container.move_window(0, 2);
+    assert_eq!(hwnds(&container), [2, 3, 1, 4]);
+
+    container.move_window(3, 0);
+    assert_eq!(hwnds(&container), [4, 2, 3, 1]);
+}
+
+#[test]
+fn moving_the_focused_window_keeps_it_focused() {
+    let mut container = stack(&[1, 2, 3, 4], 1);
+
+    container.move_window(1, 3);
+
+    assert_eq!(hwnds(&container), [1, 3, 4, 2]);
+    assert_eq!(focused_hwnd(&container), Some(2));
+    assert_eq!(container.focused_window_idx(), 3);
+}
+
+#[test]
+fn moving_another_window_keeps_the_focused_window_focused() {
+    let mut container = stack(&[1, 2, 3, 4], 2);
+
+    // the focused window is shifted towards the start
+    container.move_window(0, 3);
+    assert_eq!(hwnds(&container), [2, 3, 4, 1]);
+    assert_eq!(focused_hwnd(&container), Some(3));
+    assert_eq!(container.focused_window_idx(), 1);
+
+    // the focused window is shifted towards the end
+    container.move_window(3, 0);
+    assert_eq!(focused_hwnd(&container), Some(3));
+    assert_eq!(container.focused_window_idx(), 2);
+}
+
+#[test]
+fn moves_to_the_same_or_an_invalid_index_are_ignored() {
+    let mut container = stack(&[1, 2, 3], 1);
+
+    container.move_window(1, 1);
+    container.move_window(0, 3);
+    container.move_window(5, 0);
+
+    assert_eq!(hwnds(&container), [1, 2, 3]);
+    assert_eq!(focused_hwnd(&container), Some(2));
+}
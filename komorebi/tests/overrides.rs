@@ -0,0 +1,143 @@
+use komorebi::border_manager::BorderSettings;
+use komorebi::border_manager::BORDER_OFFSET;
+use komorebi::border_manager::BORDER_WIDTH;
+use komorebi::stackbar_manager::StackbarSettings;
+use komorebi::static_config::BorderColours;
+use komorebi::static_config::BorderOverrides;
+use komorebi::static_config::ScalableSize;
+use komorebi::static_config::StackbarOverrides;
+use komorebi::workspace::WorkspaceGlobals;
+use komorebi::BorderColour;
+use komorebi::Colour;
+use komorebi::Rgb;
+use std::sync::atomic::Ordering;
+
+fn colour(r: u32, g: u32, b: u32) -> BorderColour {
+    BorderColour::from(Colour::from(Rgb::new(r, g, b)))
+}
+
+#[test]
+fn pixels_are_not_scaled() {
+    assert_eq!(ScalableSize::Pixels(8).to_pixels(1.5), 8);
+    assert_eq!(ScalableSize::Pixels(-2).to_pixels(2.0), -2);
+}
+
+#[test]
+fn scaled_sizes_are_multiplied_by_the_scale_factor() {
+    assert_eq!(ScalableSize::Scaled { scaled: 8.0 }.to_pixels(1.0), 8);
+    assert_eq!(ScalableSize::Scaled { scaled: 8.0 }.to_pixels(1.5), 12);
+    assert_eq!(ScalableSize::Scaled { scaled: 5.0 }.to_pixels(1.25), 6);
+    assert_eq!(ScalableSize::Scaled { scaled: -3.0 }.to_pixels(2.0), -6);
+}
+
+#[test]
+fn scaled_sizes_are_never_scaled_down() {
+    assert_eq!(ScalableSize::Scaled { scaled: 8.0 }.to_pixels(0.0), 8);
+    assert_eq!(ScalableSize::Scaled { scaled: 8.0 }.to_pixels(0.5), 8);
+}
+
+#[test]
+fn scalable_sizes_are_read_as_pixels_or_scaled() {
+    let sizes: Vec<ScalableSize> = serde_json::from_str(r#"[4, { "scaled": 2.5 }]"#).unwrap();
+
+    assert_eq!(
+        sizes,
+        [
+            ScalableSize::Pixels(4),
+            ScalableSize::Scaled { scaled: 2.5 }
+        ]
+    );
+}
+
+#[test]
+fn workspaces_are_unscaled_until_their_monitor_sets_a_scale_factor() {
+    assert_eq!(WorkspaceGlobals::default().scale_factor, 1.0);
+
+    // the scale factor isn't part of the serialized state
+    let globals = WorkspaceGlobals {
+        scale_factor: 2.0,
+        ..Default::default()
+    };
+    let restored: WorkspaceGlobals =
+        serde_json::from_str(&serde_json::to_string(&globals).unwrap()).unwrap();
+    assert_eq!(restored.scale_factor, 1.0);
+}
+
+#[test]
+fn border_settings_fall_back_to_the_global_settings() {
+    let settings = BorderSettings::default();
+
+    assert_eq!(settings.width(), BORDER_WIDTH.load(Ordering::SeqCst));
+    assert_eq!(settings.offset(), BORDER_OFFSET.load(Ordering::SeqCst));
+}
+
+#[test]
+fn workspace_border_overrides_take_precedence_over_monitor_overrides() {
+    let monitor = BorderOverrides {
+        width: Some(ScalableSize::Scaled { scaled: 4.0 }),
+        offset: Some(ScalableSize::Pixels(2)),
+        colours: Some(BorderColours {
+            single: Some(colour(255, 0, 0)),
+            stack: Some(colour(0, 255, 0)),
+            ..Default::default()
+        }),
+    };
+    let workspace = BorderOverrides {
+        width: Some(ScalableSize::Pixels(10)),
+        offset: None,
+        colours: Some(BorderColours {
+            single: Some(colour(0, 0, 255)),
+            ..Default::default()
+        }),
+    };
+
+    let mut settings = BorderSettings::default();
+    settings.apply(&monitor, 2.0);
+
+    assert_eq!(settings.width(), 8);
+    assert_eq!(settings.offset(), 2);
+
+    settings.apply(&workspace, 2.0);
+
+    assert_eq!(settings.width(), 10);
+    // anything the workspace doesn't override is inherited from the monitor
+    assert_eq!(settings.offset(), 2);
+
+    let colours = settings.colours.unwrap();
+    assert_eq!(colours.single, Some(colour(0, 0, 255)));
+    assert_eq!(colours.stack, Some(colour(0, 255, 0)));
+    assert_eq!(colours.monocle, None);
+}
+
+#[test]
+fn workspace_stackbar_overrides_take_precedence_over_monitor_overrides() {
+    let monitor = StackbarOverrides {
+        height: Some(ScalableSize::Scaled { scaled: 20.0 }),
+        tab_width: Some(ScalableSize::Pixels(100)),
+        focused_text: Some(Colour::from(Rgb::new(255, 0, 0))),
+        ..Default::default()
+    };
+    let workspace = StackbarOverrides {
+        height: Some(ScalableSize::Pixels(24)),
+        font_size: Some(ScalableSize::Scaled { scaled: 10.0 }),
+        ..Default::default()
+    };
+
+    let mut settings = StackbarSettings::default();
+    settings.apply(&monitor, 1.5);
+
+    assert_eq!(settings.height, Some(30));
+    assert_eq!(settings.tab_width, Some(100));
+    assert_eq!(settings.font_size, None);
+
+    settings.apply(&workspace, 1.5);
+
+    assert_eq!(settings.height, Some(24));
+    assert_eq!(settings.tab_width, Some(100));
+    assert_eq!(settings.font_size, Some(15));
+    assert_eq!(
+        settings.focused_text_colour,
+        Some(u32::from(Colour::from(Rgb::new(255, 0, 0))))
+    );
+    assert_eq!(settings.background_colour, None);
+}
@@ -0,0 +1,306 @@
+use komorebi::core::config_generation::IdWithIdentifier;
+use komorebi::core::config_generation::MatchingRule;
+use komorebi::core::config_generation::MatchingStrategy;
+use komorebi::core::ApplicationIdentifier;
+use komorebi::rule_index::RuleIndex;
+use komorebi::styles::ExtendedWindowStyle;
+use komorebi::styles::WindowStyle;
+use komorebi::RuleTarget;
+use regex::Regex;
+use std::collections::HashMap;
+
+fn simple(kind: ApplicationIdentifier, id: &str, strategy: MatchingStrategy) -> MatchingRule {
+    MatchingRule::Simple(IdWithIdentifier {
+        kind,
+        id: id.to_string(),
+        matching_strategy: Option::from(strategy),
+    })
+}
+
+#[test]
+fn first_matching_rule_wins_across_index_kinds() {
+    let mut regex_identifiers = HashMap::new();
+    regex_identifiers.insert(String::from("^Settings"), Regex::new("^Settings").unwrap());
+
+    let rules = [
+        simple(
+            ApplicationIdentifier::Title,
+            "Setting",
+            MatchingStrategy::Contains,
+        ),
+        simple(
+            ApplicationIdentifier::Title,
+            "^Settings",
+            MatchingStrategy::Regex,
+        ),
+        simple(
+            ApplicationIdentifier::Title,
+            "Sett",
+            MatchingStrategy::StartsWith,
+        ),
+        simple(
+            ApplicationIdentifier::Exe,
+            "app.exe",
+            MatchingStrategy::Equals,
+        ),
+    ];
+
+    let target = RuleTarget::new(
+        String::from("Settings"),
+        String::from("app.exe"),
+        String::from("AppClass"),
+        String::from("C:\\app.exe"),
+    );
+
+    for skip in 0..rules.len() {
+        let index = RuleIndex::from(rules[skip..].to_vec());
+        assert_eq!(
+            index.find(&target, &regex_identifiers),
+            Some(&rules[skip]),
+            "expected rule {skip} to be the first match"
+        );
+    }
+}
+
+#[test]
+fn nested_rules_are_evaluated() {
+    let index = RuleIndex::from(vec![MatchingRule::All {
+        all: vec![
+            simple(
+                ApplicationIdentifier::Exe,
+                "app.exe",
+                MatchingStrategy::Equals,
+            ),
+            MatchingRule::Not {
+                not: Box::new(MatchingRule::Any {
+                    any: vec![
+                        simple(
+                            ApplicationIdentifier::Title,
+                            "Main",
+                            MatchingStrategy::Equals,
+                        ),
+                        simple(
+                            ApplicationIdentifier::Class,
+                            "MainClass",
+                            MatchingStrategy::Equals,
+                        ),
+                    ],
+                }),
+            },
+        ],
+    }]);
+
+    let regex_identifiers = HashMap::new();
+
+    let popup = RuleTarget::new(
+        String::from("Popup"),
+        String::from("app.exe"),
+        String::from("PopupClass"),
+        String::new(),
+    );
+
+    let main = RuleTarget::new(
+        String::from("Popup"),
+        String::from("app.exe"),
+        String::from("MainClass"),
+        String::new(),
+    );
+
+    assert!(index.find(&popup, &regex_identifiers).is_some());
+    assert!(index.find(&main, &regex_identifiers).is_none());
+}
+
+fn target() -> RuleTarget {
+    RuleTarget::new(
+        String::from("Settings"),
+        String::from("app.exe"),
+        String::from("AppClass"),
+        String::from("C:\\app.exe"),
+    )
+    .with_command_line(String::from("app.exe --profile work"))
+    .with_parent_process(String::from("explorer.exe"))
+    .with_monitor(vec![String::from("DELA0C8"), String::from("0x4C36")])
+    .with_window_style(WindowStyle::CAPTION | WindowStyle::THICKFRAME)
+    .with_extended_window_style(ExtendedWindowStyle::TOOLWINDOW)
+    .with_size(800, 600)
+}
+
+fn matches(rule: MatchingRule) -> bool {
+    RuleIndex::from(vec![rule])
+        .find(&target(), &HashMap::new())
+        .is_some()
+}
+
+#[test]
+fn any_group_matches_if_one_rule_matches() {
+    assert!(matches(MatchingRule::Any {
+        any: vec![
+            simple(
+                ApplicationIdentifier::Exe,
+                "other.exe",
+                MatchingStrategy::Equals,
+            ),
+            simple(
+                ApplicationIdentifier::Class,
+                "AppClass",
+                MatchingStrategy::Equals,
+            ),
+        ],
+    }));
+
+    assert!(!matches(MatchingRule::Any {
+        any: vec![
+            simple(
+                ApplicationIdentifier::Exe,
+                "other.exe",
+                MatchingStrategy::Equals,
+            ),
+            simple(
+                ApplicationIdentifier::Class,
+                "OtherClass",
+                MatchingStrategy::Equals,
+            ),
+        ],
+    }));
+
+    assert!(!matches(MatchingRule::Any { any: vec![] }));
+}
+
+#[test]
+fn all_group_matches_if_every_rule_matches() {
+    assert!(matches(MatchingRule::All {
+        all: vec![
+            simple(
+                ApplicationIdentifier::Exe,
+                "app.exe",
+                MatchingStrategy::Equals,
+            ),
+            simple(
+                ApplicationIdentifier::Title,
+                "Sett",
+                MatchingStrategy::StartsWith,
+            ),
+        ],
+    }));
+
+    assert!(!matches(MatchingRule::All {
+        all: vec![
+            simple(
+                ApplicationIdentifier::Exe,
+                "app.exe",
+                MatchingStrategy::Equals,
+            ),
+            simple(
+                ApplicationIdentifier::Title,
+                "Main",
+                MatchingStrategy::StartsWith,
+            ),
+        ],
+    }));
+}
+
+#[test]
+fn not_group_inverts_the_nested_rule() {
+    assert!(matches(MatchingRule::Not {
+        not: Box::new(simple(
+            ApplicationIdentifier::Exe,
+            "other.exe",
+            MatchingStrategy::Equals,
+        )),
+    }));
+
+    assert!(!matches(MatchingRule::Not {
+        not: Box::new(simple(
+            ApplicationIdentifier::Exe,
+            "app.exe",
+            MatchingStrategy::Equals,
+        )),
+    }));
+}
+
+#[test]
+fn window_property_identifiers_are_matched() {
+    assert!(matches(simple(
+        ApplicationIdentifier::CommandLine,
+        "--profile work",
+        MatchingStrategy::Contains,
+    )));
+
+    assert!(matches(simple(
+        ApplicationIdentifier::ParentProcess,
+        "explorer.exe",
+        MatchingStrategy::Equals,
+    )));
+
+    assert!(matches(simple(
+        ApplicationIdentifier::Monitor,
+        "0x4C36",
+        MatchingStrategy::Equals,
+    )));
+
+    assert!(matches(simple(
+        ApplicationIdentifier::WindowStyle,
+        "THICKFRAME",
+        MatchingStrategy::Equals,
+    )));
+
+    assert!(matches(simple(
+        ApplicationIdentifier::ExtendedWindowStyle,
+        "TOOLWINDOW",
+        MatchingStrategy::Equals,
+    )));
+
+    assert!(!matches(simple(
+        ApplicationIdentifier::ExtendedWindowStyle,
+        "LAYERED",
+        MatchingStrategy::Equals,
+    )));
+}
+
+#[test]
+fn negated_strategies_must_hold_for_every_value() {
+    assert!(matches(simple(
+        ApplicationIdentifier::WindowStyle,
+        "POPUP",
+        MatchingStrategy::DoesNotEqual,
+    )));
+
+    assert!(!matches(simple(
+        ApplicationIdentifier::WindowStyle,
+        "CAPTION",
+        MatchingStrategy::DoesNotEqual,
+    )));
+}
+
+#[test]
+fn numeric_strategies_compare_window_size() {
+    assert!(matches(simple(
+        ApplicationIdentifier::Width,
+        "640",
+        MatchingStrategy::GreaterThan,
+    )));
+
+    assert!(!matches(simple(
+        ApplicationIdentifier::Width,
+        "800",
+        MatchingStrategy::GreaterThan,
+    )));
+
+    assert!(matches(simple(
+        ApplicationIdentifier::Height,
+        "720",
+        MatchingStrategy::LessThan,
+    )));
+
+    assert!(!matches(simple(
+        ApplicationIdentifier::Height,
+        "600",
+        MatchingStrategy::LessThan,
+    )));
+
+    assert!(!matches(simple(
+        ApplicationIdentifier::Height,
+        "tall",
+        MatchingStrategy::LessThan,
+    )));
+}
@@ -0,0 +1,82 @@
+use komorebi::core::config_generation::IdWithIdentifier;
+use komorebi::core::config_generation::MatchingStrategy;
+use komorebi::core::ApplicationIdentifier;
+use komorebi::should_act_individual;
+use komorebi::RuleTarget;
+use std::collections::HashMap;
+
+fn identifier(
+    kind: ApplicationIdentifier,
+    id: &str,
+    strategy: MatchingStrategy,
+) -> IdWithIdentifier {
+    IdWithIdentifier {
+        kind,
+        id: id.to_string(),
+        matching_strategy: Option::from(strategy),
+    }
+}
+
+fn target() -> RuleTarget {
+    RuleTarget::new(
+        String::from("Settings"),
+        String::from("app.exe"),
+        String::from("AppClass"),
+        String::from("C:\\app.exe"),
+    )
+}
+
+#[test]
+fn negated_strategies_hold_for_every_value() {
+    let target = target().with_monitor(vec![String::from("DELA0C8"), String::from("0x4C36")]);
+
+    assert!(should_act_individual(
+        &target,
+        &identifier(
+            ApplicationIdentifier::Monitor,
+            "GSM5B09",
+            MatchingStrategy::DoesNotEqual
+        ),
+        &HashMap::new()
+    ));
+
+    assert!(!should_act_individual(
+        &target,
+        &identifier(
+            ApplicationIdentifier::Monitor,
+            "0x4C36",
+            MatchingStrategy::DoesNotEqual
+        ),
+        &HashMap::new()
+    ));
+}
+
+#[test]
+fn unreadable_properties_never_match() {
+    // Without a window handle the command line and parent process can't be looked up, which is
+    // the same as failing to read them from an elevated process
+    let target = target();
+
+    for kind in [
+        ApplicationIdentifier::CommandLine,
+        ApplicationIdentifier::ParentProcess,
+    ] {
+        for strategy in [
+            MatchingStrategy::Equals,
+            MatchingStrategy::Contains,
+            MatchingStrategy::DoesNotEqual,
+            MatchingStrategy::DoesNotContain,
+            MatchingStrategy::DoesNotStartWith,
+            MatchingStrategy::DoesNotEndWith,
+        ] {
+            assert!(
+                !should_act_individual(
+                    &target,
+                    &identifier(kind, "app", strategy.clone()),
+                    &HashMap::new()
+                ),
+                "{kind:?} {strategy:?} should not match"
+            );
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use chrono::NaiveTime;
+use komorebi::KomorebiTheme;
+use komorebi::ScheduledTheme;
+use komorebi::ThemeSchedule;
+use std::path::PathBuf;
+
+fn theme(name: &str) -> KomorebiTheme {
+    KomorebiTheme::Custom {
+        path: PathBuf::from(format!("{name}.yaml")),
+        name: None,
+    }
+}
+
+fn schedule(themes: &[(&str, &str)]) -> ThemeSchedule {
+    ThemeSchedule::TimeOfDay {
+        themes: themes
+            .iter()
+            .map(|(from, name)| ScheduledTheme {
+                from: from.to_string(),
+                theme: theme(name),
+            })
+            .collect(),
+    }
+}
+
+fn at(time: &str) -> NaiveTime {
+    NaiveTime::parse_from_str(time, "%H:%M").unwrap()
+}
+
+#[test]
+fn time_of_day() {
+    let schedule = schedule(&[("07:00", "day"), ("19:00", "night")]);
+
+    assert_eq!(schedule.current(at("07:00")), Some(theme("day")));
+    assert_eq!(schedule.current(at("12:30")), Some(theme("day")));
+    assert_eq!(schedule.current(at("18:59")), Some(theme("day")));
+    assert_eq!(schedule.current(at("19:00")), Some(theme("night")));
+    assert_eq!(schedule.current(at("23:59")), Some(theme("night")));
+}
+
+#[test]
+fn wraps_around_midnight() {
+    let schedule = schedule(&[("19:00", "night"), ("07:00", "day")]);
+
+    assert_eq!(schedule.current(at("00:00")), Some(theme("night")));
+    assert_eq!(schedule.current(at("06:59")), Some(theme("night")));
+    assert_eq!(schedule.current(at("07:00")), Some(theme("day")));
+}
+
+#[test]
+fn single_theme_applies_all_day() {
+    let schedule = schedule(&[("12:00", "only")]);
+
+    assert_eq!(schedule.current(at("00:00")), Some(theme("only")));
+    assert_eq!(schedule.current(at("12:00")), Some(theme("only")));
+    assert_eq!(schedule.current(at("23:59")), Some(theme("only")));
+}
+
+#[test]
+fn empty_schedule() {
+    assert_eq!(schedule(&[]).current(at("12:00")), None);
+}
+
+#[test]
+fn invalid_times_are_rejected() {
+    assert!(schedule(&[("07:00", "day"), ("19:00", "night")])
+        .validate()
+        .is_ok());
+    assert!(schedule(&[("7pm", "night")]).validate().is_err());
+    assert!(schedule(&[("24:00", "night")]).validate().is_err());
+}
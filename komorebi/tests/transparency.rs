@@ -0,0 +1,128 @@
+use komorebi::transparency_manager::floating_default_alpha;
+use komorebi::transparency_manager::resolve_alpha;
+use komorebi::transparency_manager::TransparencyProfile;
+
+#[test]
+fn rule_takes_precedence() {
+    assert_eq!(
+        resolve_alpha(Some(100), Some(150), Some(200), None),
+        Some(100)
+    );
+    assert_eq!(
+        resolve_alpha(Some(255), Some(150), Some(200), None),
+        Some(255)
+    );
+}
+
+#[test]
+fn workspace_takes_precedence_over_default() {
+    assert_eq!(resolve_alpha(None, Some(150), Some(200), None), Some(150));
+    assert_eq!(resolve_alpha(None, None, Some(200), None), Some(200));
+}
+
+#[test]
+fn unset_without_default() {
+    assert_eq!(resolve_alpha(None, None, None, None), None);
+    assert_eq!(resolve_alpha(None, Some(150), None, None), Some(150));
+}
+
+#[test]
+fn dim_is_a_maximum() {
+    assert_eq!(
+        resolve_alpha(Some(220), None, Some(200), Some(100)),
+        Some(100)
+    );
+    assert_eq!(
+        resolve_alpha(Some(50), None, Some(200), Some(100)),
+        Some(50)
+    );
+    assert_eq!(
+        resolve_alpha(None, Some(255), Some(200), Some(100)),
+        Some(100)
+    );
+    assert_eq!(resolve_alpha(None, None, Some(255), Some(180)), Some(180));
+}
+
+/// The alpha value of a floating window in each focus state, as (focused, unfocused)
+fn floating_alpha(
+    rule: Option<TransparencyProfile>,
+    workspace: TransparencyProfile,
+    dim: Option<u8>,
+) -> (Option<u8>, Option<u8>) {
+    let default = floating_default_alpha(rule.as_ref(), &workspace, dim);
+    let resolve = |alpha: fn(&TransparencyProfile) -> Option<u8>| {
+        resolve_alpha(
+            rule.as_ref().and_then(alpha),
+            alpha(&workspace),
+            default,
+            dim,
+        )
+    };
+
+    (resolve(|p| p.focused), resolve(|p| p.floating))
+}
+
+#[test]
+fn floating_windows_are_left_unchanged_by_default() {
+    assert_eq!(
+        floating_alpha(None, TransparencyProfile::default(), None),
+        (None, None)
+    );
+}
+
+#[test]
+fn focused_floating_windows_are_opaque_when_only_floating_is_set() {
+    let floating = TransparencyProfile {
+        floating: Some(150),
+        ..Default::default()
+    };
+
+    assert_eq!(floating_alpha(None, floating, None), (Some(255), Some(150)));
+    assert_eq!(
+        floating_alpha(Some(floating), TransparencyProfile::default(), None),
+        (Some(255), Some(150))
+    );
+}
+
+#[test]
+fn unfocused_floating_windows_are_opaque_when_only_focused_is_set() {
+    let focused = TransparencyProfile {
+        focused: Some(230),
+        ..Default::default()
+    };
+
+    assert_eq!(floating_alpha(None, focused, None), (Some(230), Some(255)));
+    assert_eq!(
+        floating_alpha(Some(focused), TransparencyProfile::default(), None),
+        (Some(230), Some(255))
+    );
+}
+
+#[test]
+fn other_profile_values_leave_floating_windows_unchanged() {
+    let tiled = TransparencyProfile {
+        unfocused: Some(150),
+        monocle: Some(200),
+        ..Default::default()
+    };
+
+    assert_eq!(floating_alpha(Some(tiled), tiled, None), (None, None));
+}
+
+#[test]
+fn floating_windows_are_dimmed() {
+    assert_eq!(
+        floating_alpha(None, TransparencyProfile::default(), Some(100)),
+        (Some(100), Some(100))
+    );
+
+    let floating = TransparencyProfile {
+        floating: Some(80),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        floating_alpha(None, floating, Some(100)),
+        (Some(100), Some(80))
+    );
+}
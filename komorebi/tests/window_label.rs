@@ -0,0 +1,59 @@
+use komorebi::window_label::LabelTemplate;
+use komorebi::RuleTarget;
+
+fn render(template: &str) -> String {
+    let target = RuleTarget::new(
+        String::from("Mozilla Firefox"),
+        String::from("firefox.exe"),
+        String::from("MozillaWindowClass"),
+        String::from("C:\\Program Files\\Mozilla Firefox\\firefox.exe"),
+    );
+
+    LabelTemplate(template.to_string()).render(&target, 1)
+}
+
+#[test]
+fn placeholders() {
+    assert_eq!(render("{index}: {process}"), "2: firefox");
+    assert_eq!(
+        render("{exe} ({class})"),
+        "firefox.exe (MozillaWindowClass)"
+    );
+    assert_eq!(render("{ title }"), "Mozilla Firefox");
+}
+
+#[test]
+fn escaped_braces() {
+    assert_eq!(render("{{title}}"), "{title}");
+    assert_eq!(render("{{{title}}}"), "{Mozilla Firefox}");
+    assert_eq!(render("a }} b"), "a } b");
+    assert_eq!(render("a } b"), "a } b");
+}
+
+#[test]
+fn unknown_placeholders_are_kept() {
+    assert_eq!(render("{nope} {title}"), "{nope} Mozilla Firefox");
+    assert_eq!(render("{title:abc}"), "{title:abc}");
+    assert_eq!(render("{title:}"), "{title:}");
+}
+
+#[test]
+fn unclosed_braces_are_kept() {
+    assert_eq!(render("{title"), "{title");
+    assert_eq!(
+        render("{process} {title - {class"),
+        "firefox {title - {class"
+    );
+}
+
+#[test]
+fn limits() {
+    assert_eq!(render("{title:8}"), "Mozilla…");
+    assert_eq!(render("{title:-8}"), "…Firefox");
+    assert_eq!(render("{ title : 5 }"), "Mozi…");
+    assert_eq!(render("{title:1}"), "…");
+    assert_eq!(render("{title:-1}"), "…");
+    assert_eq!(render("{title:0}"), "");
+    assert_eq!(render("{title:15}"), "Mozilla Firefox");
+    assert_eq!(render("{title:-100}"), "Mozilla Firefox");
+}
@@ -26,6 +26,8 @@ use komorebi_client::resolve_home_path;
 use komorebi_client::send_message;
 use komorebi_client::send_query;
 use komorebi_client::ApplicationSpecificConfiguration;
+use komorebi_client::RuleTarget;
+use komorebi_client::Window;
 use lazy_static::lazy_static;
 use miette::NamedSource;
 use miette::Report;
@@ -751,8 +753,9 @@ struct AnimationFps {
 
 #[derive(Parser)]
 struct AnimationStyle {
-    /// Desired ease function for animation
-    #[clap(value_enum, short, long, default_value = "linear")]
+    /// Desired ease function for animation (e.g. ease-out-cubic, "cubic-bezier(0.25, 0.1, 0.25, 1.0)"
+    /// or "spring(170, 26, 1)")
+    #[clap(short, long, default_value = "linear")]
     style: komorebi_client::AnimationStyle,
     /// Animation type to apply the style to. If not specified, sets global style
     #[clap(value_enum, short, long)]
@@ -926,6 +929,31 @@ struct EnableAutostart {
     masir: bool,
 }
 
+#[derive(Parser)]
+struct Explain {
+    /// Window handle of an existing window to explain
+    #[clap(long, conflicts_with_all = ["exe", "class", "title", "path"])]
+    hwnd: Option<isize>,
+    /// Exe name of a synthetic window to explain
+    #[clap(long)]
+    exe: Option<String>,
+    /// Class of a synthetic window to explain
+    #[clap(long)]
+    class: Option<String>,
+    /// Title of a synthetic window to explain
+    #[clap(long)]
+    title: Option<String>,
+    /// Path of a synthetic window to explain
+    #[clap(long)]
+    path: Option<String>,
+    /// Path to a static configuration JSON file
+    #[clap(action, short, long)]
+    komorebi_config: Option<PathBuf>,
+    /// Print the decision trace as JSON
+    #[clap(long)]
+    json: bool,
+}
+
 #[derive(Parser)]
 struct Check {
     /// Path to a static configuration JSON file
@@ -966,6 +994,9 @@ enum SubCommand {
     Kill(Kill),
     /// Check komorebi configuration and related files for common errors
     Check(Check),
+    /// Explain which rules match a window (or a synthetic window) and why
+    #[clap(arg_required_else_help = true)]
+    Explain(Explain),
     /// Show the path to komorebi.json
     #[clap(alias = "config")]
     Configuration,
@@ -1721,6 +1752,31 @@ fn main() -> Result<()> {
                 }
             }
         }
+        SubCommand::Explain(args) => {
+            let static_config = args
+                .komorebi_config
+                .unwrap_or_else(|| HOME_DIR.join("komorebi.json"));
+
+            let config = StaticConfig::read(&static_config)?;
+
+            let target = match args.hwnd {
+                Some(hwnd) => RuleTarget::try_from(Window::from(hwnd))?,
+                None => RuleTarget::new(
+                    args.title.unwrap_or_default(),
+                    args.exe.unwrap_or_default(),
+                    args.class.unwrap_or_default(),
+                    args.path.unwrap_or_default(),
+                ),
+            };
+
+            let explanation = komorebi_client::explain(&config, &target)?;
+
+            if args.json {
+                println!("{}", serde_json::to_string_pretty(&explanation)?);
+            } else {
+                print!("{explanation}");
+            }
+        }
         SubCommand::Configuration => {
             let static_config = HOME_DIR.join("komorebi.json");
 